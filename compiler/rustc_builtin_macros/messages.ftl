@@ -232,8 +232,16 @@ builtin_macros_test_bad_fn = {$kind} functions cannot be used for tests
 
 builtin_macros_test_case_non_item = `#[test_case]` attribute is only allowed on items
 
-builtin_macros_test_runner_invalid = `test_runner` argument must be a path
-builtin_macros_test_runner_nargs = `#![test_runner(..)]` accepts exactly 1 argument
+builtin_macros_test_params_bench = `#[test_params]` cannot be used on benchmarks
+
+builtin_macros_test_params_count = `#[test_params]` must provide one argument for each parameter of the test function
+    .label = expected {$expected} {$expected ->
+        [one] argument
+        *[more] arguments
+    }, found {$found}
+
+builtin_macros_test_runner_invalid = `{$name}` argument must be a path
+builtin_macros_test_runner_nargs = `#![{$name}(..)]` accepts exactly 1 argument
 
 builtin_macros_tests_not_support = building tests with panic=abort is not supported without `-Zpanic_abort_tests`
 
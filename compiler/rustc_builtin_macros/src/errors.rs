@@ -684,6 +684,23 @@ pub(crate) struct TestBadFn {
     pub(crate) kind: &'static str,
 }
 
+#[derive(Diagnostic)]
+#[diag(builtin_macros_test_params_bench)]
+pub(crate) struct TestParamsBench {
+    #[primary_span]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(builtin_macros_test_params_count)]
+pub(crate) struct TestParamsCount {
+    #[primary_span]
+    #[label]
+    pub(crate) span: Span,
+    pub(crate) expected: usize,
+    pub(crate) found: usize,
+}
+
 #[derive(Diagnostic)]
 #[diag(builtin_macros_asm_explicit_register_name)]
 pub(crate) struct AsmExplicitRegisterName {
@@ -839,6 +856,7 @@ pub(crate) struct AsmOptAlreadyprovided {
 pub(crate) struct TestRunnerInvalid {
     #[primary_span]
     pub(crate) span: Span,
+    pub(crate) name: Symbol,
 }
 
 #[derive(Diagnostic)]
@@ -846,6 +864,7 @@ pub(crate) struct TestRunnerInvalid {
 pub(crate) struct TestRunnerNargs {
     #[primary_span]
     pub(crate) span: Span,
+    pub(crate) name: Symbol,
 }
 
 #[derive(Diagnostic)]
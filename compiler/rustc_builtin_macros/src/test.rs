@@ -105,7 +105,7 @@ pub fn expand_test_or_bench(
         return vec![];
     }
 
-    let (item, is_stmt) = match item {
+    let (mut item, is_stmt) = match item {
         Annotatable::Item(i) => (i, false),
         Annotatable::Stmt(stmt) if matches!(stmt.kind, ast::StmtKind::Item(_)) => {
             // FIXME: Use an 'if let' guard once they are implemented
@@ -130,24 +130,32 @@ pub fn expand_test_or_bench(
         };
     };
 
+    let has_params = attr::contains_name(&item.attrs, sym::test_params);
+
     // check_*_signature will report any errors in the type so compilation
     // will fail. We shouldn't try to expand in this case because the errors
     // would be spurious.
     let check_result = if is_bench {
         check_bench_signature(cx, &item, fn_)
     } else {
-        check_test_signature(cx, &item, fn_)
+        check_test_signature(cx, &item, fn_, has_params)
     };
-    if check_result.is_err() {
+    // The arguments to call the function with, once per generated test.
+    let cases = check_result.ok().and_then(|()| test_cases(cx, &item, fn_, is_bench));
+    let ret_ty_sp = cx.with_def_site_ctxt(fn_.sig.decl.output.span());
+
+    // `#[test_params]` is consumed here, so that the attributes left are
+    // reported as being on a function that isn't a test.
+    item.attrs.retain(|attr| !attr.has_name(sym::test_params));
+    let Some(cases) = cases else {
         return if is_stmt {
             vec![Annotatable::Stmt(P(cx.stmt_item(item.span, item)))]
         } else {
             vec![Annotatable::Item(item)]
         };
-    }
+    };
 
     let sp = cx.with_def_site_ctxt(item.span);
-    let ret_ty_sp = cx.with_def_site_ctxt(fn_.sig.decl.output.span());
     let attr_sp = cx.with_def_site_ctxt(attr_sp);
 
     let test_id = Ident::new(sym::test, attr_sp);
@@ -182,96 +190,106 @@ pub fn expand_test_or_bench(
     // creates $name: $expr
     let field = |name, expr| cx.field_imm(sp, Ident::from_str_and_span(name, sp), expr);
 
-    let test_fn = if is_bench {
-        // A simple ident for a lambda
-        let b = Ident::from_str_and_span("b", attr_sp);
+    let location_info = get_location_info(cx, &item);
 
-        cx.expr_call(
-            sp,
-            cx.expr_path(test_path("StaticBenchFn")),
-            thin_vec![
-                // |b| self::test::assert_test_result(
-                cx.lambda1(
+    let test_consts = cases
+        .into_iter()
+        .enumerate()
+        .map(|(i, args)| {
+            let test_fn = if is_bench {
+                // A simple ident for a lambda
+                let b = Ident::from_str_and_span("b", attr_sp);
+
+                cx.expr_call(
                     sp,
-                    cx.expr_call(
-                        sp,
-                        cx.expr_path(test_path("assert_test_result")),
-                        thin_vec![
-                            // super::$test_fn(b)
+                    cx.expr_path(test_path("StaticBenchFn")),
+                    thin_vec![
+                        // |b| self::test::assert_test_result(
+                        cx.lambda1(
+                            sp,
                             cx.expr_call(
-                                ret_ty_sp,
-                                cx.expr_path(cx.path(sp, vec![item.ident])),
-                                thin_vec![cx.expr_ident(sp, b)],
+                                sp,
+                                cx.expr_path(test_path("assert_test_result")),
+                                thin_vec![
+                                    // super::$test_fn(b)
+                                    cx.expr_call(
+                                        ret_ty_sp,
+                                        cx.expr_path(cx.path(sp, vec![item.ident])),
+                                        thin_vec![cx.expr_ident(sp, b)],
+                                    ),
+                                ],
                             ),
-                        ],
-                    ),
-                    b,
-                ), // )
-            ],
-        )
-    } else {
-        cx.expr_call(
-            sp,
-            cx.expr_path(test_path("StaticTestFn")),
-            thin_vec![
-                // || {
-                cx.lambda0(
+                            b,
+                        ), // )
+                    ],
+                )
+            } else {
+                cx.expr_call(
                     sp,
-                    // test::assert_test_result(
-                    cx.expr_call(
-                        sp,
-                        cx.expr_path(test_path("assert_test_result")),
-                        thin_vec![
-                            // $test_fn()
-                            cx.expr_call(
-                                ret_ty_sp,
-                                cx.expr_path(cx.path(sp, vec![item.ident])),
-                                ThinVec::new(),
-                            ), // )
-                        ],
-                    ), // }
-                ), // )
-            ],
-        )
-    };
-
-    let test_path_symbol = Symbol::intern(&item_path(
-        // skip the name of the root module
-        &cx.current_expansion.module.mod_path[1..],
-        &item.ident,
-    ));
-
-    let location_info = get_location_info(cx, &item);
-
-    let mut test_const =
-        cx.item(
-            sp,
-            Ident::new(item.ident.name, sp),
-            thin_vec![
-                // #[cfg(test)]
-                cx.attr_nested_word(sym::cfg, sym::test, attr_sp),
-                // #[rustc_test_marker = "test_case_sort_key"]
-                cx.attr_name_value_str(sym::rustc_test_marker, test_path_symbol, attr_sp),
-            ],
-            // const $ident: test::TestDescAndFn =
-            ast::ItemKind::Const(
-                ast::ConstItem {
-                    defaultness: ast::Defaultness::Final,
-                    generics: ast::Generics::default(),
-                    ty: cx.ty(sp, ast::TyKind::Path(None, test_path("TestDescAndFn"))),
-                    // test::TestDescAndFn {
-                    expr: Some(
-                        cx.expr_struct(
+                    cx.expr_path(test_path("StaticTestFn")),
+                    thin_vec![
+                        // || {
+                        cx.lambda0(
                             sp,
-                            test_path("TestDescAndFn"),
-                            thin_vec![
-                        // desc: test::TestDesc {
-                        field(
-                            "desc",
+                            // test::assert_test_result(
+                            cx.expr_call(
+                                sp,
+                                cx.expr_path(test_path("assert_test_result")),
+                                thin_vec![
+                                    // $test_fn($args)
+                                    cx.expr_call(
+                                        ret_ty_sp,
+                                        cx.expr_path(cx.path(sp, vec![item.ident])),
+                                        args,
+                                    ), // )
+                                ],
+                            ), // }
+                        ), // )
+                    ],
+                )
+            };
+
+            let mut test_name = item_path(
+                // skip the name of the root module
+                &cx.current_expansion.module.mod_path[1..],
+                &item.ident,
+            );
+            let mut test_ident = item.ident.name;
+            if has_params {
+                // Each case is a separate test named `path::to::test::case_N`
+                test_name.push_str(&format!("::case_{i}"));
+                test_ident = Symbol::intern(&format!("{}_case_{i}", item.ident.name));
+            }
+            let test_path_symbol = Symbol::intern(&test_name);
+
+            let mut test_const = cx.item(
+                sp,
+                Ident::new(test_ident, sp),
+                thin_vec![
+                    // #[cfg(test)]
+                    cx.attr_nested_word(sym::cfg, sym::test, attr_sp),
+                    // #[rustc_test_marker = "test_case_sort_key"]
+                    cx.attr_name_value_str(sym::rustc_test_marker, test_path_symbol, attr_sp),
+                ],
+                // const $ident: test::TestDescAndFn =
+                ast::ItemKind::Const(
+                    ast::ConstItem {
+                        defaultness: ast::Defaultness::Final,
+                        generics: ast::Generics::default(),
+                        ty: cx.ty(sp, ast::TyKind::Path(None, test_path("TestDescAndFn"))),
+                        // test::TestDescAndFn {
+                        expr: Some(
                             cx.expr_struct(
                                 sp,
-                                test_path("TestDesc"),
+                                test_path("TestDescAndFn"),
                                 thin_vec![
+                                    // desc: test::TestDesc {
+                                    field(
+                                        "desc",
+                                        cx.expr_struct(
+                                            sp,
+                                            test_path("TestDesc"),
+                                            thin_vec![
                                     // name: "path::to::test"
                                     field(
                                         "name",
@@ -346,45 +364,49 @@ pub fn expand_test_or_bench(
                                     ),
                                     // },
                                 ],
-                            ),
+                                        ),
+                                    ),
+                                    // testfn: test::StaticTestFn(...) | test::StaticBenchFn(...)
+                                    field("testfn", test_fn), // }
+                                ],
+                            ), // }
                         ),
-                        // testfn: test::StaticTestFn(...) | test::StaticBenchFn(...)
-                        field("testfn", test_fn), // }
-                    ],
-                        ), // }
-                    ),
-                }
-                .into(),
-            ),
-        );
-    test_const = test_const.map(|mut tc| {
-        tc.vis.kind = ast::VisibilityKind::Public;
-        tc
-    });
+                    }
+                    .into(),
+                ),
+            );
+            test_const = test_const.map(|mut tc| {
+                tc.vis.kind = ast::VisibilityKind::Public;
+                tc
+            });
+
+            debug!("synthetic test item:\n{}\n", pprust::item_to_string(&test_const));
+
+            test_const
+        })
+        .collect::<Vec<_>>();
 
     // extern crate test
     let test_extern = cx.item(sp, test_id, ast::AttrVec::new(), ast::ItemKind::ExternCrate(None));
 
-    debug!("synthetic test item:\n{}\n", pprust::item_to_string(&test_const));
-
     if is_stmt {
-        vec![
-            // Access to libtest under a hygienic name
-            Annotatable::Stmt(P(cx.stmt_item(sp, test_extern))),
-            // The generated test case
-            Annotatable::Stmt(P(cx.stmt_item(sp, test_const))),
+        // Access to libtest under a hygienic name
+        iter::once(test_extern)
+            // The generated test cases
+            .chain(test_consts)
             // The original item
-            Annotatable::Stmt(P(cx.stmt_item(sp, item))),
-        ]
+            .chain(iter::once(item))
+            .map(|i| Annotatable::Stmt(P(cx.stmt_item(sp, i))))
+            .collect()
     } else {
-        vec![
-            // Access to libtest under a hygienic name
-            Annotatable::Item(test_extern),
-            // The generated test case
-            Annotatable::Item(test_const),
+        // Access to libtest under a hygienic name
+        iter::once(test_extern)
+            // The generated test cases
+            .chain(test_consts)
             // The original item
-            Annotatable::Item(item),
-        ]
+            .chain(iter::once(item))
+            .map(Annotatable::Item)
+            .collect()
     }
 }
 
@@ -531,6 +553,7 @@ fn check_test_signature(
     cx: &ExtCtxt<'_>,
     i: &ast::Item,
     f: &ast::Fn,
+    has_params: bool,
 ) -> Result<(), ErrorGuaranteed> {
     let has_should_panic_attr = attr::contains_name(&i.attrs, sym::should_panic);
     let dcx = cx.dcx();
@@ -573,7 +596,9 @@ fn check_test_signature(
         _ => true,
     };
 
-    if !f.sig.decl.inputs.is_empty() {
+    // The arguments of parameterized tests are checked against `#[test_params]`
+    // in `test_cases`.
+    if !f.sig.decl.inputs.is_empty() && !has_params {
         return Err(dcx.span_err(i.span, "functions used as tests can not have any arguments"));
     }
 
@@ -591,6 +616,44 @@ fn check_test_signature(
     Ok(())
 }
 
+/// Returns the arguments to call the test function with, one list per test to
+/// generate. Each `#[test_params(arg1, arg2, ...)]` attribute adds a case; a
+/// function without any is called once, without arguments.
+fn test_cases(
+    cx: &mut ExtCtxt<'_>,
+    i: &ast::Item,
+    f: &ast::Fn,
+    is_bench: bool,
+) -> Option<Vec<ThinVec<P<ast::Expr>>>> {
+    let attrs = i.attrs.iter().filter(|attr| attr.has_name(sym::test_params)).collect::<Vec<_>>();
+    if attrs.is_empty() {
+        return Some(vec![ThinVec::new()]);
+    }
+    if is_bench {
+        cx.dcx().emit_err(errors::TestParamsBench { span: attrs[0].span });
+        return None;
+    }
+
+    let mut cases = Vec::with_capacity(attrs.len());
+    for attr in attrs {
+        // Malformed attributes are reported when validating builtin attributes.
+        let ast::AttrArgs::Delimited(args) = &attr.get_normal_item().args else {
+            return None;
+        };
+        let args = get_exprs_from_tts(cx, args.tokens.clone())?;
+        if args.len() != f.sig.decl.inputs.len() {
+            cx.dcx().emit_err(errors::TestParamsCount {
+                span: attr.span,
+                expected: f.sig.decl.inputs.len(),
+                found: args.len(),
+            });
+            return None;
+        }
+        cases.push(args.into());
+    }
+    Some(cases)
+}
+
 fn check_bench_signature(
     cx: &ExtCtxt<'_>,
     i: &ast::Item,
@@ -37,6 +37,8 @@ struct TestCtxt<'a> {
     test_cases: Vec<Test>,
     reexport_test_harness_main: Option<Symbol>,
     test_runner: Option<ast::Path>,
+    test_setup: Option<ast::Path>,
+    test_teardown: Option<ast::Path>,
 }
 
 /// Traverse the crate, collecting all the test functions, eliding any
@@ -58,9 +60,11 @@ pub fn inject(
     let reexport_test_harness_main =
         attr::first_attr_value_str_by_name(&krate.attrs, sym::reexport_test_harness_main);
 
-    // Do this here so that the test_runner crate attribute gets marked as used
-    // even in non-test builds
-    let test_runner = get_test_runner(dcx, krate);
+    // Do this here so that the test_runner, test_setup and test_teardown crate
+    // attributes get marked as used even in non-test builds
+    let test_runner = get_path_attr(dcx, krate, sym::test_runner);
+    let test_setup = get_path_attr(dcx, krate, sym::test_setup);
+    let test_teardown = get_path_attr(dcx, krate, sym::test_teardown);
 
     if sess.is_test_crate() {
         let panic_strategy = match (panic_strategy, sess.opts.unstable_opts.panic_abort_tests) {
@@ -84,6 +88,8 @@ pub fn inject(
             features,
             panic_strategy,
             test_runner,
+            test_setup,
+            test_teardown,
         )
     }
 }
@@ -233,6 +239,8 @@ fn generate_test_harness(
     features: &Features,
     panic_strategy: PanicStrategy,
     test_runner: Option<ast::Path>,
+    test_setup: Option<ast::Path>,
+    test_teardown: Option<ast::Path>,
 ) {
     let econfig = ExpansionConfig::default("test".to_string(), features);
     let ext_cx = ExtCtxt::new(sess, econfig, resolver, None);
@@ -256,6 +264,8 @@ fn generate_test_harness(
         test_cases: Vec::new(),
         reexport_test_harness_main,
         test_runner,
+        test_setup,
+        test_teardown,
     };
 
     TestHarnessGenerator { cx, tests: Vec::new() }.visit_crate(krate);
@@ -286,11 +296,15 @@ fn generate_test_harness(
 /// we remove the outer mark, and try resolving at its def-site, which will
 /// then resolve to `test_const`.
 ///
-/// The expansion here can be controlled by two attributes:
+/// The expansion here can be controlled by these attributes:
 ///
 /// [`TestCtxt::reexport_test_harness_main`] provides a different name for the `main`
 /// function and [`TestCtxt::test_runner`] provides a path that replaces
 /// `test::test_main_static`.
+///
+/// [`TestCtxt::test_setup`] and [`TestCtxt::test_teardown`] add calls to
+/// `test::register_setup` and `test::register_teardown` before the call to the
+/// test runner.
 fn mk_main(cx: &mut TestCtxt<'_>) -> P<ast::Item> {
     let sp = cx.def_site;
     let ecx = &cx.ext_cx;
@@ -313,6 +327,21 @@ fn mk_main(cx: &mut TestCtxt<'_>) -> P<ast::Item> {
     let call_test_main = ecx.expr_call(sp, test_main_path_expr, thin_vec![mk_tests_slice(cx, sp)]);
     let call_test_main = ecx.stmt_expr(call_test_main);
 
+    // test::register_setup(...) and test::register_teardown(...)
+    let register_hooks: ThinVec<_> =
+        [("register_setup", &cx.test_setup), ("register_teardown", &cx.test_teardown)]
+            .into_iter()
+            .filter_map(|(register_name, hook)| {
+                let mut hook = hook.clone()?;
+                hook.span = sp;
+                let register =
+                    ecx.path(sp, vec![test_id, Ident::from_str_and_span(register_name, sp)]);
+                let call =
+                    ecx.expr_call(sp, ecx.expr_path(register), thin_vec![ecx.expr_path(hook)]);
+                Some(ecx.stmt_expr(call))
+            })
+            .collect();
+
     // extern crate test
     let test_extern_stmt = ecx.stmt_item(
         sp,
@@ -327,12 +356,15 @@ fn mk_main(cx: &mut TestCtxt<'_>) -> P<ast::Item> {
     // pub fn main() { ... }
     let main_ret_ty = ecx.ty(sp, ast::TyKind::Tup(ThinVec::new()));
 
-    // If no test runner is provided we need to import the test crate
-    let main_body = if cx.test_runner.is_none() {
-        ecx.block(sp, thin_vec![test_extern_stmt, call_test_main])
-    } else {
-        ecx.block(sp, thin_vec![call_test_main])
-    };
+    // If no test runner is provided we need to import the test crate, which is
+    // also needed to register the setup and teardown functions
+    let mut main_stmts = ThinVec::new();
+    if cx.test_runner.is_none() || !register_hooks.is_empty() {
+        main_stmts.push(test_extern_stmt);
+    }
+    main_stmts.extend(register_hooks);
+    main_stmts.push(call_test_main);
+    let main_body = ecx.block(sp, main_stmts);
 
     let decl = ecx.fn_decl(ThinVec::new(), ast::FnRetTy::Ty(main_ret_ty));
     let sig = ast::FnSig { decl, header: ast::FnHeader::default(), span: sp };
@@ -389,19 +421,25 @@ fn get_test_name(i: &ast::Item) -> Option<Symbol> {
     attr::first_attr_value_str_by_name(&i.attrs, sym::rustc_test_marker)
 }
 
-fn get_test_runner(dcx: &rustc_errors::DiagCtxt, krate: &ast::Crate) -> Option<ast::Path> {
-    let test_attr = attr::find_by_name(&krate.attrs, sym::test_runner)?;
+/// Returns the path given to the crate attribute `name`, such as
+/// `#![test_runner(path)]`.
+fn get_path_attr(
+    dcx: &rustc_errors::DiagCtxt,
+    krate: &ast::Crate,
+    name: Symbol,
+) -> Option<ast::Path> {
+    let test_attr = attr::find_by_name(&krate.attrs, name)?;
     let meta_list = test_attr.meta_item_list()?;
     let span = test_attr.span;
     match &*meta_list {
         [single] => match single.meta_item() {
             Some(meta_item) if meta_item.is_word() => return Some(meta_item.path.clone()),
             _ => {
-                dcx.emit_err(errors::TestRunnerInvalid { span });
+                dcx.emit_err(errors::TestRunnerInvalid { span, name });
             }
         },
         _ => {
-            dcx.emit_err(errors::TestRunnerNargs { span });
+            dcx.emit_err(errors::TestRunnerNargs { span, name });
         }
     }
     None
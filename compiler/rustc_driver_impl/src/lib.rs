@@ -452,7 +452,7 @@ fn run_compiler(
             // This must run after monomorphization so that all generic types
             // have been instantiated.
             if sess.opts.unstable_opts.print_type_sizes {
-                sess.code_stats.print_type_sizes();
+                sess.code_stats.print_type_sizes(sess.opts.unstable_opts.print_type_sizes_format);
             }
 
             if sess.opts.unstable_opts.print_vtable_sizes {
@@ -424,6 +424,18 @@ pub struct BuiltinAttribute {
         test_runner, CrateLevel, template!(List: "path"), ErrorFollowing, custom_test_frameworks,
         "custom test frameworks are an unstable feature",
     ),
+    gated!(
+        test_setup, CrateLevel, template!(List: "path"), ErrorFollowing, test_hooks,
+        experimental!(test_setup)
+    ),
+    gated!(
+        test_teardown, CrateLevel, template!(List: "path"), ErrorFollowing, test_hooks,
+        experimental!(test_teardown)
+    ),
+    gated!(
+        test_params, Normal, template!(List: "arg1, arg2, ..."), DuplicatesOk, test_params,
+        experimental!(test_params)
+    ),
     // RFC #1268
     gated!(
         marker, Normal, template!(Word), WarnFollowing, @only_local: true,
@@ -578,6 +578,10 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, string_deref_patterns, "1.67.0", Some(87121)),
     /// Allows the use of `#[target_feature]` on safe functions.
     (unstable, target_feature_11, "1.45.0", Some(69098)),
+    /// Allows running functions before and after the tests with `#![test_setup]` and `#![test_teardown]`.
+    (unstable, test_hooks, "CURRENT_RUSTC_VERSION", None),
+    /// Allows running a `#[test]` function once for each set of arguments given with `#[test_params]`.
+    (unstable, test_params, "CURRENT_RUSTC_VERSION", None),
     /// Allows using `#[thread_local]` on `static` items.
     (unstable, thread_local, "1.0.0", Some(29594)),
     /// Allows defining `trait X = A + B;` alias items.
@@ -219,6 +219,7 @@ fn check_attributes(
                 sym::ignore | sym::should_panic => {
                     self.check_generic_attr(hir_id, attr, target, Target::Fn)
                 }
+                sym::test_params => self.check_test_params(attr.span),
                 sym::automatically_derived => {
                     self.check_generic_attr(hir_id, attr, target, Target::Impl)
                 }
@@ -425,6 +426,12 @@ fn check_generic_attr(
         }
     }
 
+    /// `#[test_params]` is removed by the `#[test]` macro of the function it is
+    /// on, so any that is left is on something that isn't a test.
+    fn check_test_params(&self, attr_span: Span) {
+        self.dcx().emit_err(errors::TestParamsWithoutTest { span: attr_span });
+    }
+
     /// Checks if `#[naked]` is applied to a function definition.
     fn check_naked(&self, hir_id: HirId, attr: &Attribute, span: Span, target: Target) -> bool {
         match target {
@@ -1464,6 +1464,13 @@ pub enum AttrApplication {
     },
 }
 
+#[derive(Diagnostic)]
+#[diag(passes_test_params_without_test)]
+pub struct TestParamsWithoutTest {
+    #[primary_span]
+    pub span: Span,
+}
+
 #[derive(Diagnostic)]
 #[diag(passes_transparent_incompatible, code = "E0692")]
 pub struct TransparentIncompatible {
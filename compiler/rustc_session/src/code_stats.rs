@@ -1,3 +1,4 @@
+use crate::config::PrintTypeSizesFormat;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::Lock;
 use rustc_span::def_id::DefId;
@@ -46,6 +47,25 @@ pub struct FieldInfo {
     pub align: u64,
 }
 
+impl SizeKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            SizeKind::Exact => "exact",
+            SizeKind::Min => "min",
+        }
+    }
+}
+
+/// The largest niche of a type, i.e. the invalid values of one of its scalars
+/// that can be used to store the discriminant of an enclosing enum.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NicheInfo {
+    pub offset: u64,
+    pub size: u64,
+    /// Number of values that are still available in the niche.
+    pub available: u128,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum DataTypeKind {
     Struct,
@@ -55,6 +75,18 @@ pub enum DataTypeKind {
     Coroutine,
 }
 
+impl DataTypeKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            DataTypeKind::Struct => "struct",
+            DataTypeKind::Union => "union",
+            DataTypeKind::Enum => "enum",
+            DataTypeKind::Closure => "closure",
+            DataTypeKind::Coroutine => "coroutine",
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, Debug)]
 pub struct TypeSizeInfo {
     pub kind: DataTypeKind,
@@ -63,6 +95,10 @@ pub struct TypeSizeInfo {
     pub overall_size: u64,
     pub packed: bool,
     pub opt_discr_size: Option<u64>,
+    /// Whether the discriminant is encoded in the niche of a variant's field
+    /// rather than stored in a separate tag.
+    pub niche_encoded_discr: bool,
+    pub opt_niche: Option<NicheInfo>,
     pub variants: Vec<VariantInfo>,
 }
 
@@ -100,6 +136,8 @@ pub fn record_type_size<S: ToString>(
         overall_size: Size,
         packed: bool,
         opt_discr_size: Option<Size>,
+        niche_encoded_discr: bool,
+        opt_niche: Option<NicheInfo>,
         mut variants: Vec<VariantInfo>,
     ) {
         // Sort variants so the largest ones are shown first. A stable sort is
@@ -117,6 +155,8 @@ pub fn record_type_size<S: ToString>(
             overall_size: overall_size.bytes(),
             packed,
             opt_discr_size: opt_discr_size.map(|s| s.bytes()),
+            niche_encoded_discr,
+            opt_niche,
             variants,
         };
         self.type_sizes.borrow_mut().insert(info);
@@ -130,7 +170,7 @@ pub fn record_vtable_size(&self, trait_did: DefId, trait_name: &str, info: VTabl
         );
     }
 
-    pub fn print_type_sizes(&self) {
+    pub fn print_type_sizes(&self, format: PrintTypeSizesFormat) {
         let type_sizes = self.type_sizes.borrow();
         // We will soon sort, so the initial order does not matter.
         #[allow(rustc::potential_query_instability)]
@@ -140,7 +180,14 @@ pub fn print_type_sizes(&self) {
         // Secondary sort: description (dictionary order)
         sorted.sort_by_key(|info| (cmp::Reverse(info.overall_size), &info.type_description));
 
-        for info in sorted {
+        match format {
+            PrintTypeSizesFormat::Text => Self::print_type_sizes_text(&sorted),
+            PrintTypeSizesFormat::Json => Self::print_type_sizes_json(&sorted),
+        }
+    }
+
+    fn print_type_sizes_text(sorted: &[&TypeSizeInfo]) {
+        for &info in sorted {
             let TypeSizeInfo { type_description, overall_size, align, kind, variants, .. } = info;
             println!(
                 "print-type-size type: `{type_description}`: {overall_size} bytes, alignment: {align} bytes"
@@ -228,6 +275,91 @@ pub fn print_type_sizes(&self) {
         }
     }
 
+    /// Prints one JSON object per type and per line, without the
+    /// `print-type-size` prefix of the text output, using the same order.
+    ///
+    /// Unlike the text output, the JSON output is not trimmed: padding is
+    /// reported for every field, and field alignment is always included.
+    fn print_type_sizes_json(sorted: &[&TypeSizeInfo]) {
+        for &info in sorted {
+            let TypeSizeInfo {
+                kind,
+                ref type_description,
+                align,
+                overall_size,
+                packed,
+                opt_discr_size,
+                niche_encoded_discr,
+                opt_niche,
+                ref variants,
+            } = *info;
+            let discr_size = opt_discr_size.unwrap_or(0);
+            let mut max_variant_size = discr_size;
+
+            let variants: Vec<_> = variants
+                .iter()
+                .map(|variant_info| {
+                    let VariantInfo { name, kind, align, size, ref fields } = *variant_info;
+                    max_variant_size = cmp::max(max_variant_size, size);
+
+                    // See `print_type_sizes_text` for why zero-sized fields
+                    // have to come first.
+                    let mut fields = fields.clone();
+                    fields.sort_by_key(|f| (f.offset, f.size));
+
+                    let mut min_offset = discr_size;
+                    let fields: Vec<_> = fields
+                        .into_iter()
+                        .map(|FieldInfo { kind, name, offset, size, align }| {
+                            let padding = offset.saturating_sub(min_offset);
+                            min_offset = cmp::max(min_offset, offset + size);
+                            serde_json::json!({
+                                "kind": kind.to_string(),
+                                "name": name.as_str(),
+                                "offset": offset,
+                                "size": size,
+                                "align": align,
+                                "padding": padding,
+                            })
+                        })
+                        .collect();
+
+                    serde_json::json!({
+                        "name": name.map(|name| name.to_string()),
+                        "size_kind": kind.as_str(),
+                        "size": size,
+                        "align": align,
+                        "fields": fields,
+                    })
+                })
+                .collect();
+
+            let niche = opt_niche.map(|NicheInfo { offset, size, available }| {
+                serde_json::json!({
+                    "offset": offset,
+                    "size": size,
+                    // Stringified, as it may not fit into the numbers JSON
+                    // consumers can represent losslessly.
+                    "available": available.to_string(),
+                })
+            });
+
+            let entry = serde_json::json!({
+                "type": type_description,
+                "kind": kind.as_str(),
+                "size": overall_size,
+                "align": align,
+                "packed": packed,
+                "discriminant_size": opt_discr_size,
+                "niche_encoded_discriminant": niche_encoded_discr,
+                "niche": niche,
+                "variants": variants,
+                "end_padding": overall_size.saturating_sub(max_variant_size),
+            });
+            println!("{entry}");
+        }
+    }
+
     pub fn print_vtable_sizes(&self, crate_name: Symbol) {
         // We will soon sort, so the initial order does not matter.
         #[allow(rustc::potential_query_instability)]
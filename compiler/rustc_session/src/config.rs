@@ -3471,6 +3471,15 @@ pub fn extension(self) -> &'static str {
     }
 }
 
+/// Which format to use for `-Z print-type-sizes`
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum PrintTypeSizesFormat {
+    /// Emit human readable text
+    Text,
+    /// Emit structured JSON, one object per type
+    Json,
+}
+
 /// `-Zpolonius` values, enabling the borrow checker polonius analysis, and which version: legacy,
 /// or future prototype.
 #[derive(Clone, Copy, PartialEq, Hash, Debug, Default)]
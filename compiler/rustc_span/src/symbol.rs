@@ -1645,8 +1645,12 @@
         test_2018_feature,
         test_accepted_feature,
         test_case,
+        test_hooks,
+        test_params,
         test_removed_feature,
         test_runner,
+        test_setup,
+        test_teardown,
         test_unstable_lint,
         thread,
         thread_local,
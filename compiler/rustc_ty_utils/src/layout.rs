@@ -9,7 +9,7 @@
 };
 use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::{self, AdtDef, EarlyBinder, GenericArgsRef, Ty, TyCtxt, TypeVisitableExt};
-use rustc_session::{DataTypeKind, FieldInfo, FieldKind, SizeKind, VariantInfo};
+use rustc_session::{DataTypeKind, FieldInfo, FieldKind, NicheInfo, SizeKind, VariantInfo};
 use rustc_span::symbol::Symbol;
 use rustc_target::abi::*;
 
@@ -936,6 +936,15 @@ fn record_layout_for_printing<'tcx>(cx: &LayoutCx<'tcx, TyCtxt<'tcx>>, layout: T
     // (delay format until we actually need it)
     let record = |kind, packed, opt_discr_size, variants| {
         let type_desc = with_no_trimmed_paths!(format!("{}", layout.ty));
+        let niche_encoded_discr = matches!(
+            layout.variants,
+            Variants::Multiple { tag_encoding: TagEncoding::Niche { .. }, .. }
+        );
+        let opt_niche = layout.largest_niche.map(|niche| NicheInfo {
+            offset: niche.offset.bytes(),
+            size: niche.value.size(cx).bytes(),
+            available: niche.available(cx),
+        });
         cx.tcx.sess.code_stats.record_type_size(
             kind,
             type_desc,
@@ -943,6 +952,8 @@ fn record_layout_for_printing<'tcx>(cx: &LayoutCx<'tcx, TyCtxt<'tcx>>, layout: T
             layout.size,
             packed,
             opt_discr_size,
+            niche_encoded_discr,
+            opt_niche,
             variants,
         );
     };
@@ -8,8 +8,11 @@
 #![stable(feature = "rust1", since = "1.0.0")]
 #![deny(unsafe_op_in_unsafe_fn)]
 
+mod temp;
 #[cfg(all(test, not(any(target_os = "emscripten", target_env = "sgx", target_os = "xous"))))]
 mod tests;
+mod walk;
+mod watcher;
 
 use crate::ffi::OsString;
 use crate::fmt;
@@ -21,6 +24,13 @@
 use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
 use crate::time::SystemTime;
 
+#[unstable(feature = "fs_temp", issue = "none")]
+pub use self::temp::{TempDir, TempFile};
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub use self::walk::{WalkDir, WalkEntry};
+#[unstable(feature = "fs_watcher", issue = "none")]
+pub use self::watcher::{WatchEvent, Watcher};
+
 /// An object providing access to an open file on the filesystem.
 ///
 /// An instance of a `File` can be read and/or written depending on what options
@@ -341,6 +351,57 @@ fn inner(path: &Path, contents: &[u8]) -> io::Result<()> {
     inner(path.as_ref(), contents.as_ref())
 }
 
+/// Writes a slice as the entire contents of a file, atomically.
+///
+/// Unlike [`write`], this never leaves a partially written file at `path`,
+/// not even if the process or the system crashes: the contents are written
+/// to a [`TempFile`] in the same directory, flushed to the disk, and then
+/// moved to `path`, replacing the previous file in a single step. Readers of
+/// `path` see either the previous contents or the new ones.
+///
+/// If `path` already exists, the new file gets the permissions of the
+/// previous one. Other metadata, such as the owner, isn't preserved, and
+/// neither are hard links to the previous file. If `path` is a symbolic
+/// link, the link itself is replaced.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be created in the directory of
+/// `path`, or any error returned by [`write`], [`File::sync_all`] or
+/// [`rename`]. `path` is then left untouched.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_write_atomic)]
+/// use std::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     fs::write_atomic("config.toml", "verbose = true\n")?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_write_atomic", issue = "none")]
+pub fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
+    fn inner(path: &Path, contents: &[u8]) -> io::Result<()> {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        // The file will be public once moved to `path`, so it gets the same
+        // permissions as with `write` from the start.
+        let mut file = TempFile::create(dir.to_owned(), false)?;
+        if let Ok(metadata) = metadata(path) {
+            file.as_file().set_permissions(metadata.permissions())?;
+        }
+        file.write_all(contents)?;
+        file.as_file().sync_all()?;
+        file.persist(path)?;
+        Ok(())
+    }
+    inner(path.as_ref(), contents.as_ref())
+}
+
 impl File {
     /// Attempts to open a file in read-only mode.
     ///
@@ -522,6 +583,204 @@ pub fn sync_data(&self) -> io::Result<()> {
         self.inner.datasync()
     }
 
+    /// Acquires an exclusive advisory lock on the file, blocking until it can
+    /// be acquired.
+    ///
+    /// While a file handle holds an exclusive lock, no other handle to the
+    /// same file can acquire an exclusive or shared lock. The lock is
+    /// *advisory*: it only coordinates callers of the locking methods, and
+    /// code that doesn't take the lock may still read and write the file,
+    /// though on some platforms such accesses fail while the lock is held.
+    ///
+    /// Locks are associated with the underlying file handle: they're shared
+    /// by handles obtained from [`try_clone`], and released when the last of
+    /// them is closed or [`unlock`] is called. If this handle already holds
+    /// a lock, the behavior of acquiring another one is unspecified and
+    /// platform dependent, and may deadlock.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_EX` flag, and the `LockFileEx` function on Windows with
+    /// the `LOCKFILE_EXCLUSIVE_LOCK` flag, locking the whole file. On Windows,
+    /// reads and writes through other handles fail while the lock is held.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// On other platforms, and on Unix platforms without `flock`, an error
+    /// of kind [`io::ErrorKind::Unsupported`] is returned.
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`try_clone`]: File::try_clone
+    /// [`unlock`]: File::unlock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.lock")?;
+    ///     f.lock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    #[doc(alias = "flock")]
+    #[doc(alias = "LockFileEx")]
+    pub fn lock(&self) -> io::Result<()> {
+        self.inner.lock()
+    }
+
+    /// Acquires a shared advisory lock on the file, blocking until it can be
+    /// acquired.
+    ///
+    /// Any number of file handles may hold a shared lock on the same file at
+    /// the same time, but no handle can acquire an exclusive lock while a
+    /// shared lock is held. See [`lock`] for the semantics shared by all
+    /// locking methods.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_SH` flag, and the `LockFileEx` function on Windows.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`lock`]: File::lock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.lock")?;
+    ///     f.lock_shared()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    #[doc(alias = "flock")]
+    #[doc(alias = "LockFileEx")]
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.inner.lock_shared()
+    }
+
+    /// Tries to acquire an exclusive advisory lock on the file without
+    /// blocking.
+    ///
+    /// Returns `Ok(false)` if another handle holds a lock on the file, and
+    /// `Ok(true)` if the lock was acquired. See [`lock`] for the semantics
+    /// shared by all locking methods.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_EX` and `LOCK_NB` flags, and the `LockFileEx` function
+    /// on Windows with the `LOCKFILE_EXCLUSIVE_LOCK` and
+    /// `LOCKFILE_FAIL_IMMEDIATELY` flags.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`lock`]: File::lock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.lock")?;
+    ///     if !f.try_lock()? {
+    ///         println!("waiting for another process to release the lock");
+    ///         f.lock()?;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    #[doc(alias = "flock")]
+    #[doc(alias = "LockFileEx")]
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.inner.try_lock()
+    }
+
+    /// Tries to acquire a shared advisory lock on the file without blocking.
+    ///
+    /// Returns `Ok(false)` if another handle holds an exclusive lock on the
+    /// file, and `Ok(true)` if the lock was acquired. See [`lock`] for the
+    /// semantics shared by all locking methods.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_SH` and `LOCK_NB` flags, and the `LockFileEx` function
+    /// on Windows with the `LOCKFILE_FAIL_IMMEDIATELY` flag.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`lock`]: File::lock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.lock")?;
+    ///     if f.try_lock_shared()? {
+    ///         println!("acquired a shared lock");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    #[doc(alias = "flock")]
+    #[doc(alias = "LockFileEx")]
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.inner.try_lock_shared()
+    }
+
+    /// Releases any lock held by this file handle.
+    ///
+    /// Unlocking a file that isn't locked succeeds. Locks are also released
+    /// when the last handle sharing them is closed, so calling this is only
+    /// needed to release a lock early. See [`lock`] for the semantics shared
+    /// by all locking methods.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_UN` flag, and the `UnlockFile` function on Windows.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`lock`]: File::lock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.lock")?;
+    ///     f.lock()?;
+    ///     f.unlock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    #[doc(alias = "flock")]
+    #[doc(alias = "UnlockFile")]
+    pub fn unlock(&self) -> io::Result<()> {
+        self.inner.unlock()
+    }
+
     /// Truncates or extends the underlying file, updating the size of
     /// this file to become `size`.
     ///
@@ -2452,6 +2711,54 @@ pub fn read_dir<P: AsRef<Path>>(path: P) -> io::Result<ReadDir> {
     fs_imp::readdir(path.as_ref()).map(ReadDir)
 }
 
+/// Returns an iterator over the entries within a directory and all of its
+/// subdirectories, recursively.
+///
+/// The iterator yields instances of <code>[io::Result]<[WalkEntry]></code>.
+/// The contents of each directory are returned right after the directory
+/// itself, and the directory at `path` isn't returned, as with [`read_dir`].
+/// New errors may be encountered after the iterator is created: an error
+/// reading a directory is returned in place of its contents, and the walk
+/// then carries on with the rest of the tree.
+///
+/// The walk can be customized with the methods of [`WalkDir`]: how deep it
+/// goes, whether it follows symbolic links, and in which order it returns
+/// the entries of each directory. [`WalkDir::prune`] skips the contents of
+/// a directory while walking.
+///
+/// # Platform-specific behavior
+///
+/// On most Unix platforms, subdirectories are opened relative to their
+/// parent with `openat` and `O_NOFOLLOW`, so that a directory that is
+/// replaced by a symbolic link during the walk isn't followed, as in
+/// [`remove_dir_all`]. Elsewhere, subdirectories are opened by path.
+/// **Note that this may change in the future.**
+///
+/// # Errors
+///
+/// The first item returned by the iterator is an error if `path` can't be
+/// read as a directory, in the same cases as with [`read_dir`].
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_walk_dir)]
+/// use std::fs;
+///
+/// // Finds the Rust files in `src` and in its subdirectories.
+/// for entry in fs::walk_dir("src") {
+///     let entry = entry?;
+///     if entry.file_type().is_file() && entry.path().extension() == Some("rs".as_ref()) {
+///         println!("{}", entry.path().display());
+///     }
+/// }
+/// # Ok::<_, std::io::Error>(())
+/// ```
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub fn walk_dir<P: AsRef<Path>>(path: P) -> WalkDir {
+    WalkDir::new(path.as_ref().to_owned())
+}
+
 /// Changes the permissions found on a file or a directory.
 ///
 /// # Platform-specific behavior
@@ -0,0 +1,403 @@
+use super::{remove_dir_all, remove_file, rename, File};
+use crate::env;
+use crate::fmt;
+use crate::io::{self, BorrowedCursor, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
+use crate::mem;
+use crate::path::{Path, PathBuf};
+use crate::sync::atomic::{AtomicU64, Ordering};
+use crate::sys::fs as fs_imp;
+use crate::sys::hashmap_random_keys;
+use crate::sys_common::{AsInner, FromInner};
+
+/// A temporary file, which is removed when dropped unless it's
+/// [persisted](TempFile::persist).
+///
+/// The file is created with a random name that doesn't exist yet and, on
+/// Unix, with permissions that only let its owner access it. It's opened for
+/// reading and writing, with [`Read`], [`Write`] and [`Seek`] implemented for
+/// both `TempFile` and `&TempFile`, and the rest of the methods of [`File`]
+/// available through [`as_file`].
+///
+/// [`as_file`]: TempFile::as_file
+///
+/// # Platform-specific behavior
+///
+/// On Linux, the file is created with the `O_TMPFILE` flag when the
+/// filesystem supports it, in which case it never has a name until it's
+/// persisted: it's then removed even if the process is killed before the
+/// `TempFile` is dropped. Elsewhere, the file is created with a random name
+/// in the directory, as with [`OpenOptions::create_new`], and a process that
+/// is killed can leave it behind.
+/// **Note that this may change in the future.**
+///
+/// [`OpenOptions::create_new`]: super::OpenOptions::create_new
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_temp)]
+/// use std::fs::TempFile;
+/// use std::io::{Read, Seek, Write};
+///
+/// let mut file = TempFile::new()?;
+/// file.write_all(b"scratch data")?;
+/// file.rewind()?;
+/// let mut data = String::new();
+/// file.read_to_string(&mut data)?;
+/// assert_eq!(data, "scratch data");
+/// # Ok::<_, std::io::Error>(())
+/// ```
+#[unstable(feature = "fs_temp", issue = "none")]
+pub struct TempFile {
+    file: File,
+    /// `None` if the file was created without a name.
+    path: Option<TempPath>,
+    dir: PathBuf,
+}
+
+/// The name of a temporary file, which is removed when dropped.
+#[derive(Debug)]
+struct TempPath(PathBuf);
+
+impl TempPath {
+    fn keep(mut self) -> PathBuf {
+        let path = mem::take(&mut self.0);
+        mem::forget(self);
+        path
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = remove_file(&self.0);
+    }
+}
+
+impl TempFile {
+    /// Creates a temporary file in the directory returned by
+    /// [`env::temp_dir`].
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn new() -> io::Result<TempFile> {
+        TempFile::create(env::temp_dir(), true)
+    }
+
+    /// Creates a temporary file in `dir`.
+    ///
+    /// A file can only be [persisted](TempFile::persist) to the filesystem
+    /// it was created on, which is why this is useful: creating the file
+    /// next to its final path guarantees that it can be moved there.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `dir` isn't a directory, or if a file can't be
+    /// created in it.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn new_in<P: AsRef<Path>>(dir: P) -> io::Result<TempFile> {
+        TempFile::create(dir.as_ref().to_owned(), true)
+    }
+
+    pub(super) fn create(dir: PathBuf, private: bool) -> io::Result<TempFile> {
+        if let Some(file) = fs_imp::create_unnamed(&dir, private)? {
+            return Ok(TempFile { file: File::from_inner(file), path: None, dir });
+        }
+        let (path, file) = create_named(&dir, private)?;
+        Ok(TempFile { file, path: Some(path), dir })
+    }
+
+    /// Returns a reference to the underlying file.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    #[must_use]
+    pub fn as_file(&self) -> &File {
+        &self.file
+    }
+
+    /// Returns a mutable reference to the underlying file.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    #[must_use]
+    pub fn as_file_mut(&mut self) -> &mut File {
+        &mut self.file
+    }
+
+    /// Moves the temporary file to `path`, atomically replacing the file
+    /// that is there if there is one, and returns the underlying file, which
+    /// is no longer removed on drop.
+    ///
+    /// This doesn't flush the contents of the file to the disk: call
+    /// [`File::sync_all`] first if they should survive a crash.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` is on another filesystem than the
+    /// temporary file, is a directory, or can't be written to. The temporary
+    /// file is then removed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_temp)]
+    /// use std::fs::TempFile;
+    /// use std::io::Write;
+    ///
+    /// let mut file = TempFile::new_in("cache")?;
+    /// file.write_all(b"expensive result")?;
+    /// file.as_file().sync_all()?;
+    /// // Readers see either the old cache or the new one, never a partial one.
+    /// file.persist("cache/result")?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn persist<P: AsRef<Path>>(self, path: P) -> io::Result<File> {
+        let (temp_path, file) = match self.path {
+            Some(temp_path) => (temp_path, self.file),
+            None => name_unnamed(self.file, &self.dir)?,
+        };
+        rename(&temp_path.0, path.as_ref())?;
+        temp_path.keep();
+        Ok(file)
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl fmt::Debug for TempFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self.path.as_ref().map(|path| &path.0);
+        f.debug_struct("TempFile").field("file", &self.file).field("path", &path).finish()
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Read for TempFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self).read(buf)
+    }
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        (&*self).read_vectored(bufs)
+    }
+    fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> io::Result<()> {
+        (&*self).read_buf(cursor)
+    }
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        (&&*self).is_read_vectored()
+    }
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        (&*self).read_to_end(buf)
+    }
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        (&*self).read_to_string(buf)
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Read for &TempFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&self.file).read(buf)
+    }
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        (&self.file).read_vectored(bufs)
+    }
+    fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> io::Result<()> {
+        (&self.file).read_buf(cursor)
+    }
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        (&self.file).is_read_vectored()
+    }
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        (&self.file).read_to_end(buf)
+    }
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        (&self.file).read_to_string(buf)
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Write for TempFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self).write(buf)
+    }
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (&*self).write_vectored(bufs)
+    }
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        (&&*self).is_write_vectored()
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self).flush()
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Write for &TempFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&self.file).write(buf)
+    }
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (&self.file).write_vectored(bufs)
+    }
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        (&self.file).is_write_vectored()
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        (&self.file).flush()
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Seek for TempFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        (&*self).seek(pos)
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Seek for &TempFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        (&self.file).seek(pos)
+    }
+}
+
+/// A temporary directory, which is removed along with its contents when
+/// dropped unless it's [kept](TempDir::keep).
+///
+/// The directory is created with a random name that doesn't exist yet and,
+/// on Unix, with permissions that only let its owner access it.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_temp)]
+/// use std::fs::{self, TempDir};
+///
+/// let dir = TempDir::new()?;
+/// fs::write(dir.path().join("input.txt"), "test input")?;
+/// // ... run the code under test in `dir` ...
+/// // `dir` and `input.txt` are removed here.
+/// # Ok::<_, std::io::Error>(())
+/// ```
+#[unstable(feature = "fs_temp", issue = "none")]
+#[derive(Debug)]
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Creates a temporary directory in the directory returned by
+    /// [`env::temp_dir`].
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn new() -> io::Result<TempDir> {
+        TempDir::new_in(env::temp_dir())
+    }
+
+    /// Creates a temporary directory in `dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `dir` isn't a directory, or if a directory can't
+    /// be created in it.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn new_in<P: AsRef<Path>>(dir: P) -> io::Result<TempDir> {
+        let (path, ()) = with_random_path(dir.as_ref(), fs_imp::create_dir_private)?;
+        Ok(TempDir { path })
+    }
+
+    /// Returns the path of the temporary directory.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the path of the temporary directory, which is no longer
+    /// removed on drop.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    #[must_use = "the directory is kept even if the path is unused"]
+    pub fn keep(mut self) -> PathBuf {
+        let path = mem::take(&mut self.path);
+        mem::forget(self);
+        path
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = remove_dir_all(&self.path);
+    }
+}
+
+/// Creates a file with a random name in `dir`.
+fn create_named(dir: &Path, private: bool) -> io::Result<(TempPath, File)> {
+    let (path, file) = with_random_path(dir, |path| fs_imp::create_new(path, private))?;
+    Ok((TempPath(path), File::from_inner(file)))
+}
+
+/// Gives a random name in `dir` to a file created without one, returning the
+/// file that has the name.
+fn name_unnamed(file: File, dir: &Path) -> io::Result<(TempPath, File)> {
+    // Links can't replace existing files, so the file is linked to a random
+    // path first, and then renamed like named files.
+    let linked = with_random_path(dir, |path| fs_imp::link_unnamed(file.as_inner(), path));
+    if let Ok((path, ())) = linked {
+        return Ok((TempPath(path), file));
+    }
+
+    // Linking isn't always allowed, in which case the contents are copied to
+    // a named file instead, with the same permissions and position.
+    let (path, named) = create_named(dir, true)?;
+    named.set_permissions(file.metadata()?.permissions())?;
+    let pos = (&file).stream_position()?;
+    (&file).rewind()?;
+    io::copy(&mut &file, &mut &named)?;
+    (&named).seek(SeekFrom::Start(pos))?;
+    Ok((path, named))
+}
+
+/// Calls `f` with random paths in `dir`, until it doesn't fail because the
+/// path already exists.
+fn with_random_path<T>(
+    dir: &Path,
+    mut f: impl FnMut(&Path) -> io::Result<T>,
+) -> io::Result<(PathBuf, T)> {
+    // Each attempt picks one of 36^10 names, so this only fails if something
+    // other than a full directory is wrong, e.g. broken randomness.
+    const ATTEMPTS: u32 = 1 << 16;
+    for _ in 0..ATTEMPTS {
+        let path = dir.join(random_name());
+        match f(&path) {
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+            result => return result.map(|t| (path, t)),
+        }
+    }
+    Err(io::const_io_error!(
+        io::ErrorKind::AlreadyExists,
+        "too many temporary files exist with the same name",
+    ))
+}
+
+fn random_name() -> String {
+    // Some platforms have no randomness, so a counter makes sure that every
+    // attempt has a different name anyway.
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let (k0, k1) = hashmap_random_keys();
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let mut n = k0 ^ k1.rotate_left(32) ^ count.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+
+    // Lowercase only, for case-insensitive filesystems.
+    const CHARS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    let mut name = String::from(".tmp");
+    for _ in 0..10 {
+        name.push(CHARS[(n % 36) as usize] as char);
+        n /= 36;
+    }
+    name
+}
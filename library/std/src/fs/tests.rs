@@ -1820,3 +1820,386 @@ fn test_hidden_file_truncation() {
     let metadata = file.metadata().unwrap();
     assert_eq!(metadata.len(), 0);
 }
+
+#[test]
+#[cfg(any(windows, target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+fn file_lock_multiple_handles() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("file.lock");
+    let f1 = check!(File::create(&path));
+    let f2 = check!(File::open(&path));
+
+    // Exclusive locks exclude every other lock.
+    check!(f1.lock());
+    assert!(!check!(f2.try_lock()));
+    assert!(!check!(f2.try_lock_shared()));
+    check!(f1.unlock());
+    assert!(check!(f2.try_lock()));
+    check!(f2.unlock());
+
+    // Shared locks only exclude exclusive locks.
+    check!(f1.lock_shared());
+    assert!(check!(f2.try_lock_shared()));
+    check!(f2.unlock());
+    assert!(!check!(f2.try_lock()));
+    check!(f1.unlock());
+
+    // Unlocking a file that isn't locked is fine.
+    check!(f1.unlock());
+}
+
+#[test]
+#[cfg(any(windows, target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+fn file_lock_released_on_close() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("file.lock");
+    let f1 = check!(File::create(&path));
+    let f2 = check!(File::open(&path));
+
+    assert!(check!(f1.try_lock()));
+    assert!(!check!(f2.try_lock_shared()));
+    drop(f1);
+    assert!(check!(f2.try_lock()));
+}
+
+#[test]
+#[cfg(windows)]
+fn file_lock_overlapped_handle() {
+    const FILE_FLAG_OVERLAPPED: u32 = 0x40000000;
+
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("file.lock");
+    let f1 = check!(File::create(&path));
+    let f2 = check!(OpenOptions::new().read(true).custom_flags(FILE_FLAG_OVERLAPPED).open(&path));
+
+    // The requests that can't complete immediately are cancelled.
+    check!(f1.lock());
+    assert!(!check!(f2.try_lock()));
+    assert!(!check!(f2.try_lock_shared()));
+    check!(f1.unlock());
+
+    check!(f2.lock());
+    assert!(!check!(f1.try_lock_shared()));
+    check!(f2.unlock());
+    assert!(check!(f1.try_lock()));
+}
+
+/// Waits for `expected` to be reported by `watcher`, skipping other events.
+fn expect_watch_event(watcher: &mut fs::Watcher, expected: fs::WatchEvent) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let mut seen = Vec::new();
+    while let Some(timeout) = deadline.checked_duration_since(Instant::now()) {
+        match check!(watcher.next_event_timeout(timeout)) {
+            Some(event) if event == expected => return,
+            Some(event) => seen.push(event),
+            None => break,
+        }
+    }
+    panic!("expected {expected:?}, got {seen:?}");
+}
+
+#[test]
+fn watcher_polling() {
+    use fs::WatchEvent::*;
+
+    let tmpdir = tmpdir();
+    let dir = tmpdir.join("watched");
+    check!(fs::create_dir(&dir));
+    let file = dir.join("file");
+    let mut watcher = fs::Watcher::polling(Duration::from_millis(10));
+    check!(watcher.watch(&dir));
+    assert_eq!(check!(watcher.next_event_timeout(Duration::ZERO)), None);
+
+    check!(File::create(&file));
+    expect_watch_event(&mut watcher, Create(file.clone()));
+    check!(fs::write(&file, b"hello"));
+    expect_watch_event(&mut watcher, Modify(file.clone()));
+    check!(fs::remove_file(&file));
+    expect_watch_event(&mut watcher, Remove(file.clone()));
+
+    check!(watcher.unwatch(&dir));
+    assert_eq!(watcher.unwatch(&dir).unwrap_err().kind(), ErrorKind::NotFound);
+    check!(File::create(&file));
+    assert_eq!(check!(watcher.next_event_timeout(Duration::from_millis(50))), None);
+}
+
+#[test]
+fn watcher_recursive() {
+    use fs::WatchEvent::*;
+
+    let tmpdir = tmpdir();
+    let dir = tmpdir.join("watched");
+    check!(fs::create_dir(&dir));
+    let mut watchers =
+        [fs::Watcher::polling(Duration::from_millis(10)), check!(fs::Watcher::new())];
+    for watcher in &mut watchers {
+        check!(watcher.watch_recursive(&dir));
+    }
+
+    // Directories created after the watch started are watched too.
+    let sub = dir.join("a").join("b");
+    check!(fs::create_dir_all(&sub));
+    let file = sub.join("file");
+    check!(fs::write(&file, b"hello"));
+    for watcher in &mut watchers {
+        expect_watch_event(watcher, Create(file.clone()));
+    }
+    check!(fs::write(&file, b"hello, world"));
+    for watcher in &mut watchers {
+        expect_watch_event(watcher, Modify(file.clone()));
+    }
+    check!(fs::remove_dir_all(dir.join("a")));
+    for watcher in &mut watchers {
+        expect_watch_event(watcher, Remove(file.clone()));
+        expect_watch_event(watcher, Remove(dir.join("a")));
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn watcher_native_rename() {
+    use fs::WatchEvent::*;
+
+    let tmpdir = tmpdir();
+    let dir = tmpdir.join("watched");
+    check!(fs::create_dir(&dir));
+    let mut watcher = check!(fs::Watcher::new());
+    check!(watcher.watch_recursive(&dir));
+
+    // Renames within the watched directories are reported as such, and
+    // the watches follow renamed directories.
+    let (from, to) = (dir.join("from"), dir.join("to"));
+    check!(fs::create_dir(&from));
+    expect_watch_event(&mut watcher, Create(from.clone()));
+    check!(fs::rename(&from, &to));
+    expect_watch_event(&mut watcher, Rename { from, to: to.clone() });
+    check!(File::create(to.join("file")));
+    expect_watch_event(&mut watcher, Create(to.join("file")));
+
+    // Renames out of them are reported as removals.
+    check!(fs::rename(&to, tmpdir.join("outside")));
+    expect_watch_event(&mut watcher, Remove(to));
+    check!(File::create(tmpdir.join("outside").join("other")));
+    assert_eq!(check!(watcher.next_event_timeout(Duration::from_millis(50))), None);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn watcher_native_overlapping() {
+    use fs::WatchEvent::*;
+
+    let tmpdir = tmpdir();
+    let dir = tmpdir.join("watched");
+    let sub = dir.join("sub");
+    check!(fs::create_dir_all(&sub));
+    let mut watcher = check!(fs::Watcher::new());
+    check!(watcher.watch_recursive(&dir));
+    check!(watcher.watch(&sub));
+
+    // Both paths share the watch of `sub`, which is kept until neither of
+    // them is watched.
+    check!(watcher.unwatch(&sub));
+    check!(File::create(sub.join("a")));
+    expect_watch_event(&mut watcher, Create(sub.join("a")));
+
+    check!(watcher.watch(&sub));
+    check!(watcher.unwatch(&dir));
+    check!(File::create(sub.join("b")));
+    expect_watch_event(&mut watcher, Create(sub.join("b")));
+
+    // `sub` isn't watched recursively anymore.
+    let nested = sub.join("nested");
+    check!(fs::create_dir(&nested));
+    expect_watch_event(&mut watcher, Create(nested.clone()));
+    check!(File::create(nested.join("file")));
+    assert_eq!(check!(watcher.next_event_timeout(Duration::from_millis(50))), None);
+}
+
+fn dir_entries(dir: &Path) -> Vec<String> {
+    let mut entries: Vec<_> = check!(fs::read_dir(dir))
+        .map(|entry| check!(entry).file_name().into_string().unwrap())
+        .collect();
+    entries.sort();
+    entries
+}
+
+#[test]
+fn temp_file_removed_on_drop() {
+    let tmpdir = tmpdir();
+    let mut file = check!(fs::TempFile::new_in(tmpdir.path()));
+    check!(file.write_all(b"hello"));
+    check!(file.rewind());
+    let mut contents = String::new();
+    check!(file.read_to_string(&mut contents));
+    assert_eq!(contents, "hello");
+    drop(file);
+    assert!(dir_entries(tmpdir.path()).is_empty());
+}
+
+#[test]
+fn temp_file_persist() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("file");
+    check!(fs::write(&path, b"old"));
+
+    let mut file = check!(fs::TempFile::new_in(tmpdir.path()));
+    check!(file.write_all(b"new"));
+    let mut file = check!(file.persist(&path));
+    check!(file.write_all(b" and improved"));
+    drop(file);
+    assert_eq!(check!(fs::read_to_string(&path)), "new and improved");
+    assert_eq!(dir_entries(tmpdir.path()), ["file"]);
+
+    // Persisting to a directory fails, and removes the temporary file.
+    let file = check!(fs::TempFile::new_in(tmpdir.path()));
+    check!(fs::create_dir(tmpdir.join("dir")));
+    assert!(file.persist(tmpdir.join("dir")).is_err());
+    assert_eq!(dir_entries(tmpdir.path()), ["dir", "file"]);
+}
+
+#[test]
+fn temp_dir() {
+    let tmpdir = tmpdir();
+    let dir = check!(fs::TempDir::new_in(tmpdir.path()));
+    assert!(dir.path().starts_with(tmpdir.path()));
+    check!(fs::create_dir(dir.path().join("sub")));
+    check!(fs::write(dir.path().join("sub").join("file"), b"hello"));
+    drop(dir);
+    assert!(dir_entries(tmpdir.path()).is_empty());
+
+    let path = check!(fs::TempDir::new_in(tmpdir.path())).keep();
+    assert!(path.is_dir());
+    assert_ne!(check!(fs::TempDir::new_in(tmpdir.path())).path(), path);
+}
+
+#[test]
+fn write_atomic() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("file");
+    check!(fs::write_atomic(&path, b"old"));
+    assert_eq!(check!(fs::read_to_string(&path)), "old");
+    check!(fs::write_atomic(&path, b"new"));
+    assert_eq!(check!(fs::read_to_string(&path)), "new");
+    assert_eq!(dir_entries(tmpdir.path()), ["file"]);
+}
+
+#[test]
+#[cfg(unix)]
+fn temp_file_permissions() {
+    use crate::os::unix::fs::PermissionsExt;
+
+    let tmpdir = tmpdir();
+    let file = check!(fs::TempFile::new_in(tmpdir.path()));
+    assert_eq!(check!(file.as_file().metadata()).permissions().mode() & 0o777, 0o600);
+    let dir = check!(fs::TempDir::new_in(tmpdir.path()));
+    assert_eq!(check!(fs::metadata(dir.path())).permissions().mode() & 0o777, 0o700);
+
+    // `write_atomic` keeps the permissions of the file it replaces.
+    let path = tmpdir.join("file");
+    check!(fs::write(&path, b"old"));
+    check!(fs::set_permissions(&path, fs::Permissions::from_mode(0o640)));
+    check!(fs::write_atomic(&path, b"new"));
+    assert_eq!(check!(fs::metadata(&path)).permissions().mode() & 0o777, 0o640);
+}
+
+/// Walks `walk`, and returns the paths relative to `root` with their depths.
+fn walked(root: &Path, walk: fs::WalkDir) -> Vec<(String, usize)> {
+    walk.map(|entry| {
+        let entry = check!(entry);
+        let path = entry.path().strip_prefix(root).unwrap().to_str().unwrap().replace('\\', "/");
+        (path, entry.depth())
+    })
+    .collect()
+}
+
+fn by_name(a: &fs::WalkEntry, b: &fs::WalkEntry) -> crate::cmp::Ordering {
+    a.file_name().cmp(&b.file_name())
+}
+
+#[test]
+fn walk_dir() {
+    let tmpdir = tmpdir();
+    let root = tmpdir.path();
+    check!(fs::create_dir_all(root.join("a/b")));
+    check!(fs::create_dir_all(root.join("a/c")));
+    check!(fs::write(root.join("a/b/file"), b""));
+    check!(fs::write(root.join("d"), b""));
+
+    let tree = [("a", 1), ("a/b", 2), ("a/b/file", 3), ("a/c", 2), ("d", 1)];
+    let mut unsorted = walked(root, fs::walk_dir(root));
+    unsorted.sort();
+    assert_eq!(unsorted, tree.map(|(path, depth)| (path.to_owned(), depth)));
+    assert_eq!(walked(root, fs::walk_dir(root).sort_by(by_name)), unsorted);
+
+    let walk = fs::walk_dir(root).sort_by(by_name).min_depth(2).max_depth(2);
+    assert_eq!(walked(root, walk), [("a/b".to_owned(), 2), ("a/c".to_owned(), 2)]);
+
+    let mut walk = fs::walk_dir(root).sort_by(by_name);
+    let mut pruned = Vec::new();
+    while let Some(entry) = walk.next() {
+        let entry = check!(entry);
+        if entry.file_name() == "b" {
+            walk.prune();
+        }
+        pruned.push(entry.file_name());
+    }
+    assert_eq!(pruned, ["a", "b", "c", "d"]);
+
+    let mut walk = fs::walk_dir(root.join("d"));
+    assert!(walk.next().unwrap().is_err());
+    assert!(walk.next().is_none());
+}
+
+#[test]
+fn walk_dir_symlinks() {
+    let tmpdir = tmpdir();
+    if !got_symlink_permission(&tmpdir) {
+        return;
+    };
+    let root = tmpdir.path();
+    check!(fs::create_dir_all(root.join("dir/sub")));
+    check!(fs::create_dir_all(root.join("outside")));
+    check!(fs::write(root.join("outside/file"), b""));
+    check!(symlink_dir(root.join("outside"), root.join("dir/link")));
+
+    let walk = fs::walk_dir(root.join("dir")).sort_by(by_name);
+    assert_eq!(walked(&root.join("dir"), walk), [("link".to_owned(), 1), ("sub".to_owned(), 1)]);
+    let walk = fs::walk_dir(root.join("dir")).sort_by(by_name).follow_links(true);
+    assert_eq!(
+        walked(&root.join("dir"), walk),
+        [("link".to_owned(), 1), ("link/file".to_owned(), 2), ("sub".to_owned(), 1)]
+    );
+
+    // Links to a parent directory are reported as errors instead of looping.
+    check!(symlink_dir(root.join("dir"), root.join("dir/sub/parent")));
+    let walk = fs::walk_dir(root.join("dir")).sort_by(by_name).follow_links(true);
+    let results: Vec<_> = walk.map(|entry| entry.map(|entry| entry.file_name())).collect();
+    assert_eq!(results.len(), 5);
+    assert_eq!(results[3].as_ref().unwrap(), "parent");
+    assert_eq!(results[4].as_ref().unwrap_err().kind(), ErrorKind::FilesystemLoop);
+}
+
+#[test]
+fn walk_dir_symlink_cycle() {
+    let tmpdir = tmpdir();
+    if !got_symlink_permission(&tmpdir) {
+        return;
+    };
+    let root = tmpdir.path();
+    check!(fs::create_dir_all(root.join("a")));
+    check!(fs::create_dir_all(root.join("b")));
+    check!(symlink_dir(root.join("b"), root.join("a/to_b")));
+    check!(symlink_dir(root.join("a"), root.join("b/to_a")));
+
+    // Each link is followed once, until it leads back to a directory being
+    // walked.
+    let walk = fs::walk_dir(root).sort_by(by_name).follow_links(true);
+    let results: Vec<_> = walk.map(|entry| entry.map(|entry| entry.file_name())).collect();
+    assert_eq!(results.len(), 8);
+    for (i, name) in [(0, "a"), (1, "to_b"), (2, "to_a"), (4, "b"), (5, "to_a"), (6, "to_b")] {
+        assert_eq!(results[i].as_ref().unwrap(), name);
+    }
+    for i in [3, 7] {
+        assert_eq!(results[i].as_ref().unwrap_err().kind(), ErrorKind::FilesystemLoop);
+    }
+}
@@ -0,0 +1,357 @@
+use super::{canonicalize, metadata, read_dir, DirEntry, FileType, Metadata, ReadDir};
+use crate::cmp::Ordering;
+use crate::ffi::OsString;
+use crate::fmt;
+use crate::io;
+use crate::path::PathBuf;
+use crate::sys::fs as fs_imp;
+use crate::vec;
+
+/// An iterator over the entries within a directory and all of its
+/// subdirectories.
+///
+/// This struct is created by the [`walk_dir`] function. See its
+/// documentation for more.
+///
+/// [`walk_dir`]: super::walk_dir
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub struct WalkDir {
+    root: PathBuf,
+    min_depth: usize,
+    max_depth: usize,
+    follow_links: bool,
+    sort: Option<Box<Compare>>,
+    /// `None` until the root is opened, by the first call to `next`.
+    stack: Option<Vec<Frame>>,
+    /// The directory returned by the last call to `next`, which the next
+    /// call descends into unless it was pruned.
+    pending: Option<PendingDir>,
+}
+
+type Compare = dyn FnMut(&WalkEntry, &WalkEntry) -> Ordering + Send;
+
+/// A directory being walked.
+struct Frame {
+    read_dir: ReadDir,
+    /// The entries of the directory, already read and sorted, when sorting.
+    sorted: Option<vec::IntoIter<io::Result<WalkEntry>>>,
+    /// The depth of the entries of the directory.
+    depth: usize,
+    /// The path of the directory without symbolic links, to detect loops
+    /// when following them. `None` when not following them.
+    canonical: Option<PathBuf>,
+}
+
+struct PendingDir {
+    path: PathBuf,
+    name: OsString,
+    depth: usize,
+    is_symlink: bool,
+}
+
+/// An entry returned by the [`WalkDir`] iterator.
+///
+/// This is a [`DirEntry`] and its depth in the walk. As with [`DirEntry`],
+/// the file type and the metadata of a symbolic link are those of the link
+/// itself, even when links are [followed](WalkDir::follow_links).
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+#[derive(Debug)]
+pub struct WalkEntry {
+    entry: DirEntry,
+    file_type: FileType,
+    depth: usize,
+}
+
+impl WalkDir {
+    pub(super) fn new(root: PathBuf) -> WalkDir {
+        WalkDir {
+            root,
+            min_depth: 1,
+            max_depth: usize::MAX,
+            follow_links: false,
+            sort: None,
+            stack: None,
+            pending: None,
+        }
+    }
+
+    /// Only returns the entries at a depth of at least `depth`.
+    ///
+    /// The entries of the directory being walked are at a depth of 1, the
+    /// entries of its subdirectories at a depth of 2, and so on. Directories
+    /// are still descended into when their own entries aren't returned.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    #[must_use]
+    pub fn min_depth(mut self, depth: usize) -> WalkDir {
+        self.min_depth = depth;
+        self
+    }
+
+    /// Doesn't descend into the directories at a depth of `depth` or more,
+    /// so that only the entries at a depth of at most `depth` are returned.
+    ///
+    /// A `depth` of 1 returns the same entries as [`read_dir`].
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    #[must_use]
+    pub fn max_depth(mut self, depth: usize) -> WalkDir {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Descends into the symbolic links that point to directories, if
+    /// `follow` is `true`. By default, symbolic links are returned, but not
+    /// descended into.
+    ///
+    /// When following symbolic links, a link to a directory that is already
+    /// being walked, such as one that contains the link, is reported as an
+    /// error of the kind [`FilesystemLoop`](io::ErrorKind::FilesystemLoop),
+    /// instead of being descended into.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    #[must_use]
+    pub fn follow_links(mut self, follow: bool) -> WalkDir {
+        self.follow_links = follow;
+        self
+    }
+
+    /// Returns the entries of each directory in the order given by
+    /// `compare`, instead of the order in which the platform returns them.
+    ///
+    /// The contents of a directory are still returned right after the
+    /// directory itself, before its next sibling.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_walk_dir)]
+    /// use std::fs;
+    ///
+    /// let walk = fs::walk_dir("docs").sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+    /// for entry in walk {
+    ///     println!("{}", entry?.path().display());
+    /// }
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    #[must_use]
+    pub fn sort_by<F>(mut self, compare: F) -> WalkDir
+    where
+        F: FnMut(&WalkEntry, &WalkEntry) -> Ordering + Send + 'static,
+    {
+        self.sort = Some(Box::new(compare));
+        self
+    }
+
+    /// Skips the contents of the directory returned by the last call to
+    /// [`next`](Iterator::next). Does nothing if that entry wasn't a
+    /// directory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_walk_dir)]
+    /// use std::fs;
+    ///
+    /// let mut walk = fs::walk_dir(".");
+    /// while let Some(entry) = walk.next() {
+    ///     let entry = entry?;
+    ///     if entry.file_name() == ".git" || entry.file_name() == "target" {
+    ///         walk.prune();
+    ///         continue;
+    ///     }
+    ///     println!("{}", entry.path().display());
+    /// }
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn prune(&mut self) {
+        self.pending = None;
+    }
+
+    fn open_root(&mut self) -> io::Result<Frame> {
+        let canonical = if self.follow_links { Some(canonicalize(&self.root)?) } else { None };
+        Ok(Frame::new(read_dir(&self.root)?, 1, canonical, &mut self.sort))
+    }
+
+    fn open_dir(&mut self, dir: PendingDir) -> io::Result<Frame> {
+        let stack = self.stack.as_ref().unwrap();
+        let parent = stack.last().unwrap();
+        let canonical = match &parent.canonical {
+            Some(_) if dir.is_symlink => {
+                let canonical = canonicalize(&dir.path)?;
+                // A link to a directory being walked, or to one of its
+                // ancestors, would lead back to it.
+                if stack
+                    .iter()
+                    .any(|frame| frame.canonical.as_ref().unwrap().starts_with(&canonical))
+                {
+                    return Err(io::const_io_error!(
+                        io::ErrorKind::FilesystemLoop,
+                        "symbolic link to a directory being walked",
+                    ));
+                }
+                Some(canonical)
+            }
+            Some(parent_canonical) => Some(parent_canonical.join(&dir.name)),
+            None => None,
+        };
+        let read_dir =
+            fs_imp::readdir_at(&parent.read_dir.0, &dir.name, &dir.path, dir.is_symlink)?;
+        Ok(Frame::new(ReadDir(read_dir), dir.depth + 1, canonical, &mut self.sort))
+    }
+
+    /// Returns the directory to descend into after returning `entry`, if any.
+    fn pending_dir(&self, entry: &WalkEntry) -> Option<PendingDir> {
+        if entry.depth >= self.max_depth {
+            return None;
+        }
+        let is_symlink = if entry.file_type.is_dir() {
+            false
+        } else if self.follow_links && entry.file_type.is_symlink() {
+            // Broken links are returned, but not reported as errors.
+            if !metadata(entry.path()).is_ok_and(|metadata| metadata.is_dir()) {
+                return None;
+            }
+            true
+        } else {
+            return None;
+        };
+        Some(PendingDir {
+            path: entry.path(),
+            name: entry.file_name(),
+            depth: entry.depth,
+            is_symlink,
+        })
+    }
+}
+
+impl Frame {
+    fn new(
+        mut read_dir: ReadDir,
+        depth: usize,
+        canonical: Option<PathBuf>,
+        sort: &mut Option<Box<Compare>>,
+    ) -> Frame {
+        let sorted = sort.as_mut().map(|compare| {
+            let mut entries: Vec<_> =
+                read_dir.by_ref().map(|entry| WalkEntry::new(entry?, depth)).collect();
+            // Errors come first, as they don't have a place in the order.
+            entries.sort_by(|a, b| match (a, b) {
+                (Ok(a), Ok(b)) => compare(a, b),
+                (Ok(_), Err(_)) => Ordering::Greater,
+                (Err(_), Ok(_)) => Ordering::Less,
+                (Err(_), Err(_)) => Ordering::Equal,
+            });
+            entries.into_iter()
+        });
+        Frame { read_dir, sorted, depth, canonical }
+    }
+
+    fn next_entry(&mut self) -> Option<io::Result<WalkEntry>> {
+        match &mut self.sorted {
+            Some(entries) => entries.next(),
+            None => self.read_dir.next().map(|entry| WalkEntry::new(entry?, self.depth)),
+        }
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl Iterator for WalkDir {
+    type Item = io::Result<WalkEntry>;
+
+    fn next(&mut self) -> Option<io::Result<WalkEntry>> {
+        if self.stack.is_none() {
+            let root = self.open_root();
+            let stack = self.stack.insert(Vec::new());
+            match root {
+                Ok(frame) => stack.push(frame),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        loop {
+            if let Some(dir) = self.pending.take() {
+                match self.open_dir(dir) {
+                    Ok(frame) => self.stack.as_mut().unwrap().push(frame),
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+            let stack = self.stack.as_mut().unwrap();
+            let entry = match stack.last_mut()?.next_entry() {
+                Some(Ok(entry)) => entry,
+                Some(Err(e)) => return Some(Err(e)),
+                None => {
+                    stack.pop();
+                    continue;
+                }
+            };
+            self.pending = self.pending_dir(&entry);
+            if entry.depth >= self.min_depth {
+                return Some(Ok(entry));
+            }
+        }
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl fmt::Debug for WalkDir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalkDir")
+            .field("root", &self.root)
+            .field("min_depth", &self.min_depth)
+            .field("max_depth", &self.max_depth)
+            .field("follow_links", &self.follow_links)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WalkEntry {
+    fn new(entry: DirEntry, depth: usize) -> io::Result<WalkEntry> {
+        let file_type = entry.file_type()?;
+        Ok(WalkEntry { entry, file_type, depth })
+    }
+
+    /// Returns the full path to the file that this entry represents.
+    ///
+    /// The full path is created by joining the path given to [`walk_dir`]
+    /// with the names of the directories leading to the entry, and with the
+    /// name of the entry, as with [`DirEntry::path`].
+    ///
+    /// [`walk_dir`]: super::walk_dir
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    #[must_use]
+    pub fn path(&self) -> PathBuf {
+        self.entry.path()
+    }
+
+    /// Returns the file name of this entry, without any leading path
+    /// component.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    #[must_use]
+    pub fn file_name(&self) -> OsString {
+        self.entry.file_name()
+    }
+
+    /// Returns the file type of this entry, which was read when the entry
+    /// was returned by [`WalkDir`].
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    #[must_use]
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Returns the metadata of this entry. See [`DirEntry::metadata`].
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn metadata(&self) -> io::Result<Metadata> {
+        self.entry.metadata()
+    }
+
+    /// Returns the depth of this entry in the walk: 1 for the entries of the
+    /// directory given to [`walk_dir`], 2 for the entries of its
+    /// subdirectories, and so on.
+    ///
+    /// [`walk_dir`]: super::walk_dir
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+}
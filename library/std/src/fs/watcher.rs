@@ -0,0 +1,247 @@
+use crate::fmt;
+use crate::io;
+use crate::path::{Path, PathBuf};
+use crate::sys::fs_watcher as native;
+use crate::sys_common::fs_watcher::PollWatcher;
+use crate::time::{Duration, Instant};
+
+/// A change to the filesystem, reported by a [`Watcher`].
+///
+/// The paths of events are relative to the paths that were given to
+/// [`Watcher::watch`] or [`Watcher::watch_recursive`], in the same way as the
+/// paths of the entries returned by [`read_dir`](super::read_dir).
+#[unstable(feature = "fs_watcher", issue = "none")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WatchEvent {
+    /// A file or directory was created.
+    Create(PathBuf),
+    /// The contents or the metadata of a file or directory were modified.
+    Modify(PathBuf),
+    /// A file or directory was removed.
+    Remove(PathBuf),
+    /// A file or directory was renamed. Only reported when both paths are
+    /// watched, and when the watcher can tell that both refer to the same
+    /// file: otherwise, a rename is reported as a [`Remove`] of the old path
+    /// and a [`Create`] of the new one.
+    ///
+    /// [`Remove`]: WatchEvent::Remove
+    /// [`Create`]: WatchEvent::Create
+    Rename {
+        /// The old path of the file or directory.
+        from: PathBuf,
+        /// The new path of the file or directory.
+        to: PathBuf,
+    },
+}
+
+/// Watches files and directories for changes.
+///
+/// Paths are added to the watcher with [`watch`] or [`watch_recursive`], and
+/// the changes to them are then retrieved in order with [`next_event`] or
+/// [`next_event_timeout`]. Changes are recorded from the moment a path is
+/// watched, and until it's [unwatched](Watcher::unwatch) or the watcher is
+/// dropped.
+///
+/// [`watch`]: Watcher::watch
+/// [`watch_recursive`]: Watcher::watch_recursive
+/// [`next_event`]: Watcher::next_event
+/// [`next_event_timeout`]: Watcher::next_event_timeout
+///
+/// # Platform-specific behavior
+///
+/// On Linux and Android, this uses `inotify`. On other platforms, or when
+/// created with [`Watcher::polling`], the watcher compares the metadata of
+/// the watched files at a regular interval, and reports the differences:
+/// in that case, changes that are undone before the next poll are missed,
+/// and renames are reported as removals and creations.
+/// **Note that this may change in the future.**
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_watcher)]
+/// use std::fs::{WatchEvent, Watcher};
+///
+/// let mut watcher = Watcher::new()?;
+/// watcher.watch_recursive("src")?;
+/// loop {
+///     match watcher.next_event()? {
+///         WatchEvent::Modify(path) => println!("{} was modified", path.display()),
+///         event => println!("{event:?}"),
+///     }
+/// }
+/// # Ok::<_, std::io::Error>(())
+/// ```
+#[unstable(feature = "fs_watcher", issue = "none")]
+pub struct Watcher {
+    inner: Inner,
+}
+
+enum Inner {
+    Native(native::Watcher),
+    Poll(PollWatcher),
+}
+
+impl Watcher {
+    /// The interval at which the filesystem is polled when no native
+    /// watching is available to [`Watcher::new`].
+    #[unstable(feature = "fs_watcher", issue = "none")]
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+    /// Creates a watcher that isn't watching any path yet.
+    ///
+    /// This uses the native way of watching the filesystem of the platform
+    /// if there is one, and otherwise polls the filesystem every
+    /// [`DEFAULT_POLL_INTERVAL`](Watcher::DEFAULT_POLL_INTERVAL).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the native watcher couldn't be created, e.g.
+    /// because of a limit on the number of open watchers.
+    #[unstable(feature = "fs_watcher", issue = "none")]
+    pub fn new() -> io::Result<Watcher> {
+        match native::Watcher::new() {
+            Ok(watcher) => Ok(Watcher { inner: Inner::Native(watcher) }),
+            Err(e) if e.kind() == io::ErrorKind::Unsupported => {
+                Ok(Watcher::polling(Watcher::DEFAULT_POLL_INTERVAL))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Creates a watcher that polls the filesystem for changes every
+    /// `interval`, whether the platform has a native way of watching the
+    /// filesystem or not.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_watcher)]
+    /// use std::fs::Watcher;
+    /// use std::time::Duration;
+    ///
+    /// // Network filesystems often don't support native notifications.
+    /// let mut watcher = Watcher::polling(Duration::from_secs(2));
+    /// watcher.watch("/mnt/share/config.toml")?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    #[unstable(feature = "fs_watcher", issue = "none")]
+    #[must_use]
+    pub fn polling(interval: Duration) -> Watcher {
+        Watcher { inner: Inner::Poll(PollWatcher::new(interval)) }
+    }
+
+    /// Starts watching a file or directory.
+    ///
+    /// If `path` is a directory, its entries are watched too, but not the
+    /// entries of its subdirectories: use [`watch_recursive`] for that.
+    ///
+    /// [`watch_recursive`]: Watcher::watch_recursive
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` doesn't exist, or can't be watched.
+    #[unstable(feature = "fs_watcher", issue = "none")]
+    pub fn watch<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        self.watch_inner(path.as_ref(), false)
+    }
+
+    /// Starts watching a directory and all of its descendants, including the
+    /// directories created in it later on. Symbolic links aren't followed.
+    ///
+    /// If `path` is a file, this is the same as [`watch`](Watcher::watch).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` doesn't exist, or can't be watched.
+    /// Descendants that can't be watched, e.g. because of their permissions,
+    /// are skipped.
+    #[unstable(feature = "fs_watcher", issue = "none")]
+    pub fn watch_recursive<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        self.watch_inner(path.as_ref(), true)
+    }
+
+    fn watch_inner(&mut self, path: &Path, recursive: bool) -> io::Result<()> {
+        match &mut self.inner {
+            Inner::Native(watcher) => watcher.watch(path, recursive),
+            Inner::Poll(watcher) => watcher.watch(path, recursive),
+        }
+    }
+
+    /// Stops watching a path that was given to [`watch`] or
+    /// [`watch_recursive`].
+    ///
+    /// Events for this path that were already recorded are still reported.
+    ///
+    /// [`watch`]: Watcher::watch
+    /// [`watch_recursive`]: Watcher::watch_recursive
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of the kind [`NotFound`](io::ErrorKind::NotFound) if
+    /// `path` isn't watched.
+    #[unstable(feature = "fs_watcher", issue = "none")]
+    pub fn unwatch<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        match &mut self.inner {
+            Inner::Native(watcher) => watcher.unwatch(path.as_ref()),
+            Inner::Poll(watcher) => watcher.unwatch(path.as_ref()),
+        }
+    }
+
+    /// Blocks until the next change to the watched paths, and returns it.
+    #[unstable(feature = "fs_watcher", issue = "none")]
+    pub fn next_event(&mut self) -> io::Result<WatchEvent> {
+        loop {
+            if let Some(event) = self.next_event_until(None)? {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Waits for at most `timeout` for the next change to the watched paths,
+    /// and returns it, or returns `None` if there was none.
+    ///
+    /// A `timeout` of zero returns the changes that were already recorded,
+    /// without blocking.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_watcher)]
+    /// use std::fs::Watcher;
+    /// use std::time::Duration;
+    ///
+    /// let mut watcher = Watcher::new()?;
+    /// watcher.watch("Cargo.toml")?;
+    /// // Wait for the changes to settle down before rebuilding.
+    /// if let Some(event) = watcher.next_event_timeout(Duration::from_secs(1))? {
+    ///     while watcher.next_event_timeout(Duration::from_millis(100))?.is_some() {}
+    ///     println!("rebuilding after {event:?}");
+    /// }
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    #[unstable(feature = "fs_watcher", issue = "none")]
+    pub fn next_event_timeout(&mut self, timeout: Duration) -> io::Result<Option<WatchEvent>> {
+        // A timeout too large to be represented means waiting forever.
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.next_event_until(Some(deadline)),
+            None => self.next_event().map(Some),
+        }
+    }
+
+    fn next_event_until(&mut self, deadline: Option<Instant>) -> io::Result<Option<WatchEvent>> {
+        match &mut self.inner {
+            Inner::Native(watcher) => watcher.next_event(deadline),
+            Inner::Poll(watcher) => watcher.next_event(deadline),
+        }
+    }
+}
+
+#[unstable(feature = "fs_watcher", issue = "none")]
+impl fmt::Debug for Watcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let polling = matches!(self.inner, Inner::Poll(_));
+        f.debug_struct("Watcher").field("polling", &polling).finish_non_exhaustive()
+    }
+}
@@ -310,6 +310,10 @@
 pub use self::buffered::WriterPanicked;
 #[unstable(feature = "raw_os_error_ty", issue = "107792")]
 pub use self::error::RawOsError;
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+pub use self::pipe::{pipe, PipeReader, PipeWriter};
+#[unstable(feature = "io_poller", issue = "none")]
+pub use self::poller::{Event, Interest, PollSource, Poller};
 pub(crate) use self::stdio::attempt_print_to_stderr;
 #[unstable(feature = "internal_output_capture", issue = "none")]
 #[doc(no_inline, hidden)]
@@ -338,6 +342,8 @@
 mod cursor;
 mod error;
 mod impls;
+mod pipe;
+mod poller;
 pub mod prelude;
 mod stdio;
 mod util;
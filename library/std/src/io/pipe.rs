@@ -0,0 +1,235 @@
+#[cfg(all(test, any(unix, windows)))]
+mod tests;
+
+use crate::fmt;
+use crate::io::{self, BorrowedCursor, IoSlice, IoSliceMut, Read, Write};
+use crate::sys::pipe as imp;
+use crate::sys_common::{AsInner, FromInner, IntoInner};
+
+/// Creates an anonymous pipe, and returns its read and write ends.
+///
+/// The data written to the [`PipeWriter`] can be read from the
+/// [`PipeReader`], in the same order. Once every `PipeWriter` of the pipe,
+/// including its [clones](PipeWriter::try_clone), is dropped, reading returns
+/// the remaining data and then end of file. Once every `PipeReader` is
+/// dropped, writing returns an error of the kind
+/// [`BrokenPipe`](io::ErrorKind::BrokenPipe).
+///
+/// Both ends can be converted into a [`Stdio`], to connect the pipe to the
+/// standard streams of one or several child processes. The ends are not
+/// inherited by the children otherwise.
+///
+/// [`Stdio`]: crate::process::Stdio
+///
+/// # Platform-specific behavior
+///
+/// This currently corresponds to the `pipe2` function with the `O_CLOEXEC`
+/// flag on the Unix platforms that have it, to `pipe` on the other Unix
+/// platforms, and to the `CreatePipe` function on Windows. On other
+/// platforms, this returns an error of the kind
+/// [`Unsupported`](io::ErrorKind::Unsupported).
+/// **Note that this may change in the future.**
+///
+/// # Examples
+///
+/// Capturing the combined output of a pipeline and of its errors:
+///
+/// ```no_run
+/// #![feature(anonymous_pipe)]
+/// use std::io::{self, Read};
+/// use std::process::{Command, Stdio};
+///
+/// let (mut reader, writer) = io::pipe()?;
+///
+/// let mut producer = Command::new("ls")
+///     .arg("-l")
+///     .stdout(Stdio::piped())
+///     .stderr(writer.try_clone()?)
+///     .spawn()?;
+/// let mut consumer = Command::new("sort")
+///     .stdin(producer.stdout.take().unwrap())
+///     .stdout(writer.try_clone()?)
+///     .stderr(writer)
+///     .spawn()?;
+///
+/// // The writers are dropped, so that reading ends when both commands have
+/// // exited.
+/// let mut output = String::new();
+/// reader.read_to_string(&mut output)?;
+/// producer.wait()?;
+/// consumer.wait()?;
+/// println!("{output}");
+/// # Ok::<_, io::Error>(())
+/// ```
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+pub fn pipe() -> io::Result<(PipeReader, PipeWriter)> {
+    let (reader, writer) = imp::unnamed_pipe()?;
+    Ok((PipeReader { inner: reader }, PipeWriter { inner: writer }))
+}
+
+/// The read end of an anonymous pipe, created by [`pipe`].
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+pub struct PipeReader {
+    inner: imp::UnnamedPipe,
+}
+
+/// The write end of an anonymous pipe, created by [`pipe`].
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+pub struct PipeWriter {
+    inner: imp::UnnamedPipe,
+}
+
+impl PipeReader {
+    /// Creates a new `PipeReader` for the same pipe.
+    ///
+    /// Reading from either of them reads from the pipe, so that the data is
+    /// shared between them, and the pipe stays open until both are dropped.
+    #[unstable(feature = "anonymous_pipe", issue = "none")]
+    pub fn try_clone(&self) -> io::Result<PipeReader> {
+        self.inner.try_clone().map(|inner| PipeReader { inner })
+    }
+}
+
+impl PipeWriter {
+    /// Creates a new `PipeWriter` for the same pipe.
+    ///
+    /// The reader only reaches end of file once both are dropped, which
+    /// allows e.g. passing the pipe to the standard output of several child
+    /// processes.
+    #[unstable(feature = "anonymous_pipe", issue = "none")]
+    pub fn try_clone(&self) -> io::Result<PipeWriter> {
+        self.inner.try_clone().map(|inner| PipeWriter { inner })
+    }
+}
+
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+impl Read for &PipeReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+
+    fn read_buf(&mut self, buf: BorrowedCursor<'_>) -> io::Result<()> {
+        self.inner.read_buf(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        self.inner.is_read_vectored()
+    }
+}
+
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+impl Read for PipeReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self).read(buf)
+    }
+
+    fn read_buf(&mut self, buf: BorrowedCursor<'_>) -> io::Result<()> {
+        (&*self).read_buf(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        (&*self).read_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        self.inner.is_read_vectored()
+    }
+}
+
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+impl Write for &PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+impl Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self).write(buf)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (&*self).write_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsInner<imp::UnnamedPipe> for PipeReader {
+    #[inline]
+    fn as_inner(&self) -> &imp::UnnamedPipe {
+        &self.inner
+    }
+}
+
+impl IntoInner<imp::UnnamedPipe> for PipeReader {
+    fn into_inner(self) -> imp::UnnamedPipe {
+        self.inner
+    }
+}
+
+impl FromInner<imp::UnnamedPipe> for PipeReader {
+    fn from_inner(inner: imp::UnnamedPipe) -> PipeReader {
+        PipeReader { inner }
+    }
+}
+
+impl AsInner<imp::UnnamedPipe> for PipeWriter {
+    #[inline]
+    fn as_inner(&self) -> &imp::UnnamedPipe {
+        &self.inner
+    }
+}
+
+impl IntoInner<imp::UnnamedPipe> for PipeWriter {
+    fn into_inner(self) -> imp::UnnamedPipe {
+        self.inner
+    }
+}
+
+impl FromInner<imp::UnnamedPipe> for PipeWriter {
+    fn from_inner(inner: imp::UnnamedPipe) -> PipeWriter {
+        PipeWriter { inner }
+    }
+}
+
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+impl fmt::Debug for PipeReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PipeReader").finish_non_exhaustive()
+    }
+}
+
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+impl fmt::Debug for PipeWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PipeWriter").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,31 @@
+use super::pipe;
+use crate::io::{ErrorKind, Read, Write};
+use crate::thread;
+
+#[test]
+fn smoke() {
+    let (mut reader, mut writer) = pipe().unwrap();
+    writer.write_all(b"hello").unwrap();
+    let mut buf = [0; 5];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hello");
+
+    // Reading returns end of file once every writer is dropped.
+    let writer2 = writer.try_clone().unwrap();
+    let handle = thread::spawn(move || {
+        (&writer2).write_all(b"world").unwrap();
+    });
+    drop(writer);
+    let mut contents = Vec::new();
+    reader.read_to_end(&mut contents).unwrap();
+    assert_eq!(contents, b"world");
+    handle.join().unwrap();
+}
+
+#[test]
+fn broken_pipe() {
+    let (reader, mut writer) = pipe().unwrap();
+    drop(reader);
+    let err = writer.write_all(b"hello").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+}
@@ -0,0 +1,287 @@
+#[cfg(all(test, any(unix, windows)))]
+mod tests;
+
+use crate::fmt;
+use crate::io;
+use crate::sys::poller as imp;
+use crate::time::Duration;
+
+/// The readiness a [`Poller`] waits for on a source: being readable,
+/// writable, or both.
+#[unstable(feature = "io_poller", issue = "none")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Interest {
+    readable: bool,
+    writable: bool,
+}
+
+impl Interest {
+    /// Waits for the source to be readable, or for a listener to have a
+    /// connection to accept.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub const READABLE: Interest = Interest { readable: true, writable: false };
+
+    /// Waits for the source to be writable, or for a connection to be
+    /// established.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub const WRITABLE: Interest = Interest { readable: false, writable: true };
+
+    /// Waits for the source to be readable or writable.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub const BOTH: Interest = Interest { readable: true, writable: true };
+
+    /// Returns `true` if this interest includes readability.
+    #[unstable(feature = "io_poller", issue = "none")]
+    #[must_use]
+    pub fn is_readable(self) -> bool {
+        self.readable
+    }
+
+    /// Returns `true` if this interest includes writability.
+    #[unstable(feature = "io_poller", issue = "none")]
+    #[must_use]
+    pub fn is_writable(self) -> bool {
+        self.writable
+    }
+}
+
+/// A source of I/O that can be registered with a [`Poller`].
+///
+/// This trait is sealed: it can't be implemented outside of the standard
+/// library, and is only implemented by the types that the platform can poll.
+///
+/// # Platform-specific behavior
+///
+/// On Unix, this is implemented by the types that implement `AsFd`, such as
+/// [`TcpStream`], [`TcpListener`], [`UdpSocket`], `UnixStream`,
+/// [`ChildStdout`] or [`PipeReader`].
+///
+/// On Windows, this is implemented by the types that implement `AsSocket`,
+/// i.e. the sockets.
+///
+/// On other platforms, this isn't implemented by any type.
+/// **Note that this may change in the future.**
+///
+/// [`TcpStream`]: crate::net::TcpStream
+/// [`TcpListener`]: crate::net::TcpListener
+/// [`UdpSocket`]: crate::net::UdpSocket
+/// [`ChildStdout`]: crate::process::ChildStdout
+/// [`PipeReader`]: io::PipeReader
+#[unstable(feature = "io_poller", issue = "none")]
+pub trait PollSource: imp::Source {}
+
+#[unstable(feature = "io_poller", issue = "none")]
+impl<T: imp::Source + ?Sized> PollSource for T {}
+
+/// A readiness event, returned by [`Poller::wait`].
+///
+/// A source whose connection was closed, or which has an error pending, is
+/// reported as both readable and writable, so that the next read or write
+/// reports it.
+#[unstable(feature = "io_poller", issue = "none")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Event {
+    key: usize,
+    readable: bool,
+    writable: bool,
+}
+
+impl Event {
+    pub(crate) fn new(key: usize, readable: bool, writable: bool) -> Event {
+        Event { key, readable, writable }
+    }
+
+    /// Returns the key the source was registered with.
+    #[unstable(feature = "io_poller", issue = "none")]
+    #[must_use]
+    pub fn key(&self) -> usize {
+        self.key
+    }
+
+    /// Returns `true` if the source is readable.
+    #[unstable(feature = "io_poller", issue = "none")]
+    #[must_use]
+    pub fn is_readable(&self) -> bool {
+        self.readable
+    }
+
+    /// Returns `true` if the source is writable.
+    #[unstable(feature = "io_poller", issue = "none")]
+    #[must_use]
+    pub fn is_writable(&self) -> bool {
+        self.writable
+    }
+}
+
+/// Waits for several sources of I/O to be ready.
+///
+/// Sources, i.e. the types implementing [`PollSource`], are registered with
+/// [`add`], along with a key identifying them and the [`Interest`] to wait
+/// for, and [`wait`] then blocks until some of them are ready, and returns
+/// their keys. Sources are usually put in nonblocking mode, so that reading
+/// or writing them until they return an error of the kind [`WouldBlock`]
+/// doesn't block the other sources.
+///
+/// Readiness is level-triggered: a source is reported by every call to
+/// [`wait`] for as long as it is ready.
+///
+/// Sources must be [deleted](Poller::delete) from the poller before they are
+/// closed. Otherwise, events may be reported for the closed source, or for
+/// a new one that reuses its descriptor, and the closed source still counts
+/// as registered, so that [`wait`] without a timeout may block forever
+/// instead of returning when no other source is registered.
+///
+/// [`add`]: Poller::add
+/// [`wait`]: Poller::wait
+/// [`WouldBlock`]: io::ErrorKind::WouldBlock
+///
+/// # Platform-specific behavior
+///
+/// On Linux and Android, this uses `epoll`, which can't register regular
+/// files. On the other Unix platforms, this uses `poll`.
+///
+/// On Windows, this uses `WSAPoll`.
+///
+/// On other platforms, [`Poller::new`] returns an error of the kind
+/// [`Unsupported`](io::ErrorKind::Unsupported).
+/// **Note that this may change in the future.**
+///
+/// # Examples
+///
+/// An echo server that handles several clients on a single thread:
+///
+/// ```no_run
+/// #![feature(io_poller)]
+/// use std::collections::HashMap;
+/// use std::io::{self, Interest, Poller, Read, Write};
+/// use std::net::TcpListener;
+///
+/// const LISTENER: usize = usize::MAX;
+///
+/// let listener = TcpListener::bind("127.0.0.1:8080")?;
+/// listener.set_nonblocking(true)?;
+/// let mut poller = Poller::new()?;
+/// poller.add(&listener, LISTENER, Interest::READABLE)?;
+///
+/// let mut clients = HashMap::new();
+/// let mut next_key = 0;
+/// let mut events = Vec::new();
+/// loop {
+///     poller.wait(&mut events, None)?;
+///     for event in &events {
+///         if event.key() == LISTENER {
+///             let (stream, _) = match listener.accept() {
+///                 Ok(client) => client,
+///                 Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+///                 Err(e) => return Err(e),
+///             };
+///             stream.set_nonblocking(true)?;
+///             poller.add(&stream, next_key, Interest::READABLE)?;
+///             clients.insert(next_key, stream);
+///             next_key += 1;
+///             continue;
+///         }
+///
+///         let stream = clients.get_mut(&event.key()).unwrap();
+///         let mut buf = [0; 1024];
+///         match stream.read(&mut buf) {
+///             Ok(0) | Err(_) => {
+///                 poller.delete(&*stream)?;
+///                 clients.remove(&event.key());
+///             }
+///             // Replies are small enough to be written at once.
+///             Ok(n) => stream.write_all(&buf[..n])?,
+///         }
+///     }
+/// }
+/// # Ok::<_, io::Error>(())
+/// ```
+#[unstable(feature = "io_poller", issue = "none")]
+pub struct Poller {
+    inner: imp::Poller,
+}
+
+impl Poller {
+    /// Creates a poller without any source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the poller couldn't be created, e.g. because of
+    /// a limit on the number of open files, or if the platform doesn't
+    /// support polling.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn new() -> io::Result<Poller> {
+        imp::Poller::new().map(|inner| Poller { inner })
+    }
+
+    /// Registers a source, so that [`wait`] reports it with `key` when it
+    /// is ready for `interest`.
+    ///
+    /// [`wait`]: Poller::wait
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of the kind
+    /// [`AlreadyExists`](io::ErrorKind::AlreadyExists) if `source` is
+    /// already registered, or another error if it can't be polled.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn add<S: PollSource + ?Sized>(
+        &mut self,
+        source: &S,
+        key: usize,
+        interest: Interest,
+    ) -> io::Result<()> {
+        self.inner.add(source, key, interest)
+    }
+
+    /// Changes the key and the interest of a registered source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of the kind [`NotFound`](io::ErrorKind::NotFound)
+    /// if `source` isn't registered.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn modify<S: PollSource + ?Sized>(
+        &mut self,
+        source: &S,
+        key: usize,
+        interest: Interest,
+    ) -> io::Result<()> {
+        self.inner.modify(source, key, interest)
+    }
+
+    /// Unregisters a source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of the kind [`NotFound`](io::ErrorKind::NotFound)
+    /// if `source` isn't registered.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn delete<S: PollSource + ?Sized>(&mut self, source: &S) -> io::Result<()> {
+        self.inner.delete(source)
+    }
+
+    /// Waits until some of the sources are ready, or until `timeout`
+    /// elapses, and replaces the contents of `events` with the events of
+    /// the ready sources.
+    ///
+    /// A `timeout` of `None` waits forever, and a `timeout` of zero returns
+    /// the sources that are already ready, without blocking. `events` may
+    /// be left empty before the timeout elapses, e.g. when the wait is
+    /// interrupted by a signal.
+    ///
+    /// If no source is registered and `timeout` is `None`, this returns
+    /// immediately without any event, as none could ever be reported.
+    #[unstable(feature = "io_poller", issue = "none")]
+    pub fn wait(&mut self, events: &mut Vec<Event>, timeout: Option<Duration>) -> io::Result<()> {
+        events.clear();
+        self.inner.wait(events, timeout)
+    }
+}
+
+#[unstable(feature = "io_poller", issue = "none")]
+impl fmt::Debug for Poller {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Poller").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,151 @@
+use super::{Event, Interest, Poller};
+use crate::io::{ErrorKind, Read, Write};
+use crate::net::test::next_test_ip4;
+use crate::net::{TcpListener, TcpStream, UdpSocket};
+use crate::time::Duration;
+
+macro_rules! t {
+    ($e:expr) => {
+        match $e {
+            Ok(t) => t,
+            Err(e) => panic!("received error for `{}`: {}", stringify!($e), e),
+        }
+    };
+}
+
+/// Waits for the events of the sources, for at most a few seconds, so that
+/// a failing test doesn't hang.
+fn wait(poller: &mut Poller, events: &mut Vec<Event>) {
+    t!(poller.wait(events, Some(Duration::from_secs(10))));
+}
+
+#[test]
+fn timeout() {
+    let mut poller = t!(Poller::new());
+    let mut events = Vec::new();
+
+    t!(poller.wait(&mut events, Some(Duration::from_millis(50))));
+    assert!(events.is_empty());
+
+    let socket = t!(UdpSocket::bind(next_test_ip4()));
+    t!(poller.add(&socket, 0, Interest::READABLE));
+    t!(poller.wait(&mut events, Some(Duration::ZERO)));
+    assert!(events.is_empty());
+}
+
+#[test]
+fn tcp() {
+    let addr = next_test_ip4();
+    let listener = t!(TcpListener::bind(&addr));
+    t!(listener.set_nonblocking(true));
+    let mut poller = t!(Poller::new());
+    let mut events = Vec::new();
+    t!(poller.add(&listener, 0, Interest::READABLE));
+
+    let mut client = t!(TcpStream::connect(&addr));
+    wait(&mut poller, &mut events);
+    assert_eq!(events, [Event::new(0, true, false)]);
+
+    let (mut server, _) = t!(listener.accept());
+    t!(server.set_nonblocking(true));
+    t!(poller.delete(&listener));
+    t!(poller.add(&server, 1, Interest::BOTH));
+    wait(&mut poller, &mut events);
+    assert_eq!(events, [Event::new(1, false, true)]);
+
+    t!(poller.modify(&server, 2, Interest::READABLE));
+    t!(client.write_all(b"hello"));
+    wait(&mut poller, &mut events);
+    assert_eq!(events, [Event::new(2, true, false)]);
+
+    // Readiness is level-triggered.
+    wait(&mut poller, &mut events);
+    assert_eq!(events, [Event::new(2, true, false)]);
+
+    let mut buf = [0; 8];
+    assert_eq!(t!(server.read(&mut buf)), 5);
+    assert_eq!(server.read(&mut buf).unwrap_err().kind(), ErrorKind::WouldBlock);
+    t!(poller.wait(&mut events, Some(Duration::ZERO)));
+    assert!(events.is_empty());
+
+    drop(client);
+    wait(&mut poller, &mut events);
+    assert_eq!(events.len(), 1);
+    assert!(events[0].is_readable());
+    assert_eq!(t!(server.read(&mut buf)), 0);
+}
+
+#[test]
+fn udp() {
+    let addr1 = next_test_ip4();
+    let addr2 = next_test_ip4();
+    let socket1 = t!(UdpSocket::bind(&addr1));
+    let socket2 = t!(UdpSocket::bind(&addr2));
+    let mut poller = t!(Poller::new());
+    let mut events = Vec::new();
+    t!(poller.add(&socket1, 1, Interest::READABLE));
+    t!(poller.add(&socket2, 2, Interest::READABLE));
+
+    t!(socket1.send_to(b"hello", &addr2));
+    wait(&mut poller, &mut events);
+    assert_eq!(events, [Event::new(2, true, false)]);
+}
+
+#[test]
+fn registration_errors() {
+    let socket = t!(UdpSocket::bind(next_test_ip4()));
+    let mut poller = t!(Poller::new());
+
+    assert_eq!(poller.delete(&socket).unwrap_err().kind(), ErrorKind::NotFound);
+    assert_eq!(
+        poller.modify(&socket, 0, Interest::READABLE).unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+    t!(poller.add(&socket, 0, Interest::READABLE));
+    assert_eq!(
+        poller.add(&socket, 1, Interest::WRITABLE).unwrap_err().kind(),
+        ErrorKind::AlreadyExists
+    );
+    t!(poller.delete(&socket));
+}
+
+#[test]
+fn wait_without_sources() {
+    let mut poller = t!(Poller::new());
+    let mut events = Vec::new();
+
+    // Nothing could ever be reported, so this doesn't block forever.
+    t!(poller.wait(&mut events, None));
+    assert!(events.is_empty());
+
+    let socket = t!(UdpSocket::bind(next_test_ip4()));
+    t!(poller.add(&socket, 0, Interest::READABLE));
+    t!(poller.delete(&socket));
+    t!(poller.wait(&mut events, None));
+    assert!(events.is_empty());
+}
+
+#[test]
+#[cfg(unix)]
+fn pipe() {
+    let (mut reader, mut writer) = t!(crate::io::pipe());
+    let mut poller = t!(Poller::new());
+    let mut events = Vec::new();
+    t!(poller.add(&reader, 0, Interest::READABLE));
+    t!(poller.add(&writer, 1, Interest::WRITABLE));
+    wait(&mut poller, &mut events);
+    assert_eq!(events, [Event::new(1, false, true)]);
+
+    t!(poller.delete(&writer));
+    t!(writer.write_all(b"hello"));
+    wait(&mut poller, &mut events);
+    assert_eq!(events, [Event::new(0, true, false)]);
+    let mut buf = [0; 8];
+    assert_eq!(t!(reader.read(&mut buf)), 5);
+
+    drop(writer);
+    wait(&mut poller, &mut events);
+    assert_eq!(events.len(), 1);
+    assert!(events[0].is_readable());
+    assert_eq!(t!(reader.read(&mut buf)), 0);
+}
@@ -110,6 +110,53 @@ pub struct IntoIncoming {
     listener: TcpListener,
 }
 
+/// A TCP socket that is not bound or connected yet.
+///
+/// [`TcpStream::connect`] and [`TcpListener::bind`] create, configure and
+/// connect or bind a socket in a single call. A `TcpSocket` instead allows
+/// setting the options that only take effect when they are set before the
+/// socket is bound or connected, such as [`set_reuseaddr`] or
+/// [`set_only_v6`], and choosing the local address of a connection or the
+/// backlog of a listener. The socket is then turned into a [`TcpListener`] by
+/// [`listen`], or into a [`TcpStream`] by [`connect`].
+///
+/// Unlike [`TcpListener::bind`], a `TcpSocket` doesn't set any option by
+/// default.
+///
+/// The socket will be closed when the value is dropped.
+///
+/// [`connect`]: TcpSocket::connect
+/// [`listen`]: TcpSocket::listen
+/// [`set_only_v6`]: TcpSocket::set_only_v6
+/// [`set_reuseaddr`]: TcpSocket::set_reuseaddr
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(tcp_socket)]
+/// use std::net::{Ipv6Addr, SocketAddr, TcpSocket, TcpStream};
+///
+/// fn handle_client(stream: TcpStream) {
+///     // ...
+/// }
+///
+/// fn main() -> std::io::Result<()> {
+///     // a listener for both IPv4 and IPv6, which can be restarted right away
+///     let socket = TcpSocket::new_v6()?;
+///     socket.set_reuseaddr(true)?;
+///     socket.set_only_v6(false)?;
+///     socket.bind(SocketAddr::from((Ipv6Addr::UNSPECIFIED, 8080)))?;
+///
+///     let listener = socket.listen(1024)?;
+///     for stream in listener.incoming() {
+///         handle_client(stream?);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "tcp_socket", issue = "none")]
+pub struct TcpSocket(net_imp::UnboundSocket);
+
 impl TcpStream {
     /// Opens a TCP connection to a remote host.
     ///
@@ -541,6 +588,156 @@ pub fn ttl(&self) -> io::Result<u32> {
         self.0.ttl()
     }
 
+    /// Enables or disables keepalive with the `SO_KEEPALIVE` option.
+    ///
+    /// When enabled, probes are sent on the connection after it has been idle
+    /// for some time, and the connection is closed if they go unanswered.
+    /// This detects peers that went away without closing the connection, and
+    /// keeps the connection open through NATs and firewalls that drop idle
+    /// connections. The timing of the probes can be changed with
+    /// [`set_keepalive_idle`], [`set_keepalive_interval`] and
+    /// [`set_keepalive_retries`]. By default, the first probe is only sent
+    /// after 2 hours on most platforms.
+    ///
+    /// [`set_keepalive_idle`]: TcpStream::set_keepalive_idle
+    /// [`set_keepalive_interval`]: TcpStream::set_keepalive_interval
+    /// [`set_keepalive_retries`]: TcpStream::set_keepalive_retries
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_keepalive)]
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive(true).expect("set_keepalive call failed");
+    /// stream.set_keepalive_idle(Duration::from_secs(60)).expect("set_keepalive_idle call failed");
+    /// ```
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        self.0.set_keepalive(keepalive)
+    }
+
+    /// Gets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStream::set_keepalive`].
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn keepalive(&self) -> io::Result<bool> {
+        self.0.keepalive()
+    }
+
+    /// Sets how long the connection must be idle before the first keepalive
+    /// probe is sent, with the `TCP_KEEPIDLE` option.
+    ///
+    /// The time is rounded up to a whole number of seconds. An error of the
+    /// kind [`InvalidInput`](io::ErrorKind::InvalidInput) is returned if it
+    /// is zero.
+    ///
+    /// ## Platform-specific behavior
+    ///
+    /// This option is supported on Linux, Android, FreeBSD, DragonFly BSD,
+    /// NetBSD, Apple platforms and Windows. On other platforms, an error of
+    /// the kind [`Unsupported`](io::ErrorKind::Unsupported) is returned.
+    /// **Note that this may change in the future.**
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn set_keepalive_idle(&self, idle: Duration) -> io::Result<()> {
+        self.0.set_keepalive_idle(idle)
+    }
+
+    /// Gets how long the connection must be idle before the first keepalive
+    /// probe is sent.
+    ///
+    /// For more information about this option, see
+    /// [`TcpStream::set_keepalive_idle`].
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn keepalive_idle(&self) -> io::Result<Duration> {
+        self.0.keepalive_idle()
+    }
+
+    /// Sets the time between two keepalive probes, with the `TCP_KEEPINTVL`
+    /// option.
+    ///
+    /// The time is rounded up to a whole number of seconds. An error of the
+    /// kind [`InvalidInput`](io::ErrorKind::InvalidInput) is returned if it
+    /// is zero. This option is supported on the same platforms as
+    /// [`TcpStream::set_keepalive_idle`].
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()> {
+        self.0.set_keepalive_interval(interval)
+    }
+
+    /// Gets the time between two keepalive probes.
+    ///
+    /// For more information about this option, see
+    /// [`TcpStream::set_keepalive_interval`].
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        self.0.keepalive_interval()
+    }
+
+    /// Sets the number of unanswered keepalive probes after which the
+    /// connection is closed, with the `TCP_KEEPCNT` option.
+    ///
+    /// This option is supported on the same platforms as
+    /// [`TcpStream::set_keepalive_idle`].
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn set_keepalive_retries(&self, retries: u32) -> io::Result<()> {
+        self.0.set_keepalive_retries(retries)
+    }
+
+    /// Gets the number of unanswered keepalive probes after which the
+    /// connection is closed.
+    ///
+    /// For more information about this option, see
+    /// [`TcpStream::set_keepalive_retries`].
+    #[unstable(feature = "tcp_keepalive", issue = "none")]
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        self.0.keepalive_retries()
+    }
+
+    /// Sets the size of the send buffer of this socket, with the `SO_SNDBUF`
+    /// option.
+    ///
+    /// The size is a hint, which the platform may round or clamp, so the
+    /// size returned by [`TcpStream::send_buffer_size`] may differ from it.
+    #[unstable(feature = "tcp_buffer_size", issue = "none")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the size of the send buffer of this socket.
+    ///
+    /// For more information about this option, see
+    /// [`TcpStream::set_send_buffer_size`].
+    #[unstable(feature = "tcp_buffer_size", issue = "none")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
+    /// Sets the size of the receive buffer of this socket, with the
+    /// `SO_RCVBUF` option.
+    ///
+    /// The size is a hint, which the platform may round or clamp, so the
+    /// size returned by [`TcpStream::recv_buffer_size`] may differ from it.
+    /// Setting it on a connected socket doesn't always enlarge the window
+    /// advertised to the peer; see [`TcpSocket::set_recv_buffer_size`] to
+    /// set it before connecting.
+    #[unstable(feature = "tcp_buffer_size", issue = "none")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the size of the receive buffer of this socket.
+    ///
+    /// For more information about this option, see
+    /// [`TcpStream::set_recv_buffer_size`].
+    #[unstable(feature = "tcp_buffer_size", issue = "none")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
     /// Gets the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -1069,3 +1266,233 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
     }
 }
+
+impl TcpSocket {
+    /// Creates a new IPv4 TCP socket.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn new_v4() -> io::Result<TcpSocket> {
+        net_imp::UnboundSocket::new_tcp(false).map(TcpSocket)
+    }
+
+    /// Creates a new IPv6 TCP socket.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn new_v6() -> io::Result<TcpSocket> {
+        net_imp::UnboundSocket::new_tcp(true).map(TcpSocket)
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// On platforms with Berkeley-derived sockets, this allows binding to a
+    /// local address that is still in use by connections in the `TIME_WAIT`
+    /// state, as left behind when a server is restarted. [`TcpListener::bind`]
+    /// sets this option on these platforms.
+    ///
+    /// On Windows, this allows binding to a local address that is actively
+    /// in use by another socket, which can then be hijacked, so it should
+    /// usually be left unset.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_socket)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().unwrap();
+    /// socket.set_reuseaddr(true).expect("set_reuseaddr call failed");
+    /// ```
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        self.0.set_reuseaddr(reuseaddr)
+    }
+
+    /// Gets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`TcpSocket::set_reuseaddr`].
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        self.0.reuseaddr()
+    }
+
+    /// Sets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// This allows several sockets to be bound to the same local address, if
+    /// all of them set this option. On Linux, incoming connections are then
+    /// distributed between the listeners.
+    ///
+    /// ## Platform-specific behavior
+    ///
+    /// This option is only supported on Linux, Android, the BSDs and Apple
+    /// platforms. On other platforms, an error of the kind
+    /// [`Unsupported`](io::ErrorKind::Unsupported) is returned.
+    /// **Note that this may change in the future.**
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        self.0.set_reuseport(reuseport)
+    }
+
+    /// Gets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`TcpSocket::set_reuseport`].
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn reuseport(&self) -> io::Result<bool> {
+        self.0.reuseport()
+    }
+
+    /// Sets the value for the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// If this is set to `true` then the socket is restricted to sending and
+    /// receiving IPv6 packets only. In this case two IPv4 and IPv6
+    /// applications can bind the same port at the same time.
+    ///
+    /// If this is set to `false` then the socket can be used to send and
+    /// receive packets from an IPv4-mapped IPv6 address.
+    ///
+    /// The default value of this option depends on the platform. It can only
+    /// be set on IPv6 sockets.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        self.0.set_only_v6(only_v6)
+    }
+
+    /// Gets the value of the `IPV6_V6ONLY` option for this socket.
+    ///
+    /// For more information about this option, see
+    /// [`TcpSocket::set_only_v6`].
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn only_v6(&self) -> io::Result<bool> {
+        self.0.only_v6()
+    }
+
+    /// Sets the size of the send buffer of this socket, with the `SO_SNDBUF`
+    /// option.
+    ///
+    /// The size is a hint, which the platform may round or clamp, so the
+    /// size returned by [`TcpSocket::send_buffer_size`] may differ from it.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the size of the send buffer of this socket.
+    ///
+    /// For more information about this option, see
+    /// [`TcpSocket::set_send_buffer_size`].
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
+    /// Sets the size of the receive buffer of this socket, with the
+    /// `SO_RCVBUF` option.
+    ///
+    /// The size is a hint, which the platform may round or clamp, so the
+    /// size returned by [`TcpSocket::recv_buffer_size`] may differ from it.
+    /// The receive buffer of a listener is inherited by the connections it
+    /// accepts, and limits the window that is advertised when they are
+    /// established.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the size of the receive buffer of this socket.
+    ///
+    /// For more information about this option, see
+    /// [`TcpSocket::set_recv_buffer_size`].
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Binds this socket to the local address `addr`.
+    ///
+    /// Binding with a port number of 0 will request that the OS assigns a
+    /// port to this socket. The port allocated can be queried via the
+    /// [`TcpSocket::local_addr`] method.
+    ///
+    /// Binding before [`connect`]ing chooses the local address of the
+    /// connection, such as the network interface it goes through.
+    ///
+    /// [`connect`]: TcpSocket::connect
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_socket)]
+    /// use std::net::{SocketAddr, TcpSocket};
+    ///
+    /// let socket = TcpSocket::new_v4().unwrap();
+    /// socket.bind(SocketAddr::from(([192, 168, 0, 2], 0))).expect("bind call failed");
+    /// let stream = socket.connect(SocketAddr::from(([192, 168, 0, 1], 8080))).unwrap();
+    /// ```
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        self.0.bind(&addr)
+    }
+
+    /// Returns the local socket address of this socket, once it is bound.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.socket_addr()
+    }
+
+    /// Starts listening for connections on this socket, and turns it into a
+    /// [`TcpListener`].
+    ///
+    /// `backlog` is the maximum number of connections that are waiting to be
+    /// [`accept`]ed. The platform may round or clamp it.
+    ///
+    /// [`accept`]: TcpListener::accept
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        self.0.listen(backlog).map(TcpListener)
+    }
+
+    /// Opens a TCP connection to a remote host, and turns this socket into a
+    /// [`TcpStream`].
+    ///
+    /// This is like [`TcpStream::connect`], except for the socket used and
+    /// for the single address.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        self.0.connect(&addr).map(TcpStream)
+    }
+
+    /// Opens a TCP connection to a remote host with a timeout, and turns this
+    /// socket into a [`TcpStream`].
+    ///
+    /// This is like [`TcpStream::connect_timeout`], except for the socket
+    /// used. It is an error to pass a zero `Duration` to this function.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn connect_timeout(self, addr: SocketAddr, timeout: Duration) -> io::Result<TcpStream> {
+        self.0.connect_timeout(&addr, timeout).map(TcpStream)
+    }
+}
+
+impl AsInner<net_imp::UnboundSocket> for TcpSocket {
+    #[inline]
+    fn as_inner(&self) -> &net_imp::UnboundSocket {
+        &self.0
+    }
+}
+
+impl FromInner<net_imp::UnboundSocket> for TcpSocket {
+    fn from_inner(inner: net_imp::UnboundSocket) -> TcpSocket {
+        TcpSocket(inner)
+    }
+}
+
+impl IntoInner<net_imp::UnboundSocket> for TcpSocket {
+    fn into_inner(self) -> net_imp::UnboundSocket {
+        self.0
+    }
+}
+
+#[unstable(feature = "tcp_socket", issue = "none")]
+impl fmt::Debug for TcpSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_named("TcpSocket", f)
+    }
+}
@@ -2,7 +2,7 @@
 use crate::io::prelude::*;
 use crate::io::{BorrowedBuf, IoSlice, IoSliceMut};
 use crate::mem::MaybeUninit;
-use crate::net::test::{next_test_ip4, next_test_ip6};
+use crate::net::test::{check_buffer_size, next_test_ip4, next_test_ip6};
 use crate::net::*;
 use crate::sync::mpsc::channel;
 use crate::thread;
@@ -914,3 +914,139 @@ fn connect_timeout_valid() {
     let addr = listener.local_addr().unwrap();
     TcpStream::connect_timeout(&addr, Duration::from_secs(2)).unwrap();
 }
+
+fn tcp_socket(addr: &SocketAddr) -> TcpSocket {
+    match addr {
+        SocketAddr::V4(..) => t!(TcpSocket::new_v4()),
+        SocketAddr::V6(..) => t!(TcpSocket::new_v6()),
+    }
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn tcp_socket_listen_connect() {
+    each_ip(&mut |addr| {
+        let socket = tcp_socket(&addr);
+        t!(socket.bind(addr));
+        assert_eq!(addr, t!(socket.local_addr()));
+        let listener = t!(socket.listen(1));
+
+        // Binds the connection to a local port chosen in advance.
+        let socket = tcp_socket(&addr);
+        let mut local_addr = addr;
+        local_addr.set_port(0);
+        t!(socket.bind(local_addr));
+        let local_addr = t!(socket.local_addr());
+        assert_ne!(local_addr.port(), 0);
+        let mut stream = t!(socket.connect(addr));
+        assert_eq!(local_addr, t!(stream.local_addr()));
+
+        let (mut accepted, peer_addr) = t!(listener.accept());
+        assert_eq!(local_addr, peer_addr);
+        t!(stream.write_all(&[1, 2, 3]));
+        let mut buf = [0; 3];
+        t!(accepted.read_exact(&mut buf));
+        assert_eq!(buf, [1, 2, 3]);
+    })
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn tcp_socket_reuseaddr() {
+    each_ip(&mut |addr| {
+        let socket = tcp_socket(&addr);
+        assert_eq!(false, t!(socket.reuseaddr()));
+        t!(socket.set_reuseaddr(true));
+        assert_eq!(true, t!(socket.reuseaddr()));
+        t!(socket.bind(addr));
+        let listener = t!(socket.listen(1));
+
+        // Closing the accepted connection first leaves it in the `TIME_WAIT`
+        // state, which prevents binding to the address without the option.
+        let stream = t!(TcpStream::connect(addr));
+        drop(t!(listener.accept()).0);
+        drop(listener);
+        drop(stream);
+
+        let socket = tcp_socket(&addr);
+        t!(socket.set_reuseaddr(true));
+        t!(socket.bind(addr));
+        t!(socket.listen(1));
+    })
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn tcp_socket_reuseport() {
+    let addr = next_test_ip4();
+    let listeners: Vec<_> = (0..2)
+        .map(|_| {
+            let socket = t!(TcpSocket::new_v4());
+            t!(socket.set_reuseport(true));
+            assert_eq!(true, t!(socket.reuseport()));
+            t!(socket.bind(addr));
+            t!(socket.listen(1))
+        })
+        .collect();
+    assert_eq!(addr, t!(listeners[1].local_addr()));
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn tcp_socket_options() {
+    each_ip(&mut |addr| {
+        let socket = tcp_socket(&addr);
+        if addr.is_ipv6() {
+            t!(socket.set_only_v6(true));
+            assert_eq!(true, t!(socket.only_v6()));
+            t!(socket.set_only_v6(false));
+            assert_eq!(false, t!(socket.only_v6()));
+        }
+
+        check_buffer_size(|size| socket.set_send_buffer_size(size), || socket.send_buffer_size());
+        check_buffer_size(|size| socket.set_recv_buffer_size(size), || socket.recv_buffer_size());
+    })
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn keepalive() {
+    let addr = next_test_ip4();
+    let _listener = t!(TcpListener::bind(&addr));
+
+    let stream = t!(TcpStream::connect(&("localhost", addr.port())));
+
+    assert_eq!(false, t!(stream.keepalive()));
+    t!(stream.set_keepalive(true));
+    assert_eq!(true, t!(stream.keepalive()));
+    t!(stream.set_keepalive(false));
+    assert_eq!(false, t!(stream.keepalive()));
+
+    if cfg!(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "macos"
+    )) {
+        t!(stream.set_keepalive_idle(Duration::from_secs(60)));
+        assert_eq!(Duration::from_secs(60), t!(stream.keepalive_idle()));
+        // Rounded up to the next second.
+        t!(stream.set_keepalive_interval(Duration::from_millis(4500)));
+        assert_eq!(Duration::from_secs(5), t!(stream.keepalive_interval()));
+        t!(stream.set_keepalive_retries(3));
+        assert_eq!(3, t!(stream.keepalive_retries()));
+        assert!(stream.set_keepalive_idle(Duration::ZERO).is_err());
+    }
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn buffer_size() {
+    let addr = next_test_ip4();
+    let _listener = t!(TcpListener::bind(&addr));
+
+    let stream = t!(TcpStream::connect(&("localhost", addr.port())));
+
+    check_buffer_size(|size| stream.set_send_buffer_size(size), || stream.send_buffer_size());
+    check_buffer_size(|size| stream.set_recv_buffer_size(size), || stream.recv_buffer_size());
+}
@@ -1,6 +1,7 @@
 #![allow(warnings)] // not used on emscripten
 
 use crate::env;
+use crate::io;
 use crate::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs};
 use crate::sync::atomic::{AtomicUsize, Ordering};
 
@@ -31,6 +32,18 @@ pub fn tsa<A: ToSocketAddrs>(a: A) -> Result<Vec<SocketAddr>, String> {
     }
 }
 
+/// Sets a buffer size of a socket with `set`, and checks that `get` returns
+/// at least that size: Linux doubles the sizes, to account for its
+/// bookkeeping.
+pub fn check_buffer_size(
+    set: impl FnOnce(usize) -> io::Result<()>,
+    get: impl FnOnce() -> io::Result<usize>,
+) {
+    const SIZE: usize = 64 * 1024;
+    set(SIZE).unwrap();
+    assert!(get().unwrap() >= SIZE);
+}
+
 // The bots run multiple builds at the same time, and these builds
 // all want to use ports. This function figures out which workspace
 // it is running in and assigns a port range based on it.
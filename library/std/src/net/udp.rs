@@ -2,7 +2,7 @@
 mod tests;
 
 use crate::fmt;
-use crate::io::{self, ErrorKind};
+use crate::io::{self, ErrorKind, IoSlice, IoSliceMut};
 use crate::net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
 use crate::sys_common::net as net_imp;
 use crate::sys_common::{AsInner, FromInner, IntoInner};
@@ -57,6 +57,42 @@
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct UdpSocket(net_imp::UdpSocket);
 
+/// A UDP socket that is not bound yet.
+///
+/// [`UdpSocket::bind`] creates, configures and binds a socket in a single
+/// call. A `UdpSocketBuilder` instead allows setting the options that only
+/// take effect when they are set before the socket is bound, such as
+/// [`set_reuseaddr`] or [`set_only_v6`]. The socket is then turned into a
+/// [`UdpSocket`] by [`bind`].
+///
+/// The socket will be closed when the value is dropped.
+///
+/// [`bind`]: UdpSocketBuilder::bind
+/// [`set_only_v6`]: UdpSocketBuilder::set_only_v6
+/// [`set_reuseaddr`]: UdpSocketBuilder::set_reuseaddr
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(udp_socket_builder)]
+/// use std::net::{Ipv6Addr, SocketAddr, UdpSocketBuilder};
+///
+/// fn main() -> std::io::Result<()> {
+///     // a socket for both IPv4 and IPv6, sharing its port with other processes
+///     let builder = UdpSocketBuilder::new_v6()?;
+///     builder.set_reuseport(true)?;
+///     builder.set_only_v6(false)?;
+///     let socket = builder.bind(SocketAddr::from((Ipv6Addr::UNSPECIFIED, 5353)))?;
+///
+///     let mut buf = [0; 1500];
+///     let (amt, src) = socket.recv_from(&mut buf)?;
+///     socket.send_to(&buf[..amt], src)?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "udp_socket_builder", issue = "none")]
+pub struct UdpSocketBuilder(net_imp::UnboundSocket);
+
 impl UdpSocket {
     /// Creates a UDP socket from the given address.
     ///
@@ -202,6 +238,71 @@ pub fn send_to<A: ToSocketAddrs>(&self, buf: &[u8], addr: A) -> io::Result<usize
         }
     }
 
+    /// Receives a single datagram message on the socket, like
+    /// [`recv_from`], but scatters it into the buffers of `bufs`. On success,
+    /// returns the number of bytes read and the origin.
+    ///
+    /// The buffers are filled in order. If the message is too long to fit in
+    /// all of them, excess bytes may be discarded.
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_vectored)]
+    /// use std::io::IoSliceMut;
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// let mut header = [0; 4];
+    /// let mut payload = [0; 1024];
+    /// let (number_of_bytes, src_addr) = socket
+    ///     .recv_vectored_from(&mut [IoSliceMut::new(&mut header), IoSliceMut::new(&mut payload)])
+    ///     .expect("Didn't receive data");
+    /// ```
+    #[unstable(feature = "udp_vectored", issue = "none")]
+    pub fn recv_vectored_from(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, SocketAddr)> {
+        self.0.recv_vectored_from(bufs)
+    }
+
+    /// Sends the concatenation of the buffers of `bufs` as a single datagram
+    /// to the given address, like [`send_to`]. On success, returns the number
+    /// of bytes written.
+    ///
+    /// [`send_to`]: UdpSocket::send_to
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_vectored)]
+    /// use std::io::IoSlice;
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// let header = [1, 2, 3, 4];
+    /// let payload = [0; 10];
+    /// socket
+    ///     .send_vectored_to(&[IoSlice::new(&header), IoSlice::new(&payload)], "127.0.0.1:4242")
+    ///     .expect("couldn't send data");
+    /// ```
+    #[unstable(feature = "udp_vectored", issue = "none")]
+    pub fn send_vectored_to<A: ToSocketAddrs>(
+        &self,
+        bufs: &[IoSlice<'_>],
+        addr: A,
+    ) -> io::Result<usize> {
+        match addr.to_socket_addrs()?.next() {
+            Some(addr) => self.0.send_vectored_to(bufs, &addr),
+            None => {
+                Err(io::const_io_error!(ErrorKind::InvalidInput, "no addresses to send data to"))
+            }
+        }
+    }
+
     /// Returns the socket address of the remote peer this socket was connected to.
     ///
     /// # Examples
@@ -815,6 +916,191 @@ pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
 // `AsSocket`/`From<OwnedSocket>`/`Into<OwnedSocket>` and
 // `AsRawSocket`/`IntoRawSocket`/`FromRawSocket` on Windows.
 
+impl UdpSocketBuilder {
+    /// Creates a new IPv4 UDP socket.
+    #[unstable(feature = "udp_socket_builder", issue = "none")]
+    pub fn new_v4() -> io::Result<UdpSocketBuilder> {
+        net_imp::UnboundSocket::new_udp(false).map(UdpSocketBuilder)
+    }
+
+    /// Creates a new IPv6 UDP socket.
+    #[unstable(feature = "udp_socket_builder", issue = "none")]
+    pub fn new_v6() -> io::Result<UdpSocketBuilder> {
+        net_imp::UnboundSocket::new_udp(true).map(UdpSocketBuilder)
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// On platforms with Berkeley-derived sockets, this allows several
+    /// sockets to be bound to the same multicast address, if all of them set
+    /// this option.
+    ///
+    /// On Windows, this allows binding to a local address that is actively
+    /// in use by another socket, which can then be hijacked, so it should
+    /// usually be left unset.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v4().unwrap();
+    /// builder.set_reuseaddr(true).expect("set_reuseaddr call failed");
+    /// ```
+    #[unstable(feature = "udp_socket_builder", issue = "none")]
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        self.0.set_reuseaddr(reuseaddr)
+    }
+
+    /// Gets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`UdpSocketBuilder::set_reuseaddr`].
+    #[unstable(feature = "udp_socket_builder", issue = "none")]
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        self.0.reuseaddr()
+    }
+
+    /// Sets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// This allows several sockets to be bound to the same local address, if
+    /// all of them set this option. On Linux, the datagrams sent to a unicast
+    /// address are then distributed between the sockets.
+    ///
+    /// ## Platform-specific behavior
+    ///
+    /// This option is only supported on Linux, Android, the BSDs and Apple
+    /// platforms. On other platforms, an error of the kind
+    /// [`Unsupported`](io::ErrorKind::Unsupported) is returned.
+    /// **Note that this may change in the future.**
+    #[unstable(feature = "udp_socket_builder", issue = "none")]
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        self.0.set_reuseport(reuseport)
+    }
+
+    /// Gets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`UdpSocketBuilder::set_reuseport`].
+    #[unstable(feature = "udp_socket_builder", issue = "none")]
+    pub fn reuseport(&self) -> io::Result<bool> {
+        self.0.reuseport()
+    }
+
+    /// Sets the value for the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// If this is set to `true` then the socket is restricted to sending and
+    /// receiving IPv6 packets only. In this case two IPv4 and IPv6
+    /// applications can bind the same port at the same time.
+    ///
+    /// If this is set to `false` then the socket can be used to send and
+    /// receive packets from an IPv4-mapped IPv6 address.
+    ///
+    /// The default value of this option depends on the platform. It can only
+    /// be set on IPv6 sockets.
+    #[unstable(feature = "udp_socket_builder", issue = "none")]
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        self.0.set_only_v6(only_v6)
+    }
+
+    /// Gets the value of the `IPV6_V6ONLY` option for this socket.
+    ///
+    /// For more information about this option, see
+    /// [`UdpSocketBuilder::set_only_v6`].
+    #[unstable(feature = "udp_socket_builder", issue = "none")]
+    pub fn only_v6(&self) -> io::Result<bool> {
+        self.0.only_v6()
+    }
+
+    /// Sets the size of the send buffer of this socket, with the `SO_SNDBUF`
+    /// option.
+    ///
+    /// The size is a hint, which the platform may round or clamp, so the
+    /// size returned by [`UdpSocketBuilder::send_buffer_size`] may differ
+    /// from it.
+    #[unstable(feature = "udp_socket_builder", issue = "none")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the size of the send buffer of this socket.
+    ///
+    /// For more information about this option, see
+    /// [`UdpSocketBuilder::set_send_buffer_size`].
+    #[unstable(feature = "udp_socket_builder", issue = "none")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
+    /// Sets the size of the receive buffer of this socket, with the
+    /// `SO_RCVBUF` option.
+    ///
+    /// The size is a hint, which the platform may round or clamp, so the
+    /// size returned by [`UdpSocketBuilder::recv_buffer_size`] may differ
+    /// from it. Datagrams that arrive while the buffer is full are dropped.
+    #[unstable(feature = "udp_socket_builder", issue = "none")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the size of the receive buffer of this socket.
+    ///
+    /// For more information about this option, see
+    /// [`UdpSocketBuilder::set_recv_buffer_size`].
+    #[unstable(feature = "udp_socket_builder", issue = "none")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Binds this socket to the local address `addr`, and turns it into a
+    /// [`UdpSocket`].
+    ///
+    /// This is like [`UdpSocket::bind`], except for the socket used and for
+    /// the single address.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_socket_builder)]
+    /// use std::net::{SocketAddr, UdpSocketBuilder};
+    ///
+    /// let builder = UdpSocketBuilder::new_v4().unwrap();
+    /// let socket = builder.bind(SocketAddr::from(([127, 0, 0, 1], 0))).expect("bind call failed");
+    /// ```
+    #[unstable(feature = "udp_socket_builder", issue = "none")]
+    pub fn bind(self, addr: SocketAddr) -> io::Result<UdpSocket> {
+        self.0.bind(&addr)?;
+        Ok(UdpSocket(self.0.into_udp()))
+    }
+}
+
+impl AsInner<net_imp::UnboundSocket> for UdpSocketBuilder {
+    #[inline]
+    fn as_inner(&self) -> &net_imp::UnboundSocket {
+        &self.0
+    }
+}
+
+impl FromInner<net_imp::UnboundSocket> for UdpSocketBuilder {
+    fn from_inner(inner: net_imp::UnboundSocket) -> UdpSocketBuilder {
+        UdpSocketBuilder(inner)
+    }
+}
+
+impl IntoInner<net_imp::UnboundSocket> for UdpSocketBuilder {
+    fn into_inner(self) -> net_imp::UnboundSocket {
+        self.0
+    }
+}
+
+#[unstable(feature = "udp_socket_builder", issue = "none")]
+impl fmt::Debug for UdpSocketBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_named("UdpSocketBuilder", f)
+    }
+}
+
 impl AsInner<net_imp::UdpSocket> for UdpSocket {
     #[inline]
     fn as_inner(&self) -> &net_imp::UdpSocket {
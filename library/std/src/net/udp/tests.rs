@@ -1,4 +1,5 @@
-use crate::net::test::{next_test_ip4, next_test_ip6};
+use crate::io::{IoSlice, IoSliceMut};
+use crate::net::test::{check_buffer_size, next_test_ip4, next_test_ip6};
 use crate::net::*;
 use crate::sync::mpsc::channel;
 use crate::thread;
@@ -365,3 +366,83 @@ fn set_nonblocking() {
         }
     })
 }
+
+#[test]
+fn send_recv_vectored() {
+    each_ip(&mut |server_ip, client_ip| {
+        let server = t!(UdpSocket::bind(&server_ip));
+        let client = t!(UdpSocket::bind(&client_ip));
+
+        let size = t!(client.send_vectored_to(
+            &[IoSlice::new(b"hello"), IoSlice::new(b""), IoSlice::new(b" world")],
+            &server_ip
+        ));
+        assert_eq!(size, 11);
+
+        let mut head = [0; 3];
+        let mut tail = [0; 16];
+        let (size, addr) = t!(server
+            .recv_vectored_from(&mut [IoSliceMut::new(&mut head), IoSliceMut::new(&mut tail)]));
+        assert_eq!(size, 11);
+        assert_eq!(addr, client_ip);
+        assert_eq!(b"hel", &head);
+        assert_eq!(b"lo world", &tail[..8]);
+    })
+}
+
+fn udp_socket_builder(addr: &SocketAddr) -> UdpSocketBuilder {
+    match addr {
+        SocketAddr::V4(..) => t!(UdpSocketBuilder::new_v4()),
+        SocketAddr::V6(..) => t!(UdpSocketBuilder::new_v6()),
+    }
+}
+
+#[test]
+fn udp_socket_builder_bind() {
+    each_ip(&mut |server_ip, client_ip| {
+        let server = t!(udp_socket_builder(&server_ip).bind(server_ip));
+        assert_eq!(server_ip, t!(server.local_addr()));
+        let client = t!(UdpSocket::bind(&client_ip));
+
+        t!(client.send_to(&[99], &server_ip));
+        let mut buf = [0];
+        let (size, addr) = t!(server.recv_from(&mut buf));
+        assert_eq!(size, 1);
+        assert_eq!(buf[0], 99);
+        assert_eq!(addr, client_ip);
+    })
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn udp_socket_builder_reuseport() {
+    let addr = next_test_ip4();
+    let sockets: Vec<_> = (0..2)
+        .map(|_| {
+            let builder = t!(UdpSocketBuilder::new_v4());
+            t!(builder.set_reuseport(true));
+            assert_eq!(true, t!(builder.reuseport()));
+            t!(builder.bind(addr))
+        })
+        .collect();
+    assert_eq!(addr, t!(sockets[1].local_addr()));
+}
+
+#[test]
+fn udp_socket_builder_options() {
+    each_ip(&mut |addr, _| {
+        let builder = udp_socket_builder(&addr);
+        assert_eq!(false, t!(builder.reuseaddr()));
+        t!(builder.set_reuseaddr(true));
+        assert_eq!(true, t!(builder.reuseaddr()));
+        if addr.is_ipv6() {
+            t!(builder.set_only_v6(true));
+            assert_eq!(true, t!(builder.only_v6()));
+            t!(builder.set_only_v6(false));
+            assert_eq!(false, t!(builder.only_v6()));
+        }
+
+        check_buffer_size(|size| builder.set_send_buffer_size(size), || builder.send_buffer_size());
+        check_buffer_size(|size| builder.set_recv_buffer_size(size), || builder.recv_buffer_size());
+    })
+}
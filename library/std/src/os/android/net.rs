@@ -7,3 +7,7 @@
 
 #[unstable(feature = "tcp_quickack", issue = "96256")]
 pub use crate::os::net::linux_ext::tcp::TcpStreamExt;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[unstable(feature = "udp_ancillary_data", issue = "none")]
+pub use crate::os::net::linux_ext::udp::UdpSocketExt;
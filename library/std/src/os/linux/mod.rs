@@ -7,3 +7,4 @@
 pub mod net;
 pub mod process;
 pub mod raw;
+pub mod thread;
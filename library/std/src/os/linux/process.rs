@@ -51,6 +51,48 @@ pub struct PidFd {
     inner: FileDesc,
 }
 
+impl PidFd {
+    /// Waits for the process referred to by this pidfd to exit, returning the
+    /// status that it exited with.
+    ///
+    /// This reaps the process, so it must be a child of the current process.
+    /// Since the exit status isn't recorded in the [`Child`] it was taken
+    /// from, waiting for the child afterwards through the `Child` will fail.
+    ///
+    /// This corresponds to `waitid` with `P_PIDFD`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_pidfd)]
+    /// use std::os::linux::process::{ChildExt, CommandExt};
+    /// use std::process::Command;
+    ///
+    /// let mut child = Command::new("true").create_pidfd(true).spawn()?;
+    /// let pidfd = child.take_pidfd()?;
+    /// assert!(pidfd.wait()?.success());
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`Child`]: process::Child
+    #[doc(alias = "waitid")]
+    pub fn wait(&self) -> Result<process::ExitStatus> {
+        crate::sys::process::pidfd_wait(self).map(FromInner::from_inner)
+    }
+
+    /// Collects the exit status of the process referred to by this pidfd if
+    /// it has already exited, without blocking.
+    ///
+    /// As with [`wait`](PidFd::wait), this reaps the process. To wait for
+    /// the process with a timeout, or along with other file descriptors,
+    /// poll the pidfd for readability first: it becomes readable when the
+    /// process exits.
+    pub fn try_wait(&self) -> Result<Option<process::ExitStatus>> {
+        Ok(crate::sys::process::pidfd_try_wait(self)?.map(FromInner::from_inner))
+    }
+}
+
 impl AsInner<FileDesc> for PidFd {
     #[inline]
     fn as_inner(&self) -> &FileDesc {
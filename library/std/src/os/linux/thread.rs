@@ -0,0 +1,213 @@
+//! Linux-specific extensions to primitives in the [`std::thread`] module.
+//!
+//! [`std::thread`]: crate::thread
+
+#![unstable(feature = "linux_thread_ext", issue = "none")]
+
+use crate::ffi::c_ulong;
+use crate::fmt;
+use crate::io;
+use crate::sealed::Sealed;
+use crate::sys;
+use crate::sys_common::AsInnerMut;
+use crate::thread;
+
+const BITS_PER_WORD: usize = c_ulong::BITS as usize;
+
+/// A set of CPUs, used as the affinity mask of a thread.
+///
+/// The set can hold CPUs numbered from 0 to [`CpuSet::MAX_CPUS`] excluded,
+/// which matches the `cpu_set_t` type of the C library.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(linux_thread_ext)]
+/// use std::os::linux::thread::{self, CpuSet};
+///
+/// // Pin the current thread to the first two CPUs.
+/// let cpus: CpuSet = [0, 1].into_iter().collect();
+/// thread::set_affinity(&cpus).unwrap();
+/// assert_eq!(thread::affinity().unwrap(), cpus);
+/// ```
+// The layout matches `cpu_set_t`, so a pointer to a `CpuSet` can be passed
+// to `sched_setaffinity` and `sched_getaffinity`.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CpuSet {
+    bits: [c_ulong; CpuSet::MAX_CPUS / BITS_PER_WORD],
+}
+
+impl CpuSet {
+    /// The number of CPUs that can be represented in a set.
+    pub const MAX_CPUS: usize = 1024;
+
+    /// Creates an empty set.
+    #[must_use]
+    pub const fn new() -> CpuSet {
+        CpuSet { bits: [0; CpuSet::MAX_CPUS / BITS_PER_WORD] }
+    }
+
+    /// Adds a CPU to the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cpu` isn't less than [`CpuSet::MAX_CPUS`].
+    pub fn insert(&mut self, cpu: usize) {
+        assert!(cpu < CpuSet::MAX_CPUS, "CPU {cpu} is out of range for a CpuSet");
+        self.bits[cpu / BITS_PER_WORD] |= 1 << (cpu % BITS_PER_WORD);
+    }
+
+    /// Removes a CPU from the set.
+    pub fn remove(&mut self, cpu: usize) {
+        if cpu < CpuSet::MAX_CPUS {
+            self.bits[cpu / BITS_PER_WORD] &= !(1 << (cpu % BITS_PER_WORD));
+        }
+    }
+
+    /// Returns `true` if the set contains the given CPU.
+    #[must_use]
+    pub fn contains(&self, cpu: usize) -> bool {
+        cpu < CpuSet::MAX_CPUS && self.bits[cpu / BITS_PER_WORD] & (1 << (cpu % BITS_PER_WORD)) != 0
+    }
+
+    /// Returns the number of CPUs in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.bits.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Returns `true` if the set contains no CPUs.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&word| word == 0)
+    }
+
+    /// Returns an iterator over the CPUs in the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..CpuSet::MAX_CPUS).filter(|&cpu| self.contains(cpu))
+    }
+}
+
+impl Default for CpuSet {
+    fn default() -> CpuSet {
+        CpuSet::new()
+    }
+}
+
+impl fmt::Debug for CpuSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<usize> for CpuSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> CpuSet {
+        let mut set = CpuSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<usize> for CpuSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for cpu in iter {
+            self.insert(cpu);
+        }
+    }
+}
+
+/// A scheduling policy of the Linux kernel.
+///
+/// Refer to the man page of [`sched(7)`] for the details of each policy.
+///
+/// [`sched(7)`]: https://man7.org/linux/man-pages/man7/sched.7.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SchedPolicy {
+    /// The default time-sharing policy, `SCHED_OTHER`.
+    Other,
+    /// The first-in first-out real-time policy, `SCHED_FIFO`.
+    Fifo,
+    /// The round-robin real-time policy, `SCHED_RR`.
+    RoundRobin,
+    /// The policy for CPU-intensive batch processes, `SCHED_BATCH`.
+    Batch,
+    /// The policy for very low priority background jobs, `SCHED_IDLE`.
+    Idle,
+}
+
+/// Linux-specific extensions to [`thread::Builder`].
+///
+/// The settings are applied by the spawned thread to itself, before it runs
+/// its main function. Setting any of them makes [`spawn`] wait until the new
+/// thread has applied them, so that if that fails, the error is returned by
+/// [`spawn`] and the main function isn't run.
+///
+/// [`spawn`]: thread::Builder::spawn
+pub trait BuilderExt: Sealed {
+    /// Sets the CPU affinity mask of the new thread, so that it only runs on
+    /// the given CPUs.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_thread_ext)]
+    /// use std::os::linux::thread::{BuilderExt, CpuSet};
+    /// use std::thread::Builder;
+    ///
+    /// let handle = Builder::new()
+    ///     .affinity([3].into_iter().collect::<CpuSet>())
+    ///     .spawn(|| {
+    ///         // thread code, running on CPU 3 only
+    ///     })
+    ///     .unwrap();
+    /// handle.join().unwrap();
+    /// ```
+    fn affinity(self, cpus: CpuSet) -> thread::Builder;
+
+    /// Sets the scheduling policy and priority of the new thread.
+    ///
+    /// The priority must be between 1 and 99 for the real-time policies
+    /// [`Fifo`](SchedPolicy::Fifo) and [`RoundRobin`](SchedPolicy::RoundRobin),
+    /// and 0 for the others. Using a real-time policy usually requires the
+    /// `CAP_SYS_NICE` capability.
+    fn scheduling(self, policy: SchedPolicy, priority: i32) -> thread::Builder;
+}
+
+impl BuilderExt for thread::Builder {
+    fn affinity(mut self, cpus: CpuSet) -> thread::Builder {
+        self.as_inner_mut().affinity = Some(cpus);
+        self
+    }
+
+    fn scheduling(mut self, policy: SchedPolicy, priority: i32) -> thread::Builder {
+        self.as_inner_mut().scheduling = Some((policy, priority));
+        self
+    }
+}
+
+/// Returns the CPU affinity mask of the current thread.
+///
+/// This corresponds to the `sched_getaffinity` function.
+#[doc(alias = "sched_getaffinity")]
+pub fn affinity() -> io::Result<CpuSet> {
+    sys::thread::affinity()
+}
+
+/// Sets the CPU affinity mask of the current thread.
+///
+/// This corresponds to the `sched_setaffinity` function.
+#[doc(alias = "sched_setaffinity")]
+pub fn set_affinity(cpus: &CpuSet) -> io::Result<()> {
+    sys::thread::set_affinity(cpus)
+}
+
+/// Sets the scheduling policy and priority of the current thread.
+///
+/// See [`BuilderExt::scheduling`] for the valid priorities. This corresponds
+/// to the `pthread_setschedparam` function.
+#[doc(alias = "pthread_setschedparam")]
+pub fn set_scheduling(policy: SchedPolicy, priority: i32) -> io::Result<()> {
+    sys::thread::set_scheduling(policy, priority)
+}
@@ -8,5 +8,9 @@
 #[unstable(feature = "tcp_quickack", issue = "96256")]
 pub(crate) mod tcp;
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[unstable(feature = "udp_ancillary_data", issue = "none")]
+pub(crate) mod udp;
+
 #[cfg(test)]
 mod tests;
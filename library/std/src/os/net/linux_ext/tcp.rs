@@ -6,6 +6,7 @@
 use crate::net;
 use crate::sealed::Sealed;
 use crate::sys_common::AsInner;
+use crate::time::Duration;
 
 /// Os-specific extensions for [`TcpStream`]
 ///
@@ -53,6 +54,38 @@ pub trait TcpStreamExt: Sealed {
     /// ```
     #[unstable(feature = "tcp_quickack", issue = "96256")]
     fn quickack(&self) -> io::Result<bool>;
+
+    /// Sets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// This is the longest time that data sent on the connection may remain
+    /// unacknowledged before Linux closes the connection. It also bounds the
+    /// time a connection may go without answering the probes of keepalive.
+    /// Setting it to `None` uses the timeouts of the system instead.
+    ///
+    /// See [`man 7 tcp`](https://man7.org/linux/man-pages/man7/tcp.7.html)
+    /// for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_user_timeout)]
+    /// use std::net::TcpStream;
+    /// use std::os::linux::net::TcpStreamExt;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///         .expect("Couldn't connect to the server...");
+    /// stream.set_user_timeout(Some(Duration::from_secs(30)))
+    ///     .expect("set_user_timeout call failed");
+    /// ```
+    #[unstable(feature = "tcp_user_timeout", issue = "none")]
+    fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+
+    /// Gets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStreamExt::set_user_timeout`].
+    #[unstable(feature = "tcp_user_timeout", issue = "none")]
+    fn user_timeout(&self) -> io::Result<Option<Duration>>;
 }
 
 #[unstable(feature = "tcp_quickack", issue = "96256")]
@@ -67,4 +100,12 @@ fn set_quickack(&self, quickack: bool) -> io::Result<()> {
     fn quickack(&self) -> io::Result<bool> {
         self.as_inner().as_inner().quickack()
     }
+
+    fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.as_inner().as_inner().set_user_timeout(timeout)
+    }
+
+    fn user_timeout(&self) -> io::Result<Option<Duration>> {
+        self.as_inner().as_inner().user_timeout()
+    }
 }
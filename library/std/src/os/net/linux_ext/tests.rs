@@ -26,3 +26,96 @@ macro_rules! t {
     t!(stream.set_quickack(false));
     assert_eq!(false, t!(stream.quickack()));
 }
+
+#[test]
+fn user_timeout() {
+    use crate::{
+        net::{test::next_test_ip4, TcpListener, TcpStream},
+        os::net::linux_ext::tcp::TcpStreamExt,
+        time::Duration,
+    };
+
+    macro_rules! t {
+        ($e:expr) => {
+            match $e {
+                Ok(t) => t,
+                Err(e) => panic!("received error for `{}`: {}", stringify!($e), e),
+            }
+        };
+    }
+
+    let addr = next_test_ip4();
+    let _listener = t!(TcpListener::bind(&addr));
+
+    let stream = t!(TcpStream::connect(&("localhost", addr.port())));
+
+    assert_eq!(None, t!(stream.user_timeout()));
+    t!(stream.set_user_timeout(Some(Duration::from_secs(30))));
+    assert_eq!(Some(Duration::from_secs(30)), t!(stream.user_timeout()));
+    t!(stream.set_user_timeout(None));
+    assert_eq!(None, t!(stream.user_timeout()));
+    assert!(stream.set_user_timeout(Some(Duration::ZERO)).is_err());
+}
+
+#[test]
+fn udp_pktinfo() {
+    use crate::{
+        io::{IoSlice, IoSliceMut},
+        net::{test::next_test_ip4, Ipv4Addr, UdpSocket},
+        os::net::linux_ext::udp::UdpSocketExt,
+        os::unix::net::{AncillaryData, IpPktInfo, SocketAncillary},
+    };
+
+    macro_rules! t {
+        ($e:expr) => {
+            match $e {
+                Ok(t) => t,
+                Err(e) => panic!("received error for `{}`: {}", stringify!($e), e),
+            }
+        };
+    }
+
+    let addr1 = next_test_ip4();
+    let addr2 = next_test_ip4();
+    let socket1 = t!(UdpSocket::bind(&addr1));
+    let socket2 = t!(UdpSocket::bind(&addr2));
+
+    assert_eq!(false, t!(socket2.recv_pktinfo()));
+    t!(socket2.set_recv_pktinfo(true));
+    assert_eq!(true, t!(socket2.recv_pktinfo()));
+    t!(socket2.set_recv_timestamp(true));
+    assert_eq!(true, t!(socket2.recv_timestamp()));
+
+    let mut send_buffer = [0; 64];
+    let mut send_ancillary = SocketAncillary::new(&mut send_buffer[..]);
+    assert!(send_ancillary.add_ip_pktinfo(&IpPktInfo::new(0, Ipv4Addr::LOCALHOST)));
+    let bufs = [IoSlice::new(b"hello "), IoSlice::new(b"world")];
+    let sent = t!(socket1.send_vectored_with_ancillary_to(&bufs, &mut send_ancillary, &addr2));
+    assert_eq!(sent, 11);
+
+    let mut buf = [0; 16];
+    let mut recv_buffer = [0; 128];
+    let mut recv_ancillary = SocketAncillary::new(&mut recv_buffer[..]);
+    let mut bufs = [IoSliceMut::new(&mut buf)];
+    let (size, truncated, from) =
+        t!(socket2.recv_vectored_with_ancillary_from(&mut bufs, &mut recv_ancillary));
+    assert_eq!(size, 11);
+    assert!(!truncated);
+    assert_eq!(from, addr1);
+    assert_eq!(&buf[..size], b"hello world");
+
+    let mut pktinfo = None;
+    let mut timestamp = None;
+    for data in recv_ancillary.messages() {
+        match data.unwrap() {
+            AncillaryData::IpPktInfo(info) => pktinfo = Some(info),
+            AncillaryData::Timestamp(time) => timestamp = Some(time),
+            _ => {}
+        }
+    }
+    let pktinfo = pktinfo.expect("no IP_PKTINFO control message");
+    assert_eq!(pktinfo.local_addr(), Ipv4Addr::LOCALHOST);
+    assert_eq!(pktinfo.dest_addr(), Ipv4Addr::LOCALHOST);
+    assert_ne!(pktinfo.interface(), 0);
+    assert!(timestamp.is_some());
+}
@@ -0,0 +1,222 @@
+//! Linux and Android-specific udp extensions to primitives in the [`std::net`] module.
+//!
+//! [`std::net`]: crate::net
+
+use crate::io::{self, IoSlice, IoSliceMut};
+use crate::net::{self, SocketAddr, ToSocketAddrs};
+use crate::os::unix::net::{
+    recv_vectored_with_ancillary_from_inet, send_vectored_with_ancillary_to_inet, SocketAncillary,
+};
+use crate::sealed::Sealed;
+use crate::sys_common::AsInner;
+
+/// Os-specific extensions for [`UdpSocket`]
+///
+/// [`UdpSocket`]: net::UdpSocket
+#[unstable(feature = "udp_ancillary_data", issue = "none")]
+pub trait UdpSocketExt: Sealed {
+    /// Enable or disable the reception of the local address and interface
+    /// of the packets received on this socket.
+    ///
+    /// This sets `IP_PKTINFO` on IPv4 sockets and `IPV6_RECVPKTINFO` on
+    /// IPv6 sockets. The information is received as an
+    /// [`AncillaryData::IpPktInfo`] or [`AncillaryData::Ipv6PktInfo`]
+    /// control message by [`recv_vectored_with_ancillary_from`].
+    ///
+    /// See [`man 7 ip`](https://man7.org/linux/man-pages/man7/ip.7.html) and
+    /// [`man 7 ipv6`](https://man7.org/linux/man-pages/man7/ipv6.7.html)
+    /// for more information.
+    ///
+    /// [`AncillaryData::IpPktInfo`]: crate::os::unix::net::AncillaryData::IpPktInfo
+    /// [`AncillaryData::Ipv6PktInfo`]: crate::os::unix::net::AncillaryData::Ipv6PktInfo
+    /// [`recv_vectored_with_ancillary_from`]: UdpSocketExt::recv_vectored_with_ancillary_from
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_ancillary_data)]
+    /// use std::net::UdpSocket;
+    /// use std::os::linux::net::UdpSocketExt;
+    ///
+    /// let socket = UdpSocket::bind("0.0.0.0:4433").expect("couldn't bind to address");
+    /// socket.set_recv_pktinfo(true).expect("set_recv_pktinfo call failed");
+    /// ```
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    fn set_recv_pktinfo(&self, recv_pktinfo: bool) -> io::Result<()>;
+
+    /// Gets whether the local address and interface of the packets received
+    /// on this socket are received.
+    ///
+    /// For more information about this option, see [`UdpSocketExt::set_recv_pktinfo`].
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    fn recv_pktinfo(&self) -> io::Result<bool>;
+
+    /// Enable or disable the reception of the time at which the packets
+    /// received on this socket were received, as an
+    /// [`AncillaryData::Timestamp`] control message.
+    ///
+    /// This sets the `SO_TIMESTAMP` option. See
+    /// [`man 7 socket`](https://man7.org/linux/man-pages/man7/socket.7.html)
+    /// for more information.
+    ///
+    /// [`AncillaryData::Timestamp`]: crate::os::unix::net::AncillaryData::Timestamp
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    fn set_recv_timestamp(&self, recv_timestamp: bool) -> io::Result<()>;
+
+    /// Gets the value of the `SO_TIMESTAMP` option on this socket.
+    ///
+    /// For more information about this option, see [`UdpSocketExt::set_recv_timestamp`].
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    fn recv_timestamp(&self) -> io::Result<bool>;
+
+    /// Enable or disable generic receive offload on this socket.
+    ///
+    /// With this enabled, Linux may coalesce consecutive packets of the same
+    /// size from the same sender into a single packet, whose segment size is
+    /// received as an [`AncillaryData::UdpGroSegmentSize`] control message.
+    /// This sets the `UDP_GRO` option, which requires Linux 5.0 or later.
+    ///
+    /// [`AncillaryData::UdpGroSegmentSize`]: crate::os::unix::net::AncillaryData::UdpGroSegmentSize
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    fn set_gro(&self, gro: bool) -> io::Result<()>;
+
+    /// Gets the value of the `UDP_GRO` option on this socket.
+    ///
+    /// For more information about this option, see [`UdpSocketExt::set_gro`].
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    fn gro(&self) -> io::Result<bool>;
+
+    /// Receives data and ancillary data on the socket.
+    ///
+    /// On success, returns the number of bytes read, if the data was
+    /// truncated and the address from where the data came.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_ancillary_data, unix_socket_ancillary_data)]
+    /// use std::io::IoSliceMut;
+    /// use std::net::UdpSocket;
+    /// use std::os::linux::net::UdpSocketExt;
+    /// use std::os::unix::net::{AncillaryData, SocketAncillary};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let socket = UdpSocket::bind("0.0.0.0:4433")?;
+    ///     socket.set_recv_pktinfo(true)?;
+    ///
+    ///     let mut buf = [0; 1500];
+    ///     let bufs = &mut [IoSliceMut::new(&mut buf)][..];
+    ///     let mut ancillary_buffer = [0; 128];
+    ///     let mut ancillary = SocketAncillary::new(&mut ancillary_buffer[..]);
+    ///     let (size, _truncated, sender) =
+    ///         socket.recv_vectored_with_ancillary_from(bufs, &mut ancillary)?;
+    ///     println!("received {size} bytes from {sender}");
+    ///     for ancillary_result in ancillary.messages() {
+    ///         if let AncillaryData::IpPktInfo(info) = ancillary_result.unwrap() {
+    ///             println!("received on {}", info.local_addr());
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    fn recv_vectored_with_ancillary_from(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        ancillary: &mut SocketAncillary<'_>,
+    ) -> io::Result<(usize, bool, SocketAddr)>;
+
+    /// Sends data and ancillary data on the socket to the specified address.
+    ///
+    /// On success, returns the number of bytes written. As with
+    /// [`UdpSocket::send_to`], only the first address `addr` resolves to is
+    /// used.
+    ///
+    /// [`UdpSocket::send_to`]: net::UdpSocket::send_to
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_ancillary_data, unix_socket_ancillary_data)]
+    /// use std::io::IoSlice;
+    /// use std::net::{Ipv4Addr, UdpSocket};
+    /// use std::os::linux::net::UdpSocketExt;
+    /// use std::os::unix::net::{IpPktInfo, SocketAncillary};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let socket = UdpSocket::bind("0.0.0.0:0")?;
+    ///
+    ///     let mut ancillary_buffer = [0; 128];
+    ///     let mut ancillary = SocketAncillary::new(&mut ancillary_buffer[..]);
+    ///     ancillary.add_ip_pktinfo(&IpPktInfo::new(0, Ipv4Addr::new(192, 0, 2, 1)));
+    ///
+    ///     let buf = [1; 8];
+    ///     let bufs = &[IoSlice::new(&buf)][..];
+    ///     socket.send_vectored_with_ancillary_to(bufs, &mut ancillary, "192.0.2.2:4433")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    fn send_vectored_with_ancillary_to<A: ToSocketAddrs>(
+        &self,
+        bufs: &[IoSlice<'_>],
+        ancillary: &mut SocketAncillary<'_>,
+        addr: A,
+    ) -> io::Result<usize>;
+}
+
+#[unstable(feature = "udp_ancillary_data", issue = "none")]
+impl Sealed for net::UdpSocket {}
+
+#[unstable(feature = "udp_ancillary_data", issue = "none")]
+impl UdpSocketExt for net::UdpSocket {
+    fn set_recv_pktinfo(&self, recv_pktinfo: bool) -> io::Result<()> {
+        let v6 = self.local_addr()?.is_ipv6();
+        self.as_inner().socket().set_recv_pktinfo(v6, recv_pktinfo)
+    }
+
+    fn recv_pktinfo(&self) -> io::Result<bool> {
+        let v6 = self.local_addr()?.is_ipv6();
+        self.as_inner().socket().recv_pktinfo(v6)
+    }
+
+    fn set_recv_timestamp(&self, recv_timestamp: bool) -> io::Result<()> {
+        self.as_inner().socket().set_recv_timestamp(recv_timestamp)
+    }
+
+    fn recv_timestamp(&self) -> io::Result<bool> {
+        self.as_inner().socket().recv_timestamp()
+    }
+
+    fn set_gro(&self, gro: bool) -> io::Result<()> {
+        self.as_inner().socket().set_gro(gro)
+    }
+
+    fn gro(&self) -> io::Result<bool> {
+        self.as_inner().socket().gro()
+    }
+
+    fn recv_vectored_with_ancillary_from(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        ancillary: &mut SocketAncillary<'_>,
+    ) -> io::Result<(usize, bool, SocketAddr)> {
+        recv_vectored_with_ancillary_from_inet(self.as_inner().socket(), bufs, ancillary)
+    }
+
+    fn send_vectored_with_ancillary_to<A: ToSocketAddrs>(
+        &self,
+        bufs: &[IoSlice<'_>],
+        ancillary: &mut SocketAncillary<'_>,
+        addr: A,
+    ) -> io::Result<usize> {
+        let socket = self.as_inner().socket();
+        match addr.to_socket_addrs()?.next() {
+            Some(addr) => send_vectored_with_ancillary_to_inet(socket, &addr, bufs, ancillary),
+            None => Err(io::const_io_error!(
+                io::ErrorKind::InvalidInput,
+                "no addresses to send data to"
+            )),
+        }
+    }
+}
@@ -4,11 +4,21 @@
 use crate::io::{self, IoSlice, IoSliceMut};
 use crate::marker::PhantomData;
 use crate::mem::{size_of, zeroed};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::{self, Ipv4Addr, Ipv6Addr};
 use crate::os::unix::io::RawFd;
 use crate::path::Path;
 use crate::ptr::{eq, read_unaligned};
 use crate::slice::from_raw_parts;
 use crate::sys::net::Socket;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::sys::net::{UDP_GRO, UDP_SEGMENT};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::sys_common::net::sockaddr_to_addr;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::sys_common::{FromInner, IntoInner};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::time::{Duration, SystemTime};
 
 // FIXME(#43348): Make libc adapt #[doc(cfg(...))] so we don't need these fake definitions here?
 #[cfg(all(
@@ -86,6 +96,62 @@ pub(super) fn send_vectored_with_ancillary_to(
     }
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_vectored_with_ancillary_from_inet(
+    socket: &Socket,
+    bufs: &mut [IoSliceMut<'_>],
+    ancillary: &mut SocketAncillary<'_>,
+) -> io::Result<(usize, bool, net::SocketAddr)> {
+    unsafe {
+        let mut msg_name: libc::sockaddr_storage = zeroed();
+        let mut msg: libc::msghdr = zeroed();
+        msg.msg_name = &mut msg_name as *mut _ as *mut _;
+        msg.msg_namelen = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+        msg.msg_iov = bufs.as_mut_ptr().cast();
+        msg.msg_iovlen = bufs.len() as _;
+        msg.msg_controllen = ancillary.buffer.len() as _;
+        if msg.msg_controllen > 0 {
+            msg.msg_control = ancillary.buffer.as_mut_ptr().cast();
+        }
+
+        let count = socket.recv_msg(&mut msg)?;
+
+        ancillary.length = msg.msg_controllen as usize;
+        ancillary.truncated = msg.msg_flags & libc::MSG_CTRUNC == libc::MSG_CTRUNC;
+
+        let truncated = msg.msg_flags & libc::MSG_TRUNC == libc::MSG_TRUNC;
+        let addr = sockaddr_to_addr(&msg_name, msg.msg_namelen as usize)?;
+
+        Ok((count, truncated, addr))
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_vectored_with_ancillary_to_inet(
+    socket: &Socket,
+    addr: &net::SocketAddr,
+    bufs: &[IoSlice<'_>],
+    ancillary: &mut SocketAncillary<'_>,
+) -> io::Result<usize> {
+    unsafe {
+        let (msg_name, msg_namelen) = addr.into_inner();
+
+        let mut msg: libc::msghdr = zeroed();
+        msg.msg_name = msg_name.as_ptr() as *mut _;
+        msg.msg_namelen = msg_namelen;
+        msg.msg_iov = bufs.as_ptr() as *mut _;
+        msg.msg_iovlen = bufs.len() as _;
+        msg.msg_controllen = ancillary.length as _;
+        if msg.msg_controllen > 0 {
+            msg.msg_control = ancillary.buffer.as_mut_ptr().cast();
+        }
+
+        ancillary.truncated = false;
+
+        socket.send_msg(&mut msg)
+    }
+}
+
 fn add_to_ancillary_data<T>(
     buffer: &mut [u8],
     length: &mut usize,
@@ -443,6 +509,96 @@ fn next(&mut self) -> Option<SocketCred> {
     }
 }
 
+/// The local address and interface of a packet received on an IPv4 socket,
+/// or of a packet to send from one.
+///
+/// The level is equal to `IPPROTO_IP` and the type is equal to `IP_PKTINFO`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[unstable(feature = "udp_ancillary_data", issue = "none")]
+#[derive(Clone)]
+pub struct IpPktInfo(libc::in_pktinfo);
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl IpPktInfo {
+    /// Create the packet information to send a packet from `local_addr`
+    /// through the interface with the index `interface`.
+    ///
+    /// An index of 0 lets the system choose the interface from the
+    /// routing table, and an unspecified address lets it choose the
+    /// local address.
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    #[must_use]
+    pub fn new(interface: u32, local_addr: Ipv4Addr) -> IpPktInfo {
+        IpPktInfo(libc::in_pktinfo {
+            ipi_ifindex: interface as libc::c_int,
+            ipi_spec_dst: local_addr.into_inner(),
+            ipi_addr: Ipv4Addr::UNSPECIFIED.into_inner(),
+        })
+    }
+
+    /// Get the index of the interface the packet was received on.
+    #[must_use]
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    pub fn interface(&self) -> u32 {
+        self.0.ipi_ifindex as u32
+    }
+
+    /// Get the local address the packet was received on, which is the
+    /// address replies should be sent from.
+    #[must_use]
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    pub fn local_addr(&self) -> Ipv4Addr {
+        Ipv4Addr::from_inner(self.0.ipi_spec_dst)
+    }
+
+    /// Get the destination address in the header of the packet, which
+    /// differs from the local address for broadcast and multicast packets.
+    #[must_use]
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    pub fn dest_addr(&self) -> Ipv4Addr {
+        Ipv4Addr::from_inner(self.0.ipi_addr)
+    }
+}
+
+/// The destination address and interface of a packet received on an IPv6
+/// socket, or the source address and interface of a packet to send from one.
+///
+/// The level is equal to `IPPROTO_IPV6` and the type is equal to `IPV6_PKTINFO`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[unstable(feature = "udp_ancillary_data", issue = "none")]
+#[derive(Clone)]
+pub struct Ipv6PktInfo(libc::in6_pktinfo);
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl Ipv6PktInfo {
+    /// Create the packet information to send a packet from `addr` through
+    /// the interface with the index `interface`.
+    ///
+    /// An index of 0 lets the system choose the interface from the
+    /// routing table, and an unspecified address lets it choose the
+    /// source address.
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    #[must_use]
+    pub fn new(interface: u32, addr: Ipv6Addr) -> Ipv6PktInfo {
+        Ipv6PktInfo(libc::in6_pktinfo { ipi6_addr: addr.into_inner(), ipi6_ifindex: interface })
+    }
+
+    /// Get the index of the interface.
+    #[must_use]
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    pub fn interface(&self) -> u32 {
+        self.0.ipi6_ifindex
+    }
+
+    /// Get the address: the destination address of a received packet, or
+    /// the source address of a packet to send.
+    #[must_use]
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    pub fn addr(&self) -> Ipv6Addr {
+        Ipv6Addr::from_inner(self.0.ipi6_addr)
+    }
+}
+
 /// The error type which is returned from parsing the type a control message.
 #[non_exhaustive]
 #[derive(Debug)]
@@ -463,6 +619,27 @@ pub enum AncillaryData<'a> {
         target_os = "freebsd"
     ))]
     ScmCredentials(ScmCredentials<'a>),
+    /// The interface and the destination address of a received IPv4 packet,
+    /// with the level `IPPROTO_IP` and the type `IP_PKTINFO`.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    IpPktInfo(IpPktInfo),
+    /// The interface and the destination address of a received IPv6 packet,
+    /// with the level `IPPROTO_IPV6` and the type `IPV6_PKTINFO`.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    Ipv6PktInfo(Ipv6PktInfo),
+    /// The time a packet was received at, with the level `SOL_SOCKET` and
+    /// the type `SCM_TIMESTAMP`.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    Timestamp(SystemTime),
+    /// The size of the segments coalesced into a packet received with
+    /// generic receive offload, with the level `IPPROTO_UDP` and the type
+    /// `UDP_GRO`.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    UdpGroSegmentSize(u16),
 }
 
 impl<'a> AncillaryData<'a> {
@@ -497,6 +674,53 @@ unsafe fn as_credentials(data: &'a [u8]) -> Self {
         AncillaryData::ScmCredentials(scm_credentials)
     }
 
+    /// Create an `AncillaryData::Timestamp` variant.
+    ///
+    /// # Safety
+    ///
+    /// `data` must contain a valid control message and the control message must be type of
+    /// `SOL_SOCKET` and level of `SCM_TIMESTAMP`, and be at least as long as a `timeval`.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    unsafe fn as_timestamp(data: &'a [u8]) -> Self {
+        let tv: libc::timeval = read_unaligned(data.as_ptr().cast());
+        let since_epoch = Duration::new(tv.tv_sec as u64, tv.tv_usec as u32 * 1000);
+        AncillaryData::Timestamp(SystemTime::UNIX_EPOCH + since_epoch)
+    }
+
+    /// Create an `AncillaryData::IpPktInfo` variant.
+    ///
+    /// # Safety
+    ///
+    /// `data` must contain a valid control message and the control message must be type of
+    /// `IPPROTO_IP` and level of `IP_PKTINFO`, and be at least as long as an `in_pktinfo`.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    unsafe fn as_ip_pktinfo(data: &'a [u8]) -> Self {
+        AncillaryData::IpPktInfo(IpPktInfo(read_unaligned(data.as_ptr().cast())))
+    }
+
+    /// Create an `AncillaryData::Ipv6PktInfo` variant.
+    ///
+    /// # Safety
+    ///
+    /// `data` must contain a valid control message and the control message must be type of
+    /// `IPPROTO_IPV6` and level of `IPV6_PKTINFO`, and be at least as long as an `in6_pktinfo`.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    unsafe fn as_ipv6_pktinfo(data: &'a [u8]) -> Self {
+        AncillaryData::Ipv6PktInfo(Ipv6PktInfo(read_unaligned(data.as_ptr().cast())))
+    }
+
+    /// Create an `AncillaryData::UdpGroSegmentSize` variant.
+    ///
+    /// # Safety
+    ///
+    /// `data` must contain a valid control message and the control message must be type of
+    /// `IPPROTO_UDP` and level of `UDP_GRO`, and be at least as long as a `c_int`.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    unsafe fn as_udp_gro_segment_size(data: &'a [u8]) -> Self {
+        let size: libc::c_int = read_unaligned(data.as_ptr().cast());
+        AncillaryData::UdpGroSegmentSize(size as u16)
+    }
+
     fn try_from_cmsghdr(cmsg: &'a libc::cmsghdr) -> Result<Self, AncillaryError> {
         unsafe {
             let cmsg_len_zero = libc::CMSG_LEN(0) as usize;
@@ -513,10 +737,41 @@ fn try_from_cmsghdr(cmsg: &'a libc::cmsghdr) -> Result<Self, AncillaryError> {
                     libc::SCM_CREDS2 => Ok(AncillaryData::as_credentials(data)),
                     #[cfg(target_os = "netbsd")]
                     libc::SCM_CREDS => Ok(AncillaryData::as_credentials(data)),
+                    #[cfg(any(target_os = "android", target_os = "linux"))]
+                    libc::SCM_TIMESTAMP if data.len() >= size_of::<libc::timeval>() => {
+                        Ok(AncillaryData::as_timestamp(data))
+                    }
                     cmsg_type => {
                         Err(AncillaryError::Unknown { cmsg_level: libc::SOL_SOCKET, cmsg_type })
                     }
                 },
+                #[cfg(any(target_os = "android", target_os = "linux"))]
+                libc::IPPROTO_IP => match (*cmsg).cmsg_type {
+                    libc::IP_PKTINFO if data.len() >= size_of::<libc::in_pktinfo>() => {
+                        Ok(AncillaryData::as_ip_pktinfo(data))
+                    }
+                    cmsg_type => {
+                        Err(AncillaryError::Unknown { cmsg_level: libc::IPPROTO_IP, cmsg_type })
+                    }
+                },
+                #[cfg(any(target_os = "android", target_os = "linux"))]
+                libc::IPPROTO_IPV6 => match (*cmsg).cmsg_type {
+                    libc::IPV6_PKTINFO if data.len() >= size_of::<libc::in6_pktinfo>() => {
+                        Ok(AncillaryData::as_ipv6_pktinfo(data))
+                    }
+                    cmsg_type => {
+                        Err(AncillaryError::Unknown { cmsg_level: libc::IPPROTO_IPV6, cmsg_type })
+                    }
+                },
+                #[cfg(any(target_os = "android", target_os = "linux"))]
+                libc::IPPROTO_UDP => match (*cmsg).cmsg_type {
+                    UDP_GRO if data.len() >= size_of::<libc::c_int>() => {
+                        Ok(AncillaryData::as_udp_gro_segment_size(data))
+                    }
+                    cmsg_type => {
+                        Err(AncillaryError::Unknown { cmsg_level: libc::IPPROTO_UDP, cmsg_type })
+                    }
+                },
                 cmsg_level => {
                     Err(AncillaryError::Unknown { cmsg_level, cmsg_type: (*cmsg).cmsg_type })
                 }
@@ -748,6 +1003,64 @@ pub fn add_creds(&mut self, creds: &[SocketCred]) -> bool {
         )
     }
 
+    /// Add the packet information of an IPv4 socket to the ancillary data,
+    /// to choose the local address and interface a packet is sent from.
+    ///
+    /// The function returns `true` if there is enough space in the buffer.
+    /// Technically, that means this operation adds a control message with the level
+    /// `IPPROTO_IP` and type `IP_PKTINFO`.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    pub fn add_ip_pktinfo(&mut self, info: &IpPktInfo) -> bool {
+        self.truncated = false;
+        add_to_ancillary_data(
+            &mut self.buffer,
+            &mut self.length,
+            crate::slice::from_ref(&info.0),
+            libc::IPPROTO_IP,
+            libc::IP_PKTINFO,
+        )
+    }
+
+    /// Add the packet information of an IPv6 socket to the ancillary data,
+    /// to choose the source address and interface a packet is sent from.
+    ///
+    /// The function returns `true` if there is enough space in the buffer.
+    /// Technically, that means this operation adds a control message with the level
+    /// `IPPROTO_IPV6` and type `IPV6_PKTINFO`.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    pub fn add_ipv6_pktinfo(&mut self, info: &Ipv6PktInfo) -> bool {
+        self.truncated = false;
+        add_to_ancillary_data(
+            &mut self.buffer,
+            &mut self.length,
+            crate::slice::from_ref(&info.0),
+            libc::IPPROTO_IPV6,
+            libc::IPV6_PKTINFO,
+        )
+    }
+
+    /// Add the segment size of generic segmentation offload to the
+    /// ancillary data, so that the system splits the data sent into UDP
+    /// packets of `size` bytes, the last of which may be shorter.
+    ///
+    /// The function returns `true` if there is enough space in the buffer.
+    /// Technically, that means this operation adds a control message with the level
+    /// `IPPROTO_UDP` and type `UDP_SEGMENT`.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    #[unstable(feature = "udp_ancillary_data", issue = "none")]
+    pub fn add_udp_gso_segment_size(&mut self, size: u16) -> bool {
+        self.truncated = false;
+        add_to_ancillary_data(
+            &mut self.buffer,
+            &mut self.length,
+            &[size],
+            libc::IPPROTO_UDP,
+            UDP_SEGMENT,
+        )
+    }
+
     /// Clears the ancillary data, removing all values.
     ///
     /// # Example
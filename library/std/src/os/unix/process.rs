@@ -11,6 +11,7 @@
 use crate::sealed::Sealed;
 use crate::sys;
 use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+use crate::time::Duration;
 
 use cfg_if::cfg_if;
 
@@ -234,6 +235,132 @@ fn process_group(&mut self, pgroup: i32) -> &mut process::Command {
     }
 }
 
+/// Unix-specific extensions to [`process::Child`].
+///
+/// This trait is sealed: it cannot be implemented outside the standard library.
+/// This is so that future additional methods are not breaking changes.
+#[unstable(feature = "unix_child_signals", issue = "none")]
+pub trait ChildExt: Sealed {
+    /// Sends a signal to the child process.
+    ///
+    /// Like [`Child::kill`], this does nothing and returns `Ok` if the child
+    /// has already been waited on, since its process ID may have been reused.
+    ///
+    /// This corresponds to the `kill` function, or `pidfd_send_signal` on
+    /// Linux if the child has a pidfd.
+    ///
+    /// [`Child::kill`]: process::Child::kill
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_child_signals)]
+    /// use std::os::unix::process::ChildExt;
+    /// use std::process::Command;
+    ///
+    /// const SIGHUP: i32 = 1;
+    ///
+    /// let child = Command::new("sleep").arg("10").spawn()?;
+    /// // Ask the child to reload its configuration.
+    /// child.send_signal(SIGHUP)?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[doc(alias = "kill")]
+    fn send_signal(&self, signal: i32) -> io::Result<()>;
+
+    /// Sends a signal to every process in the process group of the child.
+    ///
+    /// This targets the process group whose ID is the process ID of the child,
+    /// which exists if the child was spawned with
+    /// [`process_group(0)`](CommandExt::process_group). Since descendants of
+    /// the child stay in its process group unless they create their own, this
+    /// also reaches them, including after the child itself has exited.
+    ///
+    /// Returns an error if there is no such process group.
+    ///
+    /// This corresponds to the `killpg` function.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_child_signals)]
+    /// use std::os::unix::process::{ChildExt, CommandExt};
+    /// use std::process::Command;
+    ///
+    /// const SIGKILL: i32 = 9;
+    ///
+    /// let mut child = Command::new("sh")
+    ///     .args(["-c", "sleep 10 & sleep 10"])
+    ///     .process_group(0)
+    ///     .spawn()?;
+    /// // Kill both the shell and the `sleep` commands it spawned.
+    /// child.send_signal_group(SIGKILL)?;
+    /// child.wait()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[doc(alias = "killpg")]
+    fn send_signal_group(&self, signal: i32) -> io::Result<()>;
+
+    /// Asks the child process to terminate with `SIGTERM`, and waits for it
+    /// to exit for at most `timeout`, after which it's killed with `SIGKILL`.
+    ///
+    /// Returns the exit status of the child, which is also returned if the
+    /// child had already exited. The child is reaped in every case where this
+    /// function returns `Ok`.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Fuchsia, which has no signals, the child is killed right away.
+    /// **Note that this may change in the future.**
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_child_signals)]
+    /// use std::os::unix::process::ChildExt;
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let mut child = Command::new("sleep").arg("10").spawn()?;
+    /// let status = child.terminate_with_timeout(Duration::from_secs(5))?;
+    /// println!("child exited with {status}");
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn terminate_with_timeout(&mut self, timeout: Duration) -> io::Result<process::ExitStatus>;
+}
+
+#[unstable(feature = "unix_child_signals", issue = "none")]
+impl ChildExt for process::Child {
+    fn send_signal(&self, signal: i32) -> io::Result<()> {
+        self.as_inner().send_signal(signal)
+    }
+
+    fn send_signal_group(&self, signal: i32) -> io::Result<()> {
+        self.as_inner().send_signal_group(signal)
+    }
+
+    fn terminate_with_timeout(&mut self, timeout: Duration) -> io::Result<process::ExitStatus> {
+        if let Some(status) = self.try_wait()? {
+            return Ok(status);
+        }
+        match self.send_signal(libc::SIGTERM) {
+            Ok(()) => {
+                if let Some(status) = self.wait_timeout(timeout)? {
+                    return Ok(status);
+                }
+            }
+            // Without signals, the child can only be killed right away.
+            Err(e) if e.kind() == io::ErrorKind::Unsupported => {}
+            Err(e) => return Err(e),
+        }
+        self.kill()?;
+        self.wait()
+    }
+}
+
 /// Unix-specific extensions to [`process::ExitStatus`] and
 /// [`ExitStatusError`](process::ExitStatusError).
 ///
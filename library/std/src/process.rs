@@ -119,6 +119,7 @@
 #[stable(feature = "command_access", since = "1.57.0")]
 pub use crate::sys_common::process::CommandEnvs;
 use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+use crate::time::Duration;
 
 /// Representation of a running or exited child process.
 ///
@@ -928,6 +929,45 @@ pub fn stderr<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Command {
         self
     }
 
+    /// Redirects the child process's standard error (stderr) to the same
+    /// destination as its standard output, like `2>&1` in a shell.
+    ///
+    /// Both streams then write to the same file or pipe, so that their output
+    /// is interleaved in the order the child produced it. If stdout is
+    /// [`piped`], stderr is written to the pipe of [`Child::stdout`], and
+    /// [`Child::stderr`] is `None`.
+    ///
+    /// This replaces any configuration previously set with [`stderr`], and is
+    /// itself replaced by a later call to [`stderr`].
+    ///
+    /// [`piped`]: Stdio::piped
+    /// [`stderr`]: Self::stderr
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(command_combined_output)]
+    /// use std::io::Read;
+    /// use std::process::{Command, Stdio};
+    ///
+    /// let mut child = Command::new("cargo")
+    ///     .arg("build")
+    ///     .stdout(Stdio::piped())
+    ///     .stderr_to_stdout()
+    ///     .spawn()?;
+    ///
+    /// let mut log = String::new();
+    /// child.stdout.take().unwrap().read_to_string(&mut log)?;
+    /// child.wait()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "command_combined_output", issue = "none")]
+    pub fn stderr_to_stdout(&mut self) -> &mut Command {
+        self.inner.stderr(imp::Stdio::SameAsStdout);
+        self
+    }
+
     /// Executes the command as a child process, returning a handle to it.
     ///
     /// By default, stdin, stdout and stderr are inherited from the parent.
@@ -978,6 +1018,39 @@ pub fn output(&mut self) -> io::Result<Output> {
         Ok(Output { status: ExitStatus(status), stdout, stderr })
     }
 
+    /// Executes the command as a child process, waiting for it to finish and
+    /// collecting its standard output and standard error together.
+    ///
+    /// This is like [`output`], with stderr redirected to stdout as with
+    /// [`stderr_to_stdout`], so the `stdout` field of the returned [`Output`]
+    /// holds both streams, interleaved in the order the child produced them.
+    /// The `stderr` field is always empty.
+    ///
+    /// Any stderr configuration of the command is ignored, but isn't
+    /// changed. Stdout is captured unless configured otherwise.
+    ///
+    /// [`output`]: Self::output
+    /// [`stderr_to_stdout`]: Self::stderr_to_stdout
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(command_combined_output)]
+    /// use std::process::Command;
+    ///
+    /// let output = Command::new("cargo").arg("build").output_combined()?;
+    /// if !output.status.success() {
+    ///     println!("build failed:\n{}", String::from_utf8_lossy(&output.stdout));
+    /// }
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "command_combined_output", issue = "none")]
+    pub fn output_combined(&mut self) -> io::Result<Output> {
+        let (status, stdout) = self.inner.output_combined()?;
+        Ok(Output { status: ExitStatus(status), stdout, stderr: Vec::new() })
+    }
+
     /// Executes a command as a child process, waiting for it to finish and
     /// collecting its status.
     ///
@@ -1500,6 +1573,63 @@ fn from(file: fs::File) -> Stdio {
     }
 }
 
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+impl From<io::PipeReader> for Stdio {
+    /// Converts the read end of a pipe, created by [`io::pipe`], into a
+    /// [`Stdio`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// #![feature(anonymous_pipe)]
+    /// use std::io::{self, Write};
+    /// use std::process::Command;
+    ///
+    /// let (reader, mut writer) = io::pipe()?;
+    /// let mut reverse = Command::new("rev")
+    ///     .stdin(reader) // Converted into a Stdio here
+    ///     .spawn()?;
+    /// writer.write_all(b"Hello, world!")?;
+    /// drop(writer);
+    ///
+    /// // "!dlrow ,olleH" echoed to console
+    /// reverse.wait()?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn from(pipe: io::PipeReader) -> Stdio {
+        Stdio::from_inner(pipe.into_inner().into())
+    }
+}
+
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+impl From<io::PipeWriter> for Stdio {
+    /// Converts the write end of a pipe, created by [`io::pipe`], into a
+    /// [`Stdio`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// #![feature(anonymous_pipe)]
+    /// use std::io::{self, Read};
+    /// use std::process::Command;
+    ///
+    /// let (mut reader, writer) = io::pipe()?;
+    /// let mut echo = Command::new("echo")
+    ///     .arg("Hello, world!")
+    ///     .stdout(writer) // Converted into a Stdio here
+    ///     .spawn()?;
+    ///
+    /// let mut output = String::new();
+    /// reader.read_to_string(&mut output)?;
+    /// assert_eq!(output, "Hello, world!\n");
+    /// echo.wait()?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    fn from(pipe: io::PipeWriter) -> Stdio {
+        Stdio::from_inner(pipe.into_inner().into())
+    }
+}
+
 #[stable(feature = "stdio_from_stdio", since = "1.74.0")]
 impl From<io::Stdout> for Stdio {
     /// Redirect command stdout/stderr to our stdout
@@ -2110,6 +2240,53 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         Ok(self.handle.try_wait()?.map(ExitStatus))
     }
 
+    /// Waits for the child to exit for at most `timeout`, returning the
+    /// status that it exited with if it did.
+    ///
+    /// If the child has exited before the timeout elapses, then
+    /// `Ok(Some(status))` is returned, and on Unix the process ID is reaped.
+    /// If the timeout elapses first, then `Ok(None)` is returned. If an error
+    /// occurs, then that error is returned.
+    ///
+    /// As with [`wait`], the stdin handle to the child process, if any, will
+    /// be closed before waiting.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Windows, this uses `WaitForSingleObject`. On Linux, this polls a
+    /// pidfd for the child, which is opened if the [`Command`] didn't already
+    /// create one. On other Unix platforms, or if no pidfd is available, this
+    /// calls [`try_wait`] repeatedly, with sleeps of up to 100 milliseconds in
+    /// between. **Note that this may change in the future.**
+    ///
+    /// [`wait`]: Child::wait
+    /// [`try_wait`]: Child::try_wait
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(child_wait_timeout)]
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+    ///
+    /// match child.wait_timeout(Duration::from_secs(1)) {
+    ///     Ok(Some(status)) => println!("exited with: {status}"),
+    ///     Ok(None) => {
+    ///         println!("still running after one second, killing it");
+    ///         child.kill().unwrap();
+    ///         child.wait().unwrap();
+    ///     }
+    ///     Err(e) => println!("error attempting to wait: {e}"),
+    /// }
+    /// ```
+    #[unstable(feature = "child_wait_timeout", issue = "none")]
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        drop(self.stdin.take());
+        Ok(self.handle.wait_timeout(timeout)?.map(ExitStatus))
+    }
+
     /// Simultaneously waits for the child to exit and collect all remaining
     /// output on the stdout/stderr handles, returning an `Output`
     /// instance.
@@ -1,7 +1,7 @@
 use crate::io::prelude::*;
 
 use super::{Command, Output, Stdio};
-use crate::io::{BorrowedBuf, ErrorKind};
+use crate::io::{self, BorrowedBuf, ErrorKind};
 use crate::mem::MaybeUninit;
 use crate::str;
 
@@ -207,6 +207,79 @@ fn test_process_output_error() {
     assert!(!stderr.is_empty());
 }
 
+#[test]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_process_output_combined() {
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.args(&["/C", "echo one& echo two 1>&2& echo three"]);
+        cmd
+    } else {
+        let mut cmd = shell_cmd();
+        cmd.arg("-c").arg("echo one; echo two >&2; echo three");
+        cmd
+    };
+    let Output { status, stdout, stderr } = cmd.output_combined().unwrap();
+    let stdout = String::from_utf8(stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().map(str::trim_end).collect();
+
+    assert!(status.success());
+    assert_eq!(lines, ["one", "two", "three"]);
+    assert!(stderr.is_empty());
+
+    // The stderr configuration of the command is left as is.
+    let Output { stdout, stderr, .. } = cmd.output().unwrap();
+    assert_eq!(stdout.iter().filter(|&&b| b == b'\n').count(), 2);
+    assert!(!stderr.is_empty());
+}
+
+#[test]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_pipe_to_several_children() {
+    let (mut reader, writer) = io::pipe().unwrap();
+    for word in ["one", "two"] {
+        let mut cmd = if cfg!(target_os = "windows") {
+            let mut cmd = Command::new("cmd");
+            cmd.args(&["/C", "echo", word]);
+            cmd
+        } else {
+            let mut cmd = shell_cmd();
+            cmd.arg("-c").arg(format!("echo {word}"));
+            cmd
+        };
+        let status = cmd.stdout(writer.try_clone().unwrap()).status().unwrap();
+        assert!(status.success());
+    }
+    drop(writer);
+
+    let mut output = String::new();
+    reader.read_to_string(&mut output).unwrap();
+    let lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+    assert_eq!(lines, ["one", "two"]);
+}
+
+#[test]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_stderr_to_stdout() {
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.args(&["/C", "echo one& echo two 1>&2"]);
+        cmd
+    } else {
+        let mut cmd = shell_cmd();
+        cmd.arg("-c").arg("echo one; echo two >&2");
+        cmd
+    };
+    let mut child = cmd.stdout(Stdio::piped()).stderr_to_stdout().spawn().unwrap();
+    assert!(child.stderr.is_none());
+
+    let mut stdout = String::new();
+    child.stdout.take().unwrap().read_to_string(&mut stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().map(str::trim_end).collect();
+    assert_eq!(lines, ["one", "two"]);
+    assert!(child.wait().unwrap().success());
+}
+
 #[test]
 #[cfg_attr(any(target_os = "vxworks"), ignore)]
 fn test_finish_once() {
@@ -230,6 +303,32 @@ fn test_finish_twice() {
     assert!(prog.wait().unwrap().code() == Some(1));
 }
 
+#[test]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_wait_timeout() {
+    use crate::time::Duration;
+
+    let mut prog = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(&["/C", "exit 1"]).spawn().unwrap()
+    } else {
+        shell_cmd().arg("-c").arg("false").spawn().unwrap()
+    };
+    let status = prog.wait_timeout(Duration::from_secs(60)).unwrap();
+    assert_eq!(status.and_then(|status| status.code()), Some(1));
+    // Waiting again returns the same status, without blocking.
+    let status = prog.wait_timeout(Duration::ZERO).unwrap();
+    assert_eq!(status.and_then(|status| status.code()), Some(1));
+
+    let mut prog = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(&["/C", "ping -n 1000 127.0.0.1 > nul"]).spawn().unwrap()
+    } else {
+        shell_cmd().arg("-c").arg("sleep 1000").spawn().unwrap()
+    };
+    assert!(prog.wait_timeout(Duration::from_millis(10)).unwrap().is_none());
+    prog.kill().unwrap();
+    assert!(prog.wait_timeout(Duration::from_secs(60)).unwrap().is_some());
+}
+
 #[test]
 #[cfg_attr(any(target_os = "vxworks"), ignore)]
 fn test_wait_with_output_once() {
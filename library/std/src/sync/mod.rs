@@ -187,7 +187,7 @@
 mod barrier;
 mod condvar;
 mod lazy_lock;
-mod mpmc;
+pub(crate) mod mpmc;
 mod mutex;
 pub(crate) mod once;
 mod once_lock;
@@ -2,6 +2,7 @@
 use crate::fmt;
 
 pub use crate::sync::mpsc::{RecvError, RecvTimeoutError, SendError, TryRecvError, TrySendError};
+pub use crate::sync::mpsc::{SelectTimeoutError, TrySelectError};
 
 /// An error returned from the [`send_timeout`] method.
 ///
@@ -483,6 +483,19 @@ pub enum TrySendError<T> {
     Disconnected(#[stable(feature = "rust1", since = "1.0.0")] T),
 }
 
+/// An error returned from the [`Select::try_select`] method, when none of
+/// the operations were ready.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[unstable(feature = "mpsc_select", issue = "none")]
+pub struct TrySelectError;
+
+/// An error returned from the [`Select::select_timeout`] and
+/// [`Select::select_deadline`] methods, when none of the operations became
+/// ready in time.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[unstable(feature = "mpsc_select", issue = "none")]
+pub struct SelectTimeoutError;
+
 /// Creates a new asynchronous channel, returning the sender/receiver halves.
 /// All data sent on the [`Sender`] will become available on the [`Receiver`] in
 /// the same order as it was sent, and no [`send`] will block the calling thread
@@ -1093,6 +1106,214 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Select
+////////////////////////////////////////////////////////////////////////////////
+
+/// Waits on several channel operations at once, and completes one of them.
+///
+/// Send and receive operations are added with [`send`], [`sync_send`] and
+/// [`recv`], each of which returns the index of the operation. One of the
+/// operations is then selected with [`select`], [`try_select`],
+/// [`select_timeout`] or [`select_deadline`], as soon as it is ready: a send
+/// operation is ready when the message can be sent without blocking, and a
+/// receive operation when a message can be received without blocking. An
+/// operation on a disconnected channel is ready too, and completing it
+/// returns an error.
+///
+/// The selected operation is returned as a [`SelectedOperation`], which must
+/// be completed by passing the same [`Sender`], [`SyncSender`] or
+/// [`Receiver`] to one of its methods. If several operations are ready, a
+/// random one is selected.
+///
+/// [`send`]: Select::send
+/// [`sync_send`]: Select::sync_send
+/// [`recv`]: Select::recv
+/// [`select`]: Select::select
+/// [`try_select`]: Select::try_select
+/// [`select_timeout`]: Select::select_timeout
+/// [`select_deadline`]: Select::select_deadline
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpsc_select)]
+/// use std::sync::mpsc::{channel, Select};
+/// use std::thread;
+///
+/// let (tx1, rx1) = channel::<i32>();
+/// let (tx2, rx2) = channel::<&str>();
+///
+/// thread::spawn(move || tx2.send("hello").unwrap());
+///
+/// let mut sel = Select::new();
+/// let op1 = sel.recv(&rx1);
+/// let op2 = sel.recv(&rx2);
+///
+/// let oper = sel.select();
+/// match oper.index() {
+///     i if i == op1 => panic!("no message was sent on the first channel"),
+///     i if i == op2 => assert_eq!(oper.recv(&rx2), Ok("hello")),
+///     _ => unreachable!(),
+/// }
+/// # drop(tx1);
+/// ```
+#[unstable(feature = "mpsc_select", issue = "none")]
+#[derive(Clone, Default)]
+pub struct Select<'a> {
+    inner: mpmc::Select<'a>,
+}
+
+impl<'a> Select<'a> {
+    /// Creates an empty list of channel operations.
+    #[must_use]
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn new() -> Select<'a> {
+        Select { inner: mpmc::Select::new() }
+    }
+
+    /// Adds a send operation on the channel of a [`Sender`], and returns its
+    /// index.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn send<T>(&mut self, s: &'a Sender<T>) -> usize {
+        self.inner.send(&s.inner)
+    }
+
+    /// Adds a send operation on the channel of a [`SyncSender`], and returns
+    /// its index.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn sync_send<T>(&mut self, s: &'a SyncSender<T>) -> usize {
+        self.inner.send(&s.inner)
+    }
+
+    /// Adds a receive operation, and returns its index.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn recv<T>(&mut self, r: &'a Receiver<T>) -> usize {
+        self.inner.recv(&r.inner)
+    }
+
+    /// Removes a previously added operation.
+    ///
+    /// Indices of the other operations are not affected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is invalid, or if the operation was already removed.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn remove(&mut self, index: usize) {
+        self.inner.remove(index)
+    }
+
+    /// Attempts to select one of the operations without blocking.
+    ///
+    /// Returns an error if none of the operations is ready.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn try_select(&mut self) -> Result<SelectedOperation<'a>, TrySelectError> {
+        self.inner.try_select().map(|inner| SelectedOperation { inner })
+    }
+
+    /// Blocks until one of the operations is ready, and selects it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no operations have been added.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn select(&mut self) -> SelectedOperation<'a> {
+        SelectedOperation { inner: self.inner.select() }
+    }
+
+    /// Blocks until one of the operations is ready, and selects it, but only
+    /// for a limited time.
+    ///
+    /// Returns an error if none of the operations became ready in time.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn select_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<SelectedOperation<'a>, SelectTimeoutError> {
+        self.inner.select_timeout(timeout).map(|inner| SelectedOperation { inner })
+    }
+
+    /// Blocks until one of the operations is ready, and selects it, but only
+    /// until the given deadline.
+    ///
+    /// Returns an error if none of the operations became ready before the
+    /// deadline.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn select_deadline(
+        &mut self,
+        deadline: Instant,
+    ) -> Result<SelectedOperation<'a>, SelectTimeoutError> {
+        self.inner.select_deadline(deadline).map(|inner| SelectedOperation { inner })
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Debug for Select<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Select").finish_non_exhaustive()
+    }
+}
+
+/// An operation selected by [`Select`], which still has to be completed.
+///
+/// The operation is completed by calling [`send`], [`sync_send`] or [`recv`]
+/// with the [`Sender`], [`SyncSender`] or [`Receiver`] it was added with.
+///
+/// [`send`]: SelectedOperation::send
+/// [`sync_send`]: SelectedOperation::sync_send
+/// [`recv`]: SelectedOperation::recv
+///
+/// # Panics
+///
+/// Dropping a `SelectedOperation` without completing it panics, as the other
+/// side of the channel may be waiting for it.
+#[must_use = "the selected operation must be completed"]
+#[unstable(feature = "mpsc_select", issue = "none")]
+#[derive(Debug)]
+pub struct SelectedOperation<'a> {
+    inner: mpmc::SelectedOperation<'a>,
+}
+
+impl SelectedOperation<'_> {
+    /// Returns the index of the selected operation, as returned when it was
+    /// added to the [`Select`].
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn index(&self) -> usize {
+        self.inner.index()
+    }
+
+    /// Completes a send operation added with [`Select::send`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not the sender of the selected operation.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn send<T>(self, s: &Sender<T>, msg: T) -> Result<(), SendError<T>> {
+        self.inner.send(&s.inner, msg)
+    }
+
+    /// Completes a send operation added with [`Select::sync_send`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not the sender of the selected operation.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn sync_send<T>(self, s: &SyncSender<T>, msg: T) -> Result<(), SendError<T>> {
+        self.inner.send(&s.inner, msg)
+    }
+
+    /// Completes a receive operation added with [`Select::recv`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r` is not the receiver of the selected operation.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn recv<T>(self, r: &Receiver<T>) -> Result<T, RecvError> {
+        self.inner.recv(&r.inner)
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> fmt::Debug for SendError<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -1244,3 +1465,23 @@ fn from(err: RecvError) -> RecvTimeoutError {
         }
     }
 }
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Display for TrySelectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "all operations in select would block".fmt(f)
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl error::Error for TrySelectError {}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Display for SelectTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "timed out waiting on select".fmt(f)
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl error::Error for SelectTimeoutError {}
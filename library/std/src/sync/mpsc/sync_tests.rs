@@ -668,3 +668,43 @@ fn drop_unreceived() {
     assert!(weak.upgrade().is_none());
     drop(tx);
 }
+
+#[test]
+fn select_rendezvous() {
+    let (tx1, rx1) = sync_channel::<i32>(0);
+    let (tx2, rx2) = sync_channel::<i32>(0);
+
+    let t = thread::spawn(move || {
+        for _ in 0..2 {
+            let mut sel = Select::new();
+            let op1 = sel.sync_send(&tx1);
+            let op2 = sel.recv(&rx2);
+            let oper = sel.select();
+            match oper.index() {
+                i if i == op1 => oper.sync_send(&tx1, 1).unwrap(),
+                i if i == op2 => assert_eq!(oper.recv(&rx2), Ok(2)),
+                _ => unreachable!(),
+            }
+        }
+    });
+
+    assert_eq!(rx1.recv(), Ok(1));
+    tx2.send(2).unwrap();
+    t.join().unwrap();
+}
+
+#[test]
+fn select_bounded_full() {
+    let (tx, rx) = sync_channel::<i32>(1);
+
+    let mut sel = Select::new();
+    let op = sel.sync_send(&tx);
+    let oper = sel.try_select().unwrap();
+    assert_eq!(oper.index(), op);
+    oper.sync_send(&tx, 1).unwrap();
+
+    // The channel is full now.
+    assert!(sel.try_select().is_err());
+    assert_eq!(rx.recv(), Ok(1));
+    assert!(sel.try_select().map(|oper| oper.sync_send(&tx, 2)).is_ok());
+}
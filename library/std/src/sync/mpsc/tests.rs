@@ -720,3 +720,83 @@ fn issue_39364() {
     let _tx = t.join().unwrap(); // delay dropping until end of test
     let _ = rx.recv_timeout(Duration::from_millis(500));
 }
+
+#[test]
+fn select_recv() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+
+    let mut sel = Select::new();
+    let op1 = sel.recv(&rx1);
+    let op2 = sel.recv(&rx2);
+    assert_eq!(sel.try_select().map(|oper| oper.index()).unwrap_err(), TrySelectError);
+
+    tx2.send(2).unwrap();
+    let oper = sel.select();
+    assert_eq!(oper.index(), op2);
+    assert_eq!(oper.recv(&rx2), Ok(2));
+
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        tx1.send(1).unwrap();
+    });
+    let oper = sel.select();
+    assert_eq!(oper.index(), op1);
+    assert_eq!(oper.recv(&rx1), Ok(1));
+    t.join().unwrap();
+
+    // A disconnected channel is always ready.
+    let oper = sel.select();
+    assert_eq!(oper.index(), op1);
+    assert_eq!(oper.recv(&rx1), Err(RecvError));
+    drop(tx2);
+}
+
+#[test]
+fn select_send_and_recv() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+
+    let mut sel = Select::new();
+    let op1 = sel.recv(&rx1);
+    let op2 = sel.send(&tx2);
+
+    // Sending on an unbounded channel never blocks.
+    let oper = sel.select();
+    assert_eq!(oper.index(), op2);
+    oper.send(&tx2, 2).unwrap();
+    assert_eq!(rx2.recv(), Ok(2));
+
+    sel.remove(op2);
+    tx1.send(1).unwrap();
+    let oper = sel.select();
+    assert_eq!(oper.index(), op1);
+    assert_eq!(oper.recv(&rx1), Ok(1));
+}
+
+#[test]
+fn select_timeout() {
+    let (_tx1, rx1) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+
+    let mut sel = Select::new();
+    sel.recv(&rx1);
+    sel.recv(&rx2);
+    assert_eq!(
+        sel.select_timeout(Duration::from_millis(100)).map(|oper| oper.index()).unwrap_err(),
+        SelectTimeoutError
+    );
+}
+
+#[test]
+#[should_panic(expected = "passed a receiver that wasn't selected")]
+fn select_wrong_receiver() {
+    let (tx1, rx1) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+
+    let mut sel = Select::new();
+    sel.recv(&rx1);
+    sel.recv(&rx2);
+    tx1.send(1).unwrap();
+    let _ = sel.select().recv(&rx2);
+}
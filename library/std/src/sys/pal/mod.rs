@@ -61,6 +61,22 @@
     }
 }
 
+// Only Linux has settings for spawning a thread besides its stack size, from
+// `os::linux::thread::BuilderExt`.
+#[cfg(not(target_os = "linux"))]
+#[path = "unsupported/thread_spawn.rs"]
+pub mod thread_spawn;
+
+// `fs::Watcher` falls back to polling on platforms without a native watcher.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+#[path = "unsupported/fs_watcher.rs"]
+pub mod fs_watcher;
+
+// `io::Poller` has no way of waiting for readiness on the other platforms.
+#[cfg(not(any(unix, windows)))]
+#[path = "unsupported/poller.rs"]
+pub mod poller;
+
 cfg_if::cfg_if! {
     // Fuchsia components default to full backtrace.
     if #[cfg(target_os = "fuchsia")] {
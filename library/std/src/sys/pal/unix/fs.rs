@@ -111,6 +111,13 @@
 
 pub struct File(FileDesc);
 
+/// The operations of [`File::flock`].
+enum LockOperation {
+    Exclusive,
+    Shared,
+    Unlock,
+}
+
 // FIXME: This should be available on Linux with all `target_env`.
 // But currently only glibc exposes `statx` fn and structs.
 // We don't want to import unverified raw C structs here directly.
@@ -1293,6 +1300,74 @@ pub fn duplicate(&self) -> io::Result<File> {
         self.0.duplicate().map(File)
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        self.flock(LockOperation::Exclusive, true).map(drop)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.flock(LockOperation::Shared, true).map(drop)
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.flock(LockOperation::Exclusive, false)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.flock(LockOperation::Shared, false)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        self.flock(LockOperation::Unlock, true).map(drop)
+    }
+
+    /// Returns whether the operation was done, which is always the case when
+    /// it is allowed to block.
+    fn flock(&self, operation: LockOperation, block: bool) -> io::Result<bool> {
+        cfg_if::cfg_if! {
+            if #[cfg(any(
+                target_os = "android",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "fuchsia",
+                target_os = "illumos",
+                target_os = "linux",
+                target_os = "netbsd",
+                target_os = "openbsd",
+                target_os = "macos",
+                target_os = "ios",
+                target_os = "tvos",
+                target_os = "watchos",
+            ))] {
+                let mut operation = match operation {
+                    LockOperation::Exclusive => libc::LOCK_EX,
+                    LockOperation::Shared => libc::LOCK_SH,
+                    LockOperation::Unlock => libc::LOCK_UN,
+                };
+                if !block {
+                    operation |= libc::LOCK_NB;
+                }
+                // `flock` locks belong to the open file description, so unlike `fcntl`
+                // record locks they aren't released when an unrelated descriptor for
+                // the same file is closed, and they're shared with duplicated handles.
+                match cvt_r(|| unsafe { libc::flock(self.as_raw_fd(), operation) }) {
+                    Ok(_) => Ok(true),
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(false),
+                    Err(e) => Err(e),
+                }
+            } else {
+                // `fcntl` record locks aren't used instead: they belong to the
+                // process rather than to the open file description, so two
+                // handles of the same process never exclude each other, and
+                // closing any descriptor for the file releases all of them.
+                let _ = (operation, block);
+                Err(io::const_io_error!(
+                    io::ErrorKind::Unsupported,
+                    "file locking is not supported on this platform"
+                ))
+            }
+        }
+    }
+
     pub fn set_permissions(&self, perm: FilePermissions) -> io::Result<()> {
         cvt_r(|| unsafe { libc::fchmod(self.as_raw_fd(), perm.mode) })?;
         Ok(())
@@ -1677,6 +1752,90 @@ pub fn link(original: &Path, link: &Path) -> io::Result<()> {
     })
 }
 
+/// Creates a new file for reading and writing, which only its owner can
+/// access if `private` is set.
+pub fn create_new(path: &Path, private: bool) -> io::Result<File> {
+    let mut opts = OpenOptions::new();
+    opts.read(true);
+    opts.write(true);
+    opts.create_new(true);
+    opts.mode(if private { 0o600 } else { 0o666 });
+    File::open(path, &opts)
+}
+
+/// Creates a new directory, which only its owner can access.
+pub fn create_dir_private(path: &Path) -> io::Result<()> {
+    let mut builder = DirBuilder::new();
+    builder.set_mode(0o700);
+    builder.mkdir(path)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub use crate::sys_common::fs::{create_unnamed, link_unnamed};
+
+/// Creates a file without a name in `dir` with `O_TMPFILE`, in the same way
+/// as [`create_new`], or returns `None` if the kernel or the filesystem
+/// doesn't support it.
+#[cfg(target_os = "linux")]
+pub fn create_unnamed(dir: &Path, private: bool) -> io::Result<Option<File>> {
+    let mut opts = OpenOptions::new();
+    opts.read(true);
+    opts.write(true);
+    opts.custom_flags(libc::O_TMPFILE);
+    opts.mode(if private { 0o600 } else { 0o666 });
+    match File::open(dir, &opts) {
+        Ok(file) => Ok(Some(file)),
+        // Kernels older than 3.11 take `O_TMPFILE` as `O_DIRECTORY`, and
+        // fail with `EISDIR` or `EINVAL`.
+        Err(e)
+            if matches!(e.raw_os_error(), Some(libc::EOPNOTSUPP | libc::EISDIR | libc::EINVAL)) =>
+        {
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Gives a name to a file created by [`create_unnamed`].
+#[cfg(target_os = "linux")]
+pub fn link_unnamed(file: &File, link: &Path) -> io::Result<()> {
+    run_path_with_cstr(link, |link| {
+        // Linking the file descriptor itself with `AT_EMPTY_PATH` requires
+        // the `CAP_DAC_READ_SEARCH` capability.
+        let linked = cvt(unsafe {
+            libc::linkat(
+                file.as_raw_fd(),
+                c"".as_ptr(),
+                libc::AT_FDCWD,
+                link.as_ptr(),
+                libc::AT_EMPTY_PATH,
+            )
+        });
+        match linked {
+            Ok(_) => return Ok(()),
+            Err(e) if !matches!(e.raw_os_error(), Some(libc::ENOENT | libc::EPERM)) => {
+                return Err(e);
+            }
+            Err(_) => {}
+        }
+
+        // Linking its `/proc` entry doesn't, but requires `/proc` to be mounted.
+        let original = format!("/proc/self/fd/{}", file.as_raw_fd());
+        run_path_with_cstr(Path::new(&original), |original| {
+            cvt(unsafe {
+                libc::linkat(
+                    libc::AT_FDCWD,
+                    original.as_ptr(),
+                    libc::AT_FDCWD,
+                    link.as_ptr(),
+                    libc::AT_SYMLINK_FOLLOW,
+                )
+            })
+            .map(|_| ())
+        })
+    })
+}
+
 pub fn stat(p: &Path) -> io::Result<FileAttr> {
     run_path_with_cstr(p, |p| {
         cfg_has_statx! {
@@ -1948,7 +2107,7 @@ pub fn chroot(dir: &Path) -> io::Result<()> {
     run_path_with_cstr(dir, |dir| cvt(unsafe { libc::chroot(dir.as_ptr()) }).map(|_| ()))
 }
 
-pub use remove_dir_impl::remove_dir_all;
+pub use remove_dir_impl::{readdir_at, remove_dir_all};
 
 // Fallback for REDOX, ESP-ID, Horizon, Vita and Miri
 #[cfg(any(
@@ -1960,7 +2119,7 @@ pub fn chroot(dir: &Path) -> io::Result<()> {
     miri
 ))]
 mod remove_dir_impl {
-    pub use crate::sys_common::fs::remove_dir_all;
+    pub use crate::sys_common::fs::{readdir_at, remove_dir_all};
 }
 
 // Modern implementation using openat(), unlinkat() and fdopendir()
@@ -1974,7 +2133,7 @@ mod remove_dir_impl {
 )))]
 mod remove_dir_impl {
     use super::{lstat, Dir, DirEntry, InnerReadDir, ReadDir};
-    use crate::ffi::CStr;
+    use crate::ffi::{CStr, OsStr};
     use crate::io;
     use crate::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
     use crate::os::unix::prelude::{OwnedFd, RawFd};
@@ -2044,7 +2203,7 @@ pub fn openat_nofollow_dironly(parent_fd: Option<RawFd>, p: &CStr) -> io::Result
         Ok(unsafe { OwnedFd::from_raw_fd(fd) })
     }
 
-    fn fdreaddir(dir_fd: OwnedFd) -> io::Result<(ReadDir, RawFd)> {
+    fn fdreaddir(dir_fd: OwnedFd, root: PathBuf) -> io::Result<(ReadDir, RawFd)> {
         let ptr = unsafe { fdopendir(dir_fd.as_raw_fd()) };
         if ptr.is_null() {
             return Err(io::Error::last_os_error());
@@ -2052,13 +2211,32 @@ fn fdreaddir(dir_fd: OwnedFd) -> io::Result<(ReadDir, RawFd)> {
         let dirp = Dir(ptr);
         // file descriptor is automatically closed by libc::closedir() now, so give up ownership
         let new_parent_fd = dir_fd.into_raw_fd();
-        // a valid root is not needed because we do not call any functions involving the full path
-        // of the `DirEntry`s.
-        let dummy_root = PathBuf::new();
-        let inner = InnerReadDir { dirp, root: dummy_root };
+        let inner = InnerReadDir { dirp, root };
         Ok((ReadDir::new(inner), new_parent_fd))
     }
 
+    /// Opens the subdirectory `name` of `parent`, whose full path is `path`,
+    /// relative to `parent` so that it can't be swapped for a symbolic link
+    /// in the meantime, unless `follow_links` is set.
+    fn readdir_at_modern(
+        parent: &ReadDir,
+        name: &OsStr,
+        path: &Path,
+        follow_links: bool,
+    ) -> io::Result<ReadDir> {
+        let parent_fd = cvt(unsafe { libc::dirfd(parent.inner.dirp.0) })?;
+        let fd = run_path_with_cstr(Path::new(name), |name| {
+            if follow_links {
+                let flags = libc::O_CLOEXEC | libc::O_RDONLY | libc::O_DIRECTORY;
+                let fd = cvt_r(|| unsafe { openat(parent_fd, name.as_ptr(), flags) })?;
+                Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+            } else {
+                openat_nofollow_dironly(Some(parent_fd), name)
+            }
+        })?;
+        fdreaddir(fd, path.to_owned()).map(|(dir, _)| dir)
+    }
+
     #[cfg(any(
         target_os = "solaris",
         target_os = "illumos",
@@ -2103,8 +2281,9 @@ fn remove_dir_all_recursive(parent_fd: Option<RawFd>, path: &CStr) -> io::Result
             result => result?,
         };
 
-        // open the directory passing ownership of the fd
-        let (dir, fd) = fdreaddir(fd)?;
+        // open the directory passing ownership of the fd. a valid root is not needed because we do
+        // not call any functions involving the full path of the `DirEntry`s.
+        let (dir, fd) = fdreaddir(fd, PathBuf::new())?;
         for child in dir {
             let child = child?;
             let child_name = child.name_cstr();
@@ -2149,6 +2328,16 @@ pub fn remove_dir_all(p: &Path) -> io::Result<()> {
         remove_dir_all_modern(p)
     }
 
+    #[cfg(not(all(target_os = "macos", not(target_arch = "aarch64"))))]
+    pub fn readdir_at(
+        parent: &ReadDir,
+        name: &OsStr,
+        path: &Path,
+        follow_links: bool,
+    ) -> io::Result<ReadDir> {
+        readdir_at_modern(parent, name, path, follow_links)
+    }
+
     #[cfg(all(target_os = "macos", not(target_arch = "aarch64")))]
     pub fn remove_dir_all(p: &Path) -> io::Result<()> {
         if macos_weak::has_openat() {
@@ -2159,4 +2348,18 @@ pub fn remove_dir_all(p: &Path) -> io::Result<()> {
             crate::sys_common::fs::remove_dir_all(p)
         }
     }
+
+    #[cfg(all(target_os = "macos", not(target_arch = "aarch64")))]
+    pub fn readdir_at(
+        parent: &ReadDir,
+        name: &OsStr,
+        path: &Path,
+        follow_links: bool,
+    ) -> io::Result<ReadDir> {
+        if macos_weak::has_openat() {
+            readdir_at_modern(parent, name, path, follow_links)
+        } else {
+            crate::sys_common::fs::readdir_at(parent, name, path, follow_links)
+        }
+    }
 }
@@ -0,0 +1,299 @@
+//! Native implementation of `fs::Watcher`, with inotify.
+//!
+//! inotify only watches single files and directories, so recursive watches
+//! are made of one inotify watch per directory, which are kept up to date as
+//! directories are created, renamed and removed.
+
+use crate::collections::{BTreeMap, VecDeque};
+use crate::ffi::{CStr, OsStr};
+use crate::fs::{self, WatchEvent};
+use crate::io;
+use crate::mem;
+use crate::os::unix::ffi::OsStrExt;
+use crate::os::unix::io::{AsRawFd, FromRawFd};
+use crate::path::{Path, PathBuf};
+use crate::ptr;
+use crate::sys::common::small_c_string::run_path_with_cstr;
+use crate::sys::cvt;
+use crate::sys::fd::FileDesc;
+use crate::time::Instant;
+
+use libc::c_int;
+
+const WATCH_MASK: u32 = libc::IN_CREATE
+    | libc::IN_DELETE
+    | libc::IN_MODIFY
+    | libc::IN_ATTRIB
+    | libc::IN_MOVED_FROM
+    | libc::IN_MOVED_TO
+    | libc::IN_DELETE_SELF
+    | libc::IN_MOVE_SELF;
+
+pub struct Watcher {
+    fd: FileDesc,
+    watches: BTreeMap<c_int, Watch>,
+    events: VecDeque<WatchEvent>,
+    /// An `IN_MOVED_FROM` event, waiting to be paired with the `IN_MOVED_TO`
+    /// event that immediately follows it when a file is renamed within the
+    /// watched directories.
+    moved_from: Option<MovedFrom>,
+}
+
+struct Watch {
+    path: PathBuf,
+    /// The watched paths that this watch is part of. Watching the same
+    /// file twice returns the same watch descriptor, so there may be several,
+    /// e.g. when a recursively watched directory contains another watched
+    /// path.
+    owners: Vec<Owner>,
+}
+
+struct Owner {
+    /// The path given to `Watcher::watch`.
+    root: PathBuf,
+    recursive: bool,
+}
+
+struct MovedFrom {
+    cookie: u32,
+    path: PathBuf,
+    is_dir: bool,
+}
+
+impl Watcher {
+    pub fn new() -> io::Result<Watcher> {
+        let fd = cvt(unsafe { libc::inotify_init1(libc::IN_CLOEXEC) })?;
+        // SAFETY: `inotify_init1` returned a new file descriptor, that we own.
+        let fd = unsafe { FileDesc::from_raw_fd(fd) };
+        Ok(Watcher { fd, watches: BTreeMap::new(), events: VecDeque::new(), moved_from: None })
+    }
+
+    pub fn watch(&mut self, path: &Path, recursive: bool) -> io::Result<()> {
+        self.add_watch(path, path, recursive)?;
+        if recursive {
+            self.watch_subdirectories(path, path, false);
+        }
+        Ok(())
+    }
+
+    pub fn unwatch(&mut self, path: &Path) -> io::Result<()> {
+        if !self.remove_watches(|_, owner| owner.root == path) {
+            return Err(io::const_io_error!(io::ErrorKind::NotFound, "path is not watched"));
+        }
+        Ok(())
+    }
+
+    pub fn next_event(&mut self, deadline: Option<Instant>) -> io::Result<Option<WatchEvent>> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Ok(Some(event));
+            }
+            // The `IN_MOVED_TO` event matching a pending `IN_MOVED_FROM` one
+            // is queued at the same time, so if there are no events left,
+            // the file was moved out of the watched directories.
+            if self.moved_from.is_some() && !self.wait_readable(Some(Instant::now()))? {
+                let moved_from = self.moved_from.take().unwrap();
+                self.moved_out(moved_from);
+                continue;
+            }
+            if !self.wait_readable(deadline)? {
+                return Ok(None);
+            }
+            self.read_events()?;
+        }
+    }
+
+    fn add_watch(&mut self, path: &Path, root: &Path, recursive: bool) -> io::Result<()> {
+        let wd = run_path_with_cstr(path, |path| {
+            cvt(unsafe { libc::inotify_add_watch(self.fd.as_raw_fd(), path.as_ptr(), WATCH_MASK) })
+        })?;
+        let watch = self
+            .watches
+            .entry(wd)
+            .or_insert_with(|| Watch { path: path.to_owned(), owners: Vec::new() });
+        match watch.owners.iter_mut().find(|owner| owner.root == root) {
+            Some(owner) => owner.recursive = recursive,
+            None => watch.owners.push(Owner { root: root.to_owned(), recursive }),
+        }
+        Ok(())
+    }
+
+    /// Watches the descendants of `dir`, skipping those that can't be
+    /// watched. If `created` is set, a `Create` event is reported for each
+    /// descendant, as they may have been created before `dir` was watched.
+    fn watch_subdirectories(&mut self, dir: &Path, root: &Path, created: bool) {
+        let Ok(read_dir) = fs::read_dir(dir) else { return };
+        for child in read_dir.flatten() {
+            let path = child.path();
+            if created {
+                self.events.push_back(WatchEvent::Create(path.clone()));
+            }
+            if child.file_type().is_ok_and(|file_type| file_type.is_dir())
+                && self.add_watch(&path, root, true).is_ok()
+            {
+                self.watch_subdirectories(&path, root, created);
+            }
+        }
+    }
+
+    /// Removes the owners of the watches for which `f`, called with the
+    /// path of the watch, returns `true`, and returns whether there were
+    /// any. The watches left without owners are removed.
+    fn remove_watches(&mut self, mut f: impl FnMut(&Path, &Owner) -> bool) -> bool {
+        let fd = self.fd.as_raw_fd();
+        let mut removed = false;
+        self.watches.retain(|&wd, watch| {
+            let len = watch.owners.len();
+            watch.owners.retain(|owner| !f(&watch.path, owner));
+            removed |= watch.owners.len() != len;
+            if !watch.owners.is_empty() {
+                return true;
+            }
+            // This fails if the file was already removed, which is fine.
+            unsafe { libc::inotify_rm_watch(fd, wd) };
+            false
+        });
+        removed
+    }
+
+    fn read_events(&mut self) -> io::Result<()> {
+        const HEADER_LEN: usize = mem::size_of::<libc::inotify_event>();
+        // Large enough for an event with the longest possible file name.
+        let mut buf = [0; 4096];
+        let len = match self.fd.read(&mut buf) {
+            Ok(len) => len,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        let mut overflowed = false;
+        let mut pos = 0;
+        while pos + HEADER_LEN <= len {
+            // SAFETY: The kernel writes a header at the start of each event.
+            let event: libc::inotify_event =
+                unsafe { ptr::read_unaligned(buf.as_ptr().add(pos).cast()) };
+            let name_start = pos + HEADER_LEN;
+            pos = name_start + event.len as usize;
+            // The name is padded with NUL bytes, and empty for events about
+            // the watched file itself.
+            let name = &buf[name_start..pos];
+            let name = CStr::from_bytes_until_nul(name).map_or(name, CStr::to_bytes);
+            if event.mask & libc::IN_Q_OVERFLOW != 0 {
+                overflowed = true;
+            } else {
+                self.handle_event(&event, OsStr::from_bytes(name));
+            }
+        }
+
+        if overflowed {
+            return Err(io::const_io_error!(
+                io::ErrorKind::Other,
+                "too many filesystem events at once, some of them were lost",
+            ));
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: &libc::inotify_event, name: &OsStr) {
+        if let Some(moved_from) = self.moved_from.take() {
+            if event.mask & libc::IN_MOVED_TO != 0 && event.cookie == moved_from.cookie {
+                if let Some(watch) = self.watches.get(&event.wd) {
+                    let to = watch.path.join(name);
+                    if moved_from.is_dir {
+                        self.rename_watches(&moved_from.path, &to);
+                    }
+                    self.events.push_back(WatchEvent::Rename { from: moved_from.path, to });
+                    return;
+                }
+            }
+            self.moved_out(moved_from);
+        }
+
+        let Some(watch) = self.watches.get(&event.wd) else { return };
+        if event.mask & libc::IN_IGNORED != 0 {
+            self.watches.remove(&event.wd);
+            return;
+        }
+        let path = if name.is_empty() { watch.path.clone() } else { watch.path.join(name) };
+        let is_dir = event.mask & libc::IN_ISDIR != 0;
+
+        if event.mask & (libc::IN_CREATE | libc::IN_MOVED_TO) != 0 {
+            let recursive = watch.owners.iter().filter(|owner| is_dir && owner.recursive);
+            let roots: Vec<_> = recursive.map(|owner| owner.root.clone()).collect();
+            self.events.push_back(WatchEvent::Create(path.clone()));
+            // The descendants are only reported once, even if the directory
+            // is part of several recursive watches.
+            let mut created = true;
+            for root in roots {
+                if self.add_watch(&path, &root, true).is_ok() {
+                    self.watch_subdirectories(&path, &root, created);
+                    created = false;
+                }
+            }
+        } else if event.mask & libc::IN_MOVED_FROM != 0 {
+            self.moved_from = Some(MovedFrom { cookie: event.cookie, path, is_dir });
+        } else if event.mask & libc::IN_DELETE != 0 {
+            self.events.push_back(WatchEvent::Remove(path));
+        } else if event.mask & (libc::IN_MODIFY | libc::IN_ATTRIB) != 0 {
+            self.events.push_back(WatchEvent::Modify(path));
+        } else if event.mask & (libc::IN_DELETE_SELF | libc::IN_MOVE_SELF) != 0 {
+            // The removal of the other watched directories is reported by
+            // their parent.
+            let Some(owner) = watch.owners.iter().find(|owner| owner.root == watch.path) else {
+                return;
+            };
+            let (root, recursive) = (owner.root.clone(), owner.recursive);
+            self.events.push_back(WatchEvent::Remove(path));
+            self.remove_watches(|_, owner| owner.root == root);
+            // Files are often replaced by renaming a new file over them, in
+            // which case the new file is watched in place of the old one.
+            if self.watch(&root, recursive).is_ok() {
+                self.events.push_back(WatchEvent::Create(root));
+            }
+        }
+    }
+
+    /// Reports a file moved out of the watched directories as removed.
+    fn moved_out(&mut self, moved_from: MovedFrom) {
+        if moved_from.is_dir {
+            self.remove_watches(|path, owner| {
+                path.starts_with(&moved_from.path) && path != owner.root
+            });
+        }
+        self.events.push_back(WatchEvent::Remove(moved_from.path));
+    }
+
+    /// Updates the paths of the watches after a directory was renamed.
+    fn rename_watches(&mut self, from: &Path, to: &Path) {
+        for watch in self.watches.values_mut() {
+            if let Ok(suffix) = watch.path.strip_prefix(from) {
+                watch.path = to.join(suffix);
+            }
+        }
+    }
+
+    /// Waits until there are events to read, or until the deadline.
+    fn wait_readable(&self, deadline: Option<Instant>) -> io::Result<bool> {
+        loop {
+            let timeout = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    // Round up, so that we don't wake up just before the deadline.
+                    let ms = remaining.as_nanos().div_ceil(1_000_000);
+                    ms.try_into().unwrap_or(c_int::MAX)
+                }
+                None => -1,
+            };
+            let mut pollfd =
+                libc::pollfd { fd: self.fd.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+            match cvt(unsafe { libc::poll(&mut pollfd, 1, timeout) }) {
+                Ok(0) if timeout == 0 => return Ok(false),
+                // Either the timeout was clamped, or we woke up a bit early.
+                Ok(0) => {}
+                Ok(_) => return Ok(true),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
@@ -16,6 +16,8 @@
 pub mod env;
 pub mod fd;
 pub mod fs;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub mod fs_watcher;
 pub mod futex;
 pub mod io;
 #[cfg(any(target_os = "linux", target_os = "android"))]
@@ -32,6 +34,7 @@
 pub mod os_str;
 pub mod path;
 pub mod pipe;
+pub mod poller;
 pub mod process;
 pub mod rand;
 pub mod stack_overflow;
@@ -40,6 +43,8 @@
 pub mod thread_local_dtor;
 pub mod thread_local_key;
 pub mod thread_parking;
+#[cfg(target_os = "linux")]
+pub mod thread_spawn;
 pub mod time;
 
 #[cfg(target_os = "espidf")]
@@ -13,6 +13,13 @@
 
 use libc::{c_int, c_void, size_t, sockaddr, socklen_t, MSG_PEEK};
 
+// The options of UDP segmentation offload, which aren't defined by libc for
+// every target. Their values are the same on all architectures.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub const UDP_SEGMENT: c_int = 103;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub const UDP_GRO: c_int = 104;
+
 cfg_if::cfg_if! {
     if #[cfg(target_vendor = "apple")] {
         use libc::SO_LINGER_SEC as SO_LINGER;
@@ -337,6 +344,22 @@ pub fn peek_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
         self.recv_from_with_flags(buf, MSG_PEEK)
     }
 
+    #[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+    pub fn recv_vectored_from(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, SocketAddr)> {
+        let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        msg.msg_name = &mut storage as *mut _ as *mut _;
+        msg.msg_namelen = mem::size_of_val(&storage) as libc::socklen_t;
+        msg.msg_iov = bufs.as_mut_ptr().cast();
+        msg.msg_iovlen = bufs.len() as _;
+
+        let n = cvt(unsafe { libc::recvmsg(self.as_raw_fd(), &mut msg, 0) })?;
+        Ok((n as usize, sockaddr_to_addr(&storage, msg.msg_namelen as usize)?))
+    }
+
     pub fn write(&self, buf: &[u8]) -> io::Result<usize> {
         self.0.write(buf)
     }
@@ -356,6 +379,19 @@ pub fn send_msg(&self, msg: &mut libc::msghdr) -> io::Result<usize> {
         Ok(n as usize)
     }
 
+    #[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+    pub fn send_vectored_to(&self, bufs: &[IoSlice<'_>], dst: &SocketAddr) -> io::Result<usize> {
+        let (dst, dstlen) = dst.into_inner();
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        msg.msg_name = dst.as_ptr() as *mut _;
+        msg.msg_namelen = dstlen;
+        msg.msg_iov = bufs.as_ptr() as *mut _;
+        msg.msg_iovlen = bufs.len() as _;
+
+        let n = cvt(unsafe { libc::sendmsg(self.as_raw_fd(), &msg, 0) })?;
+        Ok(n as usize)
+    }
+
     pub fn set_timeout(&self, dur: Option<Duration>, kind: libc::c_int) -> io::Result<()> {
         let timeout = match dur {
             Some(dur) => {
@@ -441,6 +477,73 @@ pub fn quickack(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    #[cfg(any(target_os = "android", target_os = "linux",))]
+    pub fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        let ms = match timeout {
+            Some(dur) => {
+                if dur.is_zero() {
+                    return Err(io::const_io_error!(
+                        io::ErrorKind::InvalidInput,
+                        "cannot set a 0 duration timeout",
+                    ));
+                }
+                // Round up, so that a timeout under a millisecond isn't
+                // disabled.
+                let ms = dur.as_nanos().div_ceil(1_000_000);
+                ms.try_into().unwrap_or(c_int::MAX)
+            }
+            None => 0,
+        };
+        setsockopt(self, libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT, ms)
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux",))]
+    pub fn user_timeout(&self) -> io::Result<Option<Duration>> {
+        let raw: c_int = getsockopt(self, libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT)?;
+        Ok((raw != 0).then(|| Duration::from_millis(raw as u64)))
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux",))]
+    pub fn set_recv_pktinfo(&self, v6: bool, recv_pktinfo: bool) -> io::Result<()> {
+        if v6 {
+            setsockopt(self, libc::IPPROTO_IPV6, libc::IPV6_RECVPKTINFO, recv_pktinfo as c_int)
+        } else {
+            setsockopt(self, libc::IPPROTO_IP, libc::IP_PKTINFO, recv_pktinfo as c_int)
+        }
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux",))]
+    pub fn recv_pktinfo(&self, v6: bool) -> io::Result<bool> {
+        let raw: c_int = if v6 {
+            getsockopt(self, libc::IPPROTO_IPV6, libc::IPV6_RECVPKTINFO)?
+        } else {
+            getsockopt(self, libc::IPPROTO_IP, libc::IP_PKTINFO)?
+        };
+        Ok(raw != 0)
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux",))]
+    pub fn set_recv_timestamp(&self, recv_timestamp: bool) -> io::Result<()> {
+        setsockopt(self, libc::SOL_SOCKET, libc::SO_TIMESTAMP, recv_timestamp as c_int)
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux",))]
+    pub fn recv_timestamp(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(self, libc::SOL_SOCKET, libc::SO_TIMESTAMP)?;
+        Ok(raw != 0)
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux",))]
+    pub fn set_gro(&self, gro: bool) -> io::Result<()> {
+        setsockopt(self, libc::IPPROTO_UDP, UDP_GRO, gro as c_int)
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux",))]
+    pub fn gro(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(self, libc::IPPROTO_UDP, UDP_GRO)?;
+        Ok(raw != 0)
+    }
+
     #[cfg(any(target_os = "android", target_os = "linux",))]
     pub fn set_passcred(&self, passcred: bool) -> io::Result<()> {
         setsockopt(self, libc::SOL_SOCKET, libc::SO_PASSCRED, passcred as libc::c_int)
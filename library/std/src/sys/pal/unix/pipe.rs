@@ -1,9 +1,9 @@
-use crate::io::{self, BorrowedCursor, IoSlice, IoSliceMut};
+use crate::io::{self, BorrowedCursor, IoSlice, IoSliceMut, PipeReader, PipeWriter};
 use crate::mem;
-use crate::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, RawFd};
+use crate::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
 use crate::sys::fd::FileDesc;
 use crate::sys::{cvt, cvt_r};
-use crate::sys_common::{FromInner, IntoInner};
+use crate::sys_common::{AsInner, FromInner, IntoInner};
 
 ////////////////////////////////////////////////////////////////////////////////
 // Anonymous pipes
@@ -45,7 +45,18 @@ pub fn anon_pipe() -> io::Result<(AnonPipe, AnonPipe)> {
     }
 }
 
+/// The pipes of `io::pipe` are the same as those of child processes.
+pub type UnnamedPipe = AnonPipe;
+
+pub fn unnamed_pipe() -> io::Result<(AnonPipe, AnonPipe)> {
+    anon_pipe()
+}
+
 impl AnonPipe {
+    pub fn try_clone(&self) -> io::Result<AnonPipe> {
+        self.0.duplicate().map(AnonPipe)
+    }
+
     pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.0.read(buf)
     }
@@ -165,3 +176,53 @@ fn from_inner(fd: FileDesc) -> Self {
         Self(fd)
     }
 }
+
+macro_rules! impl_pipe_conversions {
+    ($($t:ty)*) => {$(
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl AsFd for $t {
+            fn as_fd(&self) -> BorrowedFd<'_> {
+                self.as_inner().as_fd()
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl AsRawFd for $t {
+            fn as_raw_fd(&self) -> RawFd {
+                self.as_inner().as_raw_fd()
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl IntoRawFd for $t {
+            fn into_raw_fd(self) -> RawFd {
+                self.into_inner().into_raw_fd()
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl FromRawFd for $t {
+            unsafe fn from_raw_fd(raw_fd: RawFd) -> Self {
+                Self::from_inner(AnonPipe::from_raw_fd(raw_fd))
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl From<$t> for OwnedFd {
+            fn from(pipe: $t) -> OwnedFd {
+                pipe.into_inner().into_inner().into_inner()
+            }
+        }
+
+        /// Creates a pipe end from the provided `OwnedFd`, which should be
+        /// the corresponding end of a pipe.
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl From<OwnedFd> for $t {
+            fn from(fd: OwnedFd) -> Self {
+                Self::from_inner(AnonPipe(FileDesc::from_inner(fd)))
+            }
+        }
+    )*};
+}
+
+impl_pipe_conversions!(PipeReader PipeWriter);
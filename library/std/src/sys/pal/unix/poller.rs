@@ -0,0 +1,248 @@
+//! Native implementation of `io::Poller`, with epoll on Linux and Android,
+//! and with `poll` on the other platforms.
+
+use crate::os::fd::AsFd;
+use crate::time::Duration;
+
+use libc::c_int;
+
+/// The bound of `io::PollSource`: the sources are the file descriptors.
+pub trait Source: AsFd {}
+
+impl<T: AsFd + ?Sized> Source for T {}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use self::epoll::Poller;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub use self::poll::Poller;
+
+/// Converts a timeout to milliseconds, with -1 meaning no timeout.
+fn timeout_ms(timeout: Option<Duration>) -> c_int {
+    match timeout {
+        // Round up, so that we don't wake up just before the timeout.
+        Some(timeout) => timeout.as_nanos().div_ceil(1_000_000).try_into().unwrap_or(c_int::MAX),
+        None => -1,
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod epoll {
+    use super::{timeout_ms, Source};
+    use crate::io::{self, Event, Interest};
+    use crate::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+    use crate::sys::cvt;
+    use crate::sys::fd::FileDesc;
+    use crate::time::Duration;
+
+    use libc::c_int;
+
+    /// The largest number of events returned by a single `epoll_wait`.
+    const MAX_EVENTS: usize = 1024;
+
+    pub struct Poller {
+        epoll: FileDesc,
+        /// The number of registered sources, to size the buffer of events.
+        /// epoll drops the sources that are closed without being deleted, so
+        /// this may count more sources than there are.
+        sources: usize,
+        events: Vec<libc::epoll_event>,
+    }
+
+    impl Poller {
+        pub fn new() -> io::Result<Poller> {
+            let fd = cvt(unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) })?;
+            // SAFETY: `epoll_create1` returned a new file descriptor, that we own.
+            let epoll = unsafe { FileDesc::from_raw_fd(fd) };
+            Ok(Poller { epoll, sources: 0, events: Vec::new() })
+        }
+
+        pub fn add<S: Source + ?Sized>(
+            &mut self,
+            source: &S,
+            key: usize,
+            interest: Interest,
+        ) -> io::Result<()> {
+            self.ctl(libc::EPOLL_CTL_ADD, source.as_fd().as_raw_fd(), key, interest)?;
+            self.sources += 1;
+            Ok(())
+        }
+
+        pub fn modify<S: Source + ?Sized>(
+            &mut self,
+            source: &S,
+            key: usize,
+            interest: Interest,
+        ) -> io::Result<()> {
+            self.ctl(libc::EPOLL_CTL_MOD, source.as_fd().as_raw_fd(), key, interest)
+        }
+
+        pub fn delete<S: Source + ?Sized>(&mut self, source: &S) -> io::Result<()> {
+            // The event is ignored, but must not be null on Linux before 2.6.9.
+            let mut event = libc::epoll_event { events: 0, u64: 0 };
+            let fd = source.as_fd().as_raw_fd();
+            cvt(unsafe {
+                libc::epoll_ctl(self.epoll.as_raw_fd(), libc::EPOLL_CTL_DEL, fd, &mut event)
+            })?;
+            self.sources -= 1;
+            Ok(())
+        }
+
+        fn ctl(&self, op: c_int, fd: RawFd, key: usize, interest: Interest) -> io::Result<()> {
+            let mut events = 0;
+            if interest.is_readable() {
+                events |= libc::EPOLLIN;
+            }
+            if interest.is_writable() {
+                events |= libc::EPOLLOUT;
+            }
+            let mut event = libc::epoll_event { events: events as u32, u64: key as u64 };
+            cvt(unsafe { libc::epoll_ctl(self.epoll.as_raw_fd(), op, fd, &mut event) })?;
+            Ok(())
+        }
+
+        pub fn wait(
+            &mut self,
+            events: &mut Vec<Event>,
+            timeout: Option<Duration>,
+        ) -> io::Result<()> {
+            // Nothing could ever be reported.
+            if self.sources == 0 && timeout.is_none() {
+                return Ok(());
+            }
+
+            let capacity = self.sources.clamp(1, MAX_EVENTS);
+            self.events.clear();
+            self.events.reserve(capacity);
+            let n = match cvt(unsafe {
+                libc::epoll_wait(
+                    self.epoll.as_raw_fd(),
+                    self.events.as_mut_ptr(),
+                    capacity as c_int,
+                    timeout_ms(timeout),
+                )
+            }) {
+                Ok(n) => n as usize,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => return Ok(()),
+                Err(e) => return Err(e),
+            };
+            // SAFETY: `epoll_wait` initialized the first `n` events.
+            unsafe { self.events.set_len(n) };
+
+            let closed = (libc::EPOLLHUP | libc::EPOLLERR) as u32;
+            events.extend(self.events.iter().map(|event| {
+                let (flags, key) = (event.events, event.u64);
+                Event::new(
+                    key as usize,
+                    flags & (libc::EPOLLIN as u32 | closed) != 0,
+                    flags & (libc::EPOLLOUT as u32 | closed) != 0,
+                )
+            }));
+            Ok(())
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+mod poll {
+    use super::{timeout_ms, Source};
+    use crate::io::{self, Event, Interest};
+    use crate::os::unix::io::{AsRawFd, RawFd};
+    use crate::sys::cvt;
+    use crate::time::Duration;
+
+    pub struct Poller {
+        fds: Vec<libc::pollfd>,
+        /// The keys of the sources, in the same order as `fds`.
+        keys: Vec<usize>,
+    }
+
+    impl Poller {
+        pub fn new() -> io::Result<Poller> {
+            Ok(Poller { fds: Vec::new(), keys: Vec::new() })
+        }
+
+        pub fn add<S: Source + ?Sized>(
+            &mut self,
+            source: &S,
+            key: usize,
+            interest: Interest,
+        ) -> io::Result<()> {
+            let fd = source.as_fd().as_raw_fd();
+            if self.position(fd).is_ok() {
+                return Err(io::const_io_error!(
+                    io::ErrorKind::AlreadyExists,
+                    "source is already registered",
+                ));
+            }
+            self.fds.push(libc::pollfd { fd, events: poll_events(interest), revents: 0 });
+            self.keys.push(key);
+            Ok(())
+        }
+
+        pub fn modify<S: Source + ?Sized>(
+            &mut self,
+            source: &S,
+            key: usize,
+            interest: Interest,
+        ) -> io::Result<()> {
+            let i = self.position(source.as_fd().as_raw_fd())?;
+            self.fds[i].events = poll_events(interest);
+            self.keys[i] = key;
+            Ok(())
+        }
+
+        pub fn delete<S: Source + ?Sized>(&mut self, source: &S) -> io::Result<()> {
+            let i = self.position(source.as_fd().as_raw_fd())?;
+            self.fds.swap_remove(i);
+            self.keys.swap_remove(i);
+            Ok(())
+        }
+
+        fn position(&self, fd: RawFd) -> io::Result<usize> {
+            self.fds.iter().position(|pollfd| pollfd.fd == fd).ok_or_else(|| {
+                io::const_io_error!(io::ErrorKind::NotFound, "source is not registered")
+            })
+        }
+
+        pub fn wait(
+            &mut self,
+            events: &mut Vec<Event>,
+            timeout: Option<Duration>,
+        ) -> io::Result<()> {
+            // Nothing could ever be reported.
+            if self.fds.is_empty() && timeout.is_none() {
+                return Ok(());
+            }
+
+            let fds = self.fds.as_mut_ptr();
+            let len = self.fds.len() as libc::nfds_t;
+            match cvt(unsafe { libc::poll(fds, len, timeout_ms(timeout)) }) {
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => return Ok(()),
+                Err(e) => return Err(e),
+            }
+
+            let closed = libc::POLLHUP | libc::POLLERR | libc::POLLNVAL;
+            let ready = self.fds.iter().zip(&self.keys).filter(|(pollfd, _)| pollfd.revents != 0);
+            events.extend(ready.map(|(pollfd, &key)| {
+                Event::new(
+                    key,
+                    pollfd.revents & (libc::POLLIN | closed) != 0,
+                    pollfd.revents & (libc::POLLOUT | closed) != 0,
+                )
+            }));
+            Ok(())
+        }
+    }
+
+    fn poll_events(interest: Interest) -> libc::c_short {
+        let mut events = 0;
+        if interest.is_readable() {
+            events |= libc::POLLIN;
+        }
+        if interest.is_writable() {
+            events |= libc::POLLOUT;
+        }
+        events
+    }
+}
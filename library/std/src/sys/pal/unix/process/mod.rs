@@ -1,4 +1,6 @@
 pub use self::process_common::{Command, CommandArgs, ExitCode, Stdio, StdioPipes};
+#[cfg(target_os = "linux")]
+pub use self::process_inner::{pidfd_try_wait, pidfd_wait};
 pub use self::process_inner::{ExitStatus, ExitStatusError, Process};
 pub use crate::ffi::OsString as EnvKey;
 
@@ -12,11 +12,16 @@
 use crate::sys::fd::FileDesc;
 use crate::sys::fs::File;
 use crate::sys::pipe::{self, AnonPipe};
+use crate::sys::process::ExitStatus;
 use crate::sys_common::process::{CommandEnv, CommandEnvs};
 use crate::sys_common::{FromInner, IntoInner};
 
 #[cfg(not(target_os = "fuchsia"))]
 use crate::sys::fs::OpenOptions;
+#[cfg(not(target_os = "fuchsia"))]
+use crate::thread;
+#[cfg(not(target_os = "fuchsia"))]
+use crate::time::{Duration, Instant};
 
 use libc::{c_char, c_int, gid_t, pid_t, uid_t, EXIT_FAILURE, EXIT_SUCCESS};
 
@@ -152,6 +157,8 @@ pub enum Stdio {
     MakePipe,
     Fd(FileDesc),
     StaticFd(BorrowedFd<'static>),
+    // Only used for stderr, which then refers to the same file as stdout.
+    SameAsStdout,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -355,6 +362,15 @@ pub fn stderr(&mut self, stderr: Stdio) {
         self.stderr = Some(stderr);
     }
 
+    pub fn output_combined(&mut self) -> io::Result<(ExitStatus, Vec<u8>)> {
+        let stderr = self.stderr.replace(Stdio::SameAsStdout);
+        let spawned = self.spawn(Stdio::MakePipe, false);
+        self.stderr = stderr;
+        let (proc, pipes) = spawned?;
+        let (status, stdout, _) = crate::sys_common::process::wait_with_output(proc, pipes)?;
+        Ok((status, stdout))
+    }
+
     pub fn env_mut(&mut self) -> &mut CommandEnv {
         &mut self.env
     }
@@ -386,7 +402,19 @@ pub fn setup_io(
         let stderr = self.stderr.as_ref().unwrap_or(&default);
         let (their_stdin, our_stdin) = stdin.to_child_stdio(true)?;
         let (their_stdout, our_stdout) = stdout.to_child_stdio(false)?;
-        let (their_stderr, our_stderr) = stderr.to_child_stdio(false)?;
+        let (their_stderr, our_stderr) = match stderr {
+            // The child's stdout is set up before its stderr, so if it's
+            // inherited, its descriptor can be duplicated as is.
+            Stdio::SameAsStdout => match their_stdout {
+                #[cfg(target_os = "fuchsia")]
+                ChildStdio::Null => (ChildStdio::Null, None),
+                _ => {
+                    let fd = their_stdout.fd().unwrap_or(libc::STDOUT_FILENO);
+                    (ChildStdio::Explicit(fd), None)
+                }
+            },
+            _ => stderr.to_child_stdio(false)?,
+        };
         let ours = StdioPipes { stdin: our_stdin, stdout: our_stdout, stderr: our_stderr };
         let theirs = ChildPipes { stdin: their_stdin, stdout: their_stdout, stderr: their_stderr };
         Ok((ours, theirs))
@@ -487,6 +515,9 @@ pub fn to_child_stdio(&self, readable: bool) -> io::Result<(ChildStdio, Option<A
 
             #[cfg(target_os = "fuchsia")]
             Stdio::Null => Ok((ChildStdio::Null, None)),
+
+            // Handled by `Command::setup_io`, since it depends on the child's stdout.
+            Stdio::SameAsStdout => unreachable!(),
         }
     }
 }
@@ -538,6 +569,34 @@ pub fn fd(&self) -> Option<c_int> {
     }
 }
 
+/// Waits for at most `timeout` until `try_wait` returns a status, by calling
+/// it with increasing delays in between. This is for platforms that have no
+/// way to block on a child with a timeout.
+#[cfg(not(target_os = "fuchsia"))]
+pub fn wait_timeout_by_polling<T>(
+    timeout: Duration,
+    mut try_wait: impl FnMut() -> io::Result<Option<T>>,
+) -> io::Result<Option<T>> {
+    // A timeout too large to be represented means waiting forever.
+    let deadline = Instant::now().checked_add(timeout);
+    let mut delay = Duration::from_millis(1);
+    loop {
+        if let Some(status) = try_wait()? {
+            return Ok(Some(status));
+        }
+        let mut sleep = delay;
+        if let Some(deadline) = deadline {
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            sleep = sleep.min(deadline - now);
+        }
+        thread::sleep(sleep);
+        delay = (delay * 2).min(Duration::from_millis(100));
+    }
+}
+
 impl fmt::Debug for Command {
     // show all attributes but `self.closures` which does not implement `Debug`
     // and `self.argv` which is not useful for debugging
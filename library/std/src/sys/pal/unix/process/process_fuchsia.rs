@@ -3,6 +3,7 @@
 use crate::mem;
 use crate::num::{NonZeroI32, NonZeroI64};
 use crate::ptr;
+use crate::time::Duration;
 
 use crate::sys::process::process_common::*;
 use crate::sys::process::zircon::{zx_handle_t, Handle};
@@ -156,8 +157,20 @@ pub fn id(&self) -> u32 {
     }
 
     pub fn kill(&mut self) -> io::Result<()> {
+        self.send_signal(libc::SIGKILL)
+    }
+
+    pub fn send_signal(&self, signal: i32) -> io::Result<()> {
         use crate::sys::process::zircon::*;
 
+        // Fuchsia has no signals, but killing a task is the equivalent of `SIGKILL`.
+        if signal != libc::SIGKILL {
+            return Err(io::const_io_error!(
+                io::ErrorKind::Unsupported,
+                "only SIGKILL can be sent to processes on Fuchsia",
+            ));
+        }
+
         unsafe {
             zx_cvt(zx_task_kill(self.handle.raw()))?;
         }
@@ -165,6 +178,13 @@ pub fn kill(&mut self) -> io::Result<()> {
         Ok(())
     }
 
+    pub fn send_signal_group(&self, _signal: i32) -> io::Result<()> {
+        Err(io::const_io_error!(
+            io::ErrorKind::Unsupported,
+            "process groups are not supported on Fuchsia",
+        ))
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         use crate::sys::process::zircon::*;
 
@@ -233,6 +253,27 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
         Ok(Some(ExitStatus(proc_info.return_code)))
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        use crate::sys::process::zircon::*;
+
+        // `zx_deadline_after` saturates, so a timeout too large to be
+        // represented means waiting forever.
+        let timeout = timeout.as_nanos().try_into().unwrap_or(zx_duration_t::MAX);
+        unsafe {
+            let status = zx_object_wait_one(
+                self.handle.raw(),
+                ZX_TASK_TERMINATED,
+                zx_deadline_after(timeout),
+                ptr::null_mut(),
+            );
+            if status == ERR_TIMED_OUT {
+                return Ok(None);
+            }
+            zx_cvt(status)?;
+        }
+        self.try_wait()
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
@@ -5,6 +5,9 @@
 use crate::sys;
 use crate::sys::cvt;
 use crate::sys::process::process_common::*;
+use crate::time::Duration;
+#[cfg(target_os = "linux")]
+use crate::time::Instant;
 use core::ffi::NonZero_c_int;
 
 #[cfg(target_os = "linux")]
@@ -815,6 +818,10 @@ pub fn id(&self) -> u32 {
     }
 
     pub fn kill(&mut self) -> io::Result<()> {
+        self.send_signal(libc::SIGKILL)
+    }
+
+    pub fn send_signal(&self, signal: i32) -> io::Result<()> {
         // If we've already waited on this process then the pid can be recycled
         // and used for another process, and we probably shouldn't be killing
         // random processes, so return Ok because the process has exited already.
@@ -828,14 +835,20 @@ pub fn kill(&mut self) -> io::Result<()> {
                 libc::syscall(
                     libc::SYS_pidfd_send_signal,
                     pid_fd.as_raw_fd(),
-                    libc::SIGKILL,
+                    signal,
                     crate::ptr::null::<()>(),
                     0,
                 )
             })
             .map(drop);
         }
-        cvt(unsafe { libc::kill(self.pid, libc::SIGKILL) }).map(drop)
+        cvt(unsafe { libc::kill(self.pid, signal) }).map(drop)
+    }
+
+    pub fn send_signal_group(&self, signal: i32) -> io::Result<()> {
+        // Unlike the pid, the process group ID can't be reused while any
+        // process of the group is alive, even after the child was reaped.
+        cvt(unsafe { libc::killpg(self.pid, signal) }).map(drop)
     }
 
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
@@ -845,12 +858,7 @@ pub fn wait(&mut self) -> io::Result<ExitStatus> {
         }
         #[cfg(target_os = "linux")]
         if let Some(pid_fd) = self.pidfd.as_ref() {
-            let mut siginfo: libc::siginfo_t = unsafe { crate::mem::zeroed() };
-
-            cvt_r(|| unsafe {
-                libc::waitid(libc::P_PIDFD, pid_fd.as_raw_fd() as u32, &mut siginfo, libc::WEXITED)
-            })?;
-            let status = ExitStatus::from_waitid_siginfo(siginfo);
+            let status = pidfd_wait(pid_fd)?;
             self.status = Some(status);
             return Ok(status);
         }
@@ -866,22 +874,9 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
         #[cfg(target_os = "linux")]
         if let Some(pid_fd) = self.pidfd.as_ref() {
-            let mut siginfo: libc::siginfo_t = unsafe { crate::mem::zeroed() };
-
-            cvt(unsafe {
-                libc::waitid(
-                    libc::P_PIDFD,
-                    pid_fd.as_raw_fd() as u32,
-                    &mut siginfo,
-                    libc::WEXITED | libc::WNOHANG,
-                )
-            })?;
-            if unsafe { siginfo.si_pid() } == 0 {
-                return Ok(None);
-            }
-            let status = ExitStatus::from_waitid_siginfo(siginfo);
-            self.status = Some(status);
-            return Ok(Some(status));
+            let status = pidfd_try_wait(pid_fd)?;
+            self.status = status;
+            return Ok(status);
         }
         let mut status = 0 as c_int;
         let pid = cvt(unsafe { libc::waitpid(self.pid, &mut status, libc::WNOHANG) })?;
@@ -892,6 +887,97 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        if let Some(status) = self.try_wait()? {
+            return Ok(Some(status));
+        }
+        #[cfg(target_os = "linux")]
+        {
+            // Without a pidfd created along with the child, try to open one
+            // just for waiting. The child hasn't been reaped yet, so its pid
+            // can't have been reused.
+            let opened;
+            let pid_fd = match self.pidfd.as_ref() {
+                Some(pid_fd) => Some(pid_fd),
+                None => {
+                    opened = pidfd_open(self.pid);
+                    opened.as_ref()
+                }
+            };
+            if let Some(pid_fd) = pid_fd {
+                return if pidfd_poll(pid_fd, timeout)? { self.try_wait() } else { Ok(None) };
+            }
+        }
+        wait_timeout_by_polling(timeout, || self.try_wait())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn pidfd_open(pid: pid_t) -> Option<PidFd> {
+    use crate::os::unix::io::FromRawFd;
+    use crate::sys_common::FromInner;
+    let pidfd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) };
+    // Safety: If `pidfd` is nonnegative, it's a new file descriptor that we own.
+    (pidfd >= 0)
+        .then(|| unsafe { PidFd::from_inner(sys::fd::FileDesc::from_raw_fd(pidfd as c_int)) })
+}
+
+/// Waits for at most `timeout` until the process referred to by `pid_fd`
+/// exits, returning whether it did.
+#[cfg(target_os = "linux")]
+fn pidfd_poll(pid_fd: &PidFd, timeout: Duration) -> io::Result<bool> {
+    // A timeout too large to be represented means waiting forever.
+    let deadline = Instant::now().checked_add(timeout);
+    loop {
+        let timeout_ms = match deadline {
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                // Round up, so that we don't wake up just before the deadline.
+                let ms = remaining.as_nanos().div_ceil(1_000_000);
+                ms.try_into().unwrap_or(c_int::MAX)
+            }
+            None => -1,
+        };
+        let mut pollfd = libc::pollfd { fd: pid_fd.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+        match cvt(unsafe { libc::poll(&mut pollfd, 1, timeout_ms) }) {
+            Ok(0) if timeout_ms == 0 => return Ok(false),
+            // Either the timeout was clamped, or we woke up a bit early.
+            Ok(0) => {}
+            Ok(_) => return Ok(true),
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn pidfd_wait(pid_fd: &PidFd) -> io::Result<ExitStatus> {
+    use crate::sys::cvt_r;
+    let mut siginfo: libc::siginfo_t = unsafe { crate::mem::zeroed() };
+
+    cvt_r(|| unsafe {
+        libc::waitid(libc::P_PIDFD, pid_fd.as_raw_fd() as u32, &mut siginfo, libc::WEXITED)
+    })?;
+    Ok(ExitStatus::from_waitid_siginfo(siginfo))
+}
+
+#[cfg(target_os = "linux")]
+pub fn pidfd_try_wait(pid_fd: &PidFd) -> io::Result<Option<ExitStatus>> {
+    let mut siginfo: libc::siginfo_t = unsafe { crate::mem::zeroed() };
+
+    cvt(unsafe {
+        libc::waitid(
+            libc::P_PIDFD,
+            pid_fd.as_raw_fd() as u32,
+            &mut siginfo,
+            libc::WEXITED | libc::WNOHANG,
+        )
+    })?;
+    if unsafe { siginfo.si_pid() } == 0 {
+        return Ok(None);
+    }
+    Ok(Some(ExitStatus::from_waitid_siginfo(siginfo)))
 }
 
 /// Unix exit statuses
@@ -98,3 +98,75 @@ fn test_command_pidfd() {
     let status = child.wait().expect("error waiting on pidfd");
     assert_eq!(status.signal(), Some(libc::SIGKILL));
 }
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_pidfd_wait() {
+    use crate::os::linux::process::{ChildExt, CommandExt};
+    use crate::process::Command;
+
+    let mut child = Command::new("sleep").arg("1000").create_pidfd(true).spawn().unwrap();
+    let Ok(pidfd) = child.take_pidfd() else {
+        // pidfds aren't supported by this kernel.
+        child.kill().unwrap();
+        child.wait().unwrap();
+        return;
+    };
+    assert!(pidfd.try_wait().unwrap().is_none());
+    child.kill().unwrap();
+    assert_eq!(pidfd.wait().unwrap().signal(), Some(libc::SIGKILL));
+    // The child was reaped through the pidfd.
+    assert!(child.try_wait().is_err());
+}
+
+#[test]
+#[cfg_attr(any(target_os = "emscripten", target_os = "vxworks"), ignore)]
+fn test_child_send_signal_group() {
+    use crate::io::{BufRead, BufReader, Read};
+    use crate::os::unix::process::ChildExt;
+    use crate::process::Stdio;
+
+    let mut child = Command::new("sh")
+        .args(["-c", "sleep 1000 & echo started; sleep 1000"])
+        .process_group(0)
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let mut line = String::new();
+    stdout.read_line(&mut line).unwrap();
+
+    child.send_signal(libc::SIGTERM).unwrap();
+    assert_eq!(child.wait().unwrap().signal(), Some(libc::SIGTERM));
+
+    // The background `sleep` is still alive, and keeps the pipe open until
+    // it's killed through the process group.
+    child.send_signal_group(libc::SIGKILL).unwrap();
+    let mut output = Vec::new();
+    stdout.read_to_end(&mut output).unwrap();
+    assert!(output.is_empty());
+}
+
+#[test]
+#[cfg_attr(any(target_os = "emscripten", target_os = "vxworks"), ignore)]
+fn test_child_terminate_with_timeout() {
+    use crate::io::{BufRead, BufReader};
+    use crate::os::unix::process::ChildExt;
+    use crate::process::Stdio;
+    use crate::time::Duration;
+
+    let mut child = Command::new("sleep").arg("1000").spawn().unwrap();
+    let status = child.terminate_with_timeout(Duration::from_secs(60)).unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGTERM));
+
+    // A child ignoring `SIGTERM` is killed once the timeout elapses.
+    let mut child = Command::new("sh")
+        .args(["-c", "trap '' TERM; echo started; exec sleep 1000"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut line = String::new();
+    BufReader::new(child.stdout.take().unwrap()).read_line(&mut line).unwrap();
+    let status = child.terminate_with_timeout(Duration::from_millis(100)).unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGKILL));
+}
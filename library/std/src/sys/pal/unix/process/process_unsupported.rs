@@ -3,6 +3,7 @@
 use crate::num::NonZeroI32;
 use crate::sys::pal::unix::unsupported::*;
 use crate::sys::process::process_common::*;
+use crate::time::Duration;
 use core::ffi::NonZero_c_int;
 
 use libc::{c_int, pid_t};
@@ -46,6 +47,14 @@ pub fn kill(&mut self) -> io::Result<()> {
         unsupported()
     }
 
+    pub fn send_signal(&self, _signal: i32) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn send_signal_group(&self, _signal: i32) -> io::Result<()> {
+        unsupported()
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         unsupported()
     }
@@ -53,6 +62,10 @@ pub fn wait(&mut self) -> io::Result<ExitStatus> {
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         unsupported()
     }
+
+    pub fn wait_timeout(&mut self, _timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        unsupported()
+    }
 }
 
 mod wait_status;
@@ -7,6 +7,10 @@
 use crate::sys::{os, stack_overflow};
 use crate::time::Duration;
 
+#[cfg(target_os = "linux")]
+use crate::os::linux::thread::{CpuSet, SchedPolicy};
+#[cfg(target_os = "linux")]
+use crate::sys::cvt;
 #[cfg(all(target_os = "linux", target_env = "gnu"))]
 use crate::sys::weak::dlsym;
 #[cfg(any(target_os = "solaris", target_os = "illumos", target_os = "nto"))]
@@ -290,6 +294,52 @@ fn drop(&mut self) {
     }
 }
 
+#[cfg(target_os = "linux")]
+pub fn affinity() -> io::Result<CpuSet> {
+    let mut cpus = CpuSet::new();
+    // `CpuSet` has the same layout as `cpu_set_t`.
+    cvt(unsafe {
+        libc::sched_getaffinity(
+            0,
+            mem::size_of::<CpuSet>(),
+            &mut cpus as *mut CpuSet as *mut libc::cpu_set_t,
+        )
+    })?;
+    Ok(cpus)
+}
+
+#[cfg(target_os = "linux")]
+pub fn set_affinity(cpus: &CpuSet) -> io::Result<()> {
+    cvt(unsafe {
+        libc::sched_setaffinity(
+            0,
+            mem::size_of::<CpuSet>(),
+            cpus as *const CpuSet as *const libc::cpu_set_t,
+        )
+    })?;
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+pub fn set_scheduling(policy: SchedPolicy, priority: i32) -> io::Result<()> {
+    let policy = match policy {
+        SchedPolicy::Other => libc::SCHED_OTHER,
+        SchedPolicy::Fifo => libc::SCHED_FIFO,
+        SchedPolicy::RoundRobin => libc::SCHED_RR,
+        SchedPolicy::Batch => libc::SCHED_BATCH,
+        SchedPolicy::Idle => libc::SCHED_IDLE,
+    };
+    unsafe {
+        // Some C libraries have reserved fields in `sched_param`.
+        let mut param: libc::sched_param = mem::zeroed();
+        param.sched_priority = priority;
+        match libc::pthread_setschedparam(libc::pthread_self(), policy, &param) {
+            0 => Ok(()),
+            e => Err(io::Error::from_raw_os_error(e)),
+        }
+    }
+}
+
 #[cfg(any(
     target_os = "linux",
     target_os = "macos",
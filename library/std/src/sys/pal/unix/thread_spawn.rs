@@ -0,0 +1,57 @@
+use crate::io;
+use crate::os::linux::thread::{CpuSet, SchedPolicy};
+use crate::sync::mpsc::sync_channel;
+use crate::sys::thread::{Thread, set_affinity, set_scheduling};
+
+/// Settings from `os::linux::thread::BuilderExt`, which a new thread applies
+/// to itself before running its main function.
+#[derive(Clone, Debug, Default)]
+pub struct SpawnOptions {
+    pub affinity: Option<CpuSet>,
+    pub scheduling: Option<(SchedPolicy, i32)>,
+}
+
+impl SpawnOptions {
+    fn apply(&self) -> io::Result<()> {
+        if let Some(cpus) = &self.affinity {
+            set_affinity(cpus)?;
+        }
+        if let Some((policy, priority)) = self.scheduling {
+            set_scheduling(policy, priority)?;
+        }
+        Ok(())
+    }
+}
+
+// unsafe: see thread::Builder::spawn_unchecked for safety requirements
+pub unsafe fn spawn(
+    stack: usize,
+    p: Box<dyn FnOnce()>,
+    options: SpawnOptions,
+) -> io::Result<Thread> {
+    if options.affinity.is_none() && options.scheduling.is_none() {
+        return Thread::new(stack, p);
+    }
+
+    // The options are applied by the new thread itself, which reports
+    // whether that worked before running `p`, so that the thread never
+    // runs any user code with the wrong settings, and errors can be
+    // returned from here.
+    let (result_tx, result_rx) = sync_channel(1);
+    let main = Box::new(move || {
+        let result = options.apply();
+        let applied = result.is_ok();
+        let _ = result_tx.send(result);
+        if applied {
+            p();
+        }
+    });
+    let thread = Thread::new(stack, main)?;
+    match result_rx.recv() {
+        Ok(Err(e)) => {
+            thread.join();
+            Err(e)
+        }
+        _ => Ok(thread),
+    }
+}
@@ -6,6 +6,10 @@
 use crate::sys::time::SystemTime;
 use crate::sys::unsupported;
 
+pub use crate::sys_common::fs::{
+    create_dir_private, create_new, create_unnamed, link_unnamed, readdir_at,
+};
+
 pub struct File(!);
 
 pub struct FileAttr(!);
@@ -198,6 +202,26 @@ pub fn datasync(&self) -> io::Result<()> {
         self.0
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        self.0
+    }
+
     pub fn truncate(&self, _size: u64) -> io::Result<()> {
         self.0
     }
@@ -0,0 +1,27 @@
+use crate::fs::WatchEvent;
+use crate::io;
+use crate::path::Path;
+use crate::time::Instant;
+
+pub struct Watcher(!);
+
+impl Watcher {
+    pub fn new() -> io::Result<Watcher> {
+        Err(io::const_io_error!(
+            io::ErrorKind::Unsupported,
+            "filesystem watching is not supported on this platform",
+        ))
+    }
+
+    pub fn watch(&mut self, _path: &Path, _recursive: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn unwatch(&mut self, _path: &Path) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn next_event(&mut self, _deadline: Option<Instant>) -> io::Result<Option<WatchEvent>> {
+        self.0
+    }
+}
@@ -2,7 +2,17 @@
 
 pub struct AnonPipe(!);
 
+pub type UnnamedPipe = AnonPipe;
+
+pub fn unnamed_pipe() -> io::Result<(AnonPipe, AnonPipe)> {
+    Err(io::const_io_error!(io::ErrorKind::Unsupported, "pipes are not supported on this platform"))
+}
+
 impl AnonPipe {
+    pub fn try_clone(&self) -> io::Result<AnonPipe> {
+        self.0
+    }
+
     pub fn read(&self, _buf: &mut [u8]) -> io::Result<usize> {
         self.0
     }
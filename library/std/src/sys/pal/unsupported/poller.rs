@@ -0,0 +1,43 @@
+use crate::io::{self, Event, Interest};
+use crate::time::Duration;
+
+/// The bound of `io::PollSource`. Sources can't be registered on this
+/// platform, so no type implements it.
+pub trait Source {}
+
+pub struct Poller(!);
+
+impl Poller {
+    pub fn new() -> io::Result<Poller> {
+        Err(io::const_io_error!(
+            io::ErrorKind::Unsupported,
+            "readiness polling is not supported on this platform",
+        ))
+    }
+
+    pub fn add<S: Source + ?Sized>(
+        &mut self,
+        _source: &S,
+        _key: usize,
+        _interest: Interest,
+    ) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn modify<S: Source + ?Sized>(
+        &mut self,
+        _source: &S,
+        _key: usize,
+        _interest: Interest,
+    ) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn delete<S: Source + ?Sized>(&mut self, _source: &S) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn wait(&mut self, _events: &mut Vec<Event>, _timeout: Option<Duration>) -> io::Result<()> {
+        self.0
+    }
+}
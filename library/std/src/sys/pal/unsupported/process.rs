@@ -8,6 +8,7 @@
 use crate::sys::pipe::AnonPipe;
 use crate::sys::unsupported;
 use crate::sys_common::process::{CommandEnv, CommandEnvs};
+use crate::time::Duration;
 
 pub use crate::ffi::OsString as EnvKey;
 
@@ -33,6 +34,7 @@ pub enum Stdio {
     Inherit,
     Null,
     MakePipe,
+    SameAsStdout,
 }
 
 impl Command {
@@ -81,6 +83,10 @@ pub fn spawn(
     pub fn output(&mut self) -> io::Result<(ExitStatus, Vec<u8>, Vec<u8>)> {
         unsupported()
     }
+
+    pub fn output_combined(&mut self) -> io::Result<(ExitStatus, Vec<u8>)> {
+        unsupported()
+    }
 }
 
 impl From<AnonPipe> for Stdio {
@@ -214,6 +220,10 @@ pub fn wait(&mut self) -> io::Result<ExitStatus> {
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         self.0
     }
+
+    pub fn wait_timeout(&mut self, _timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        self.0
+    }
 }
 
 pub struct CommandArgs<'a> {
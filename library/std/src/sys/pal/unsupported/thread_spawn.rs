@@ -0,0 +1,16 @@
+use crate::io;
+use crate::sys::thread::Thread;
+
+/// Settings for spawning a thread besides its stack size, of which there are
+/// none on this platform.
+#[derive(Debug, Default)]
+pub struct SpawnOptions;
+
+// unsafe: see thread::Builder::spawn_unchecked for safety requirements
+pub unsafe fn spawn(
+    stack: usize,
+    p: Box<dyn FnOnce()>,
+    _options: SpawnOptions,
+) -> io::Result<Thread> {
+    Thread::new(stack, p)
+}
@@ -163,6 +163,54 @@ pub fn ttl(&self) -> io::Result<u32> {
         unsupported()
     }
 
+    pub fn set_keepalive(&self, _: bool) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        unsupported()
+    }
+
+    pub fn set_keepalive_idle(&self, _: Duration) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn keepalive_idle(&self) -> io::Result<Duration> {
+        unsupported()
+    }
+
+    pub fn set_keepalive_interval(&self, _: Duration) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        unsupported()
+    }
+
+    pub fn set_keepalive_retries(&self, _: u32) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        unsupported()
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        unsupported()
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        unsupported()
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         unsupported()
     }
@@ -312,6 +360,86 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+pub struct UnboundSocket(!);
+
+impl UnboundSocket {
+    pub fn new_tcp(_: bool) -> io::Result<UnboundSocket> {
+        unsupported()
+    }
+
+    pub fn new_udp(_: bool) -> io::Result<UnboundSocket> {
+        unsupported()
+    }
+
+    pub fn set_reuseaddr(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn set_reuseport(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn reuseport(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn set_only_v6(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn only_v6(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0
+    }
+
+    pub fn bind(&self, _: &SocketAddr) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+        self.0
+    }
+
+    pub fn listen(self, _: u32) -> io::Result<TcpListener> {
+        self.0
+    }
+
+    pub fn connect(self, _: &SocketAddr) -> io::Result<TcpStream> {
+        self.0
+    }
+
+    pub fn connect_timeout(self, _: &SocketAddr, _: Duration) -> io::Result<TcpStream> {
+        self.0
+    }
+
+    pub fn into_udp(self) -> UdpSocket {
+        self.0
+    }
+
+    pub fn fmt_named(&self, _: &str, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+    }
+}
+
 pub struct UdpSocket {
     inner: Socket,
 }
@@ -337,6 +465,14 @@ pub fn peek_from(&self, _: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
         unsupported()
     }
 
+    pub fn recv_vectored_from(&self, _: &mut [IoSliceMut<'_>]) -> io::Result<(usize, SocketAddr)> {
+        unsupported()
+    }
+
+    pub fn send_vectored_to(&self, _: &[IoSlice<'_>], _: &SocketAddr) -> io::Result<usize> {
+        unsupported()
+    }
+
     pub fn send_to(&self, _: &[u8], _: &SocketAddr) -> io::Result<usize> {
         unsupported()
     }
@@ -43,6 +43,10 @@ pub fn BCryptGenRandom(
     pub fn CancelIo(hfile: HANDLE) -> BOOL;
 }
 #[link(name = "kernel32")]
+extern "system" {
+    pub fn CancelIoEx(hfile: HANDLE, lpoverlapped: *const OVERLAPPED) -> BOOL;
+}
+#[link(name = "kernel32")]
 extern "system" {
     pub fn CloseHandle(hobject: HANDLE) -> BOOL;
 }
@@ -117,6 +121,15 @@ pub fn CreateNamedPipeW(
     ) -> HANDLE;
 }
 #[link(name = "kernel32")]
+extern "system" {
+    pub fn CreatePipe(
+        hreadpipe: *mut HANDLE,
+        hwritepipe: *mut HANDLE,
+        lppipeattributes: *const SECURITY_ATTRIBUTES,
+        nsize: u32,
+    ) -> BOOL;
+}
+#[link(name = "kernel32")]
 extern "system" {
     pub fn CreateProcessW(
         lpapplicationname: PCWSTR,
@@ -394,6 +407,17 @@ pub fn InitializeProcThreadAttributeList(
     ) -> BOOL;
 }
 #[link(name = "kernel32")]
+extern "system" {
+    pub fn LockFileEx(
+        hfile: HANDLE,
+        dwflags: LOCK_FILE_FLAGS,
+        dwreserved: u32,
+        nnumberofbytestolocklow: u32,
+        nnumberofbytestolockhigh: u32,
+        lpoverlapped: *mut OVERLAPPED,
+    ) -> BOOL;
+}
+#[link(name = "kernel32")]
 extern "system" {
     pub fn MoveFileExW(
         lpexistingfilename: PCWSTR,
@@ -577,6 +601,16 @@ pub fn SleepConditionVariableSRW(
     pub fn TryAcquireSRWLockShared(srwlock: *mut SRWLOCK) -> BOOLEAN;
 }
 #[link(name = "kernel32")]
+extern "system" {
+    pub fn UnlockFile(
+        hfile: HANDLE,
+        dwfileoffsetlow: u32,
+        dwfileoffsethigh: u32,
+        nnumberofbytestounlocklow: u32,
+        nnumberofbytestounlockhigh: u32,
+    ) -> BOOL;
+}
+#[link(name = "kernel32")]
 extern "system" {
     pub fn UpdateProcThreadAttribute(
         lpattributelist: LPPROC_THREAD_ATTRIBUTE_LIST,
@@ -715,6 +749,10 @@ pub fn WSADuplicateSocketW(
     pub fn WSAGetLastError() -> WSA_ERROR;
 }
 #[link(name = "ws2_32")]
+extern "system" {
+    pub fn WSAPoll(fdarray: *mut WSAPOLLFD, fds: u32, timeout: i32) -> i32;
+}
+#[link(name = "ws2_32")]
 extern "system" {
     pub fn WSARecv(
         s: SOCKET,
@@ -727,6 +765,20 @@ pub fn WSARecv(
     ) -> i32;
 }
 #[link(name = "ws2_32")]
+extern "system" {
+    pub fn WSARecvFrom(
+        s: SOCKET,
+        lpbuffers: *const WSABUF,
+        dwbuffercount: u32,
+        lpnumberofbytesrecvd: *mut u32,
+        lpflags: *mut u32,
+        lpfrom: *mut SOCKADDR,
+        lpfromlen: *mut i32,
+        lpoverlapped: *mut OVERLAPPED,
+        lpcompletionroutine: LPWSAOVERLAPPED_COMPLETION_ROUTINE,
+    ) -> i32;
+}
+#[link(name = "ws2_32")]
 extern "system" {
     pub fn WSASend(
         s: SOCKET,
@@ -739,6 +791,20 @@ pub fn WSASend(
     ) -> i32;
 }
 #[link(name = "ws2_32")]
+extern "system" {
+    pub fn WSASendTo(
+        s: SOCKET,
+        lpbuffers: *const WSABUF,
+        dwbuffercount: u32,
+        lpnumberofbytessent: *mut u32,
+        dwflags: u32,
+        lpto: *const SOCKADDR,
+        itolen: i32,
+        lpoverlapped: *mut OVERLAPPED,
+        lpcompletionroutine: LPWSAOVERLAPPED_COMPLETION_ROUTINE,
+    ) -> i32;
+}
+#[link(name = "ws2_32")]
 extern "system" {
     pub fn WSASocketW(
         af: i32,
@@ -3649,6 +3715,9 @@ fn clone(&self) -> Self {
         *self
     }
 }
+pub const LOCKFILE_EXCLUSIVE_LOCK: LOCK_FILE_FLAGS = 2u32;
+pub const LOCKFILE_FAIL_IMMEDIATELY: LOCK_FILE_FLAGS = 1u32;
+pub type LOCK_FILE_FLAGS = u32;
 pub type LPOVERLAPPED_COMPLETION_ROUTINE = ::core::option::Option<
     unsafe extern "system" fn(
         dwerrorcode: u32,
@@ -3794,6 +3863,11 @@ fn clone(&self) -> Self {
 pub const PIPE_TYPE_BYTE: NAMED_PIPE_MODE = 0u32;
 pub const PIPE_TYPE_MESSAGE: NAMED_PIPE_MODE = 4u32;
 pub const PIPE_WAIT: NAMED_PIPE_MODE = 0u32;
+pub const POLLERR: i16 = 1i16;
+pub const POLLHUP: i16 = 2i16;
+pub const POLLNVAL: i16 = 4i16;
+pub const POLLRDNORM: i16 = 256i16;
+pub const POLLWRNORM: i16 = 16i16;
 pub type PRIORITY_HINT = i32;
 pub type PROCESSOR_ARCHITECTURE = u16;
 pub type PROCESS_CREATION_FLAGS = u32;
@@ -3884,8 +3958,12 @@ fn clone(&self) -> Self {
 pub const SOL_SOCKET: i32 = 65535i32;
 pub const SO_BROADCAST: i32 = 32i32;
 pub const SO_ERROR: i32 = 4103i32;
+pub const SO_KEEPALIVE: i32 = 8i32;
 pub const SO_LINGER: i32 = 128i32;
+pub const SO_RCVBUF: i32 = 4098i32;
 pub const SO_RCVTIMEO: i32 = 4102i32;
+pub const SO_REUSEADDR: i32 = 4i32;
+pub const SO_SNDBUF: i32 = 4097i32;
 pub const SO_SNDTIMEO: i32 = 4101i32;
 pub const SPECIFIC_RIGHTS_ALL: FILE_ACCESS_RIGHTS = 65535u32;
 #[repr(C)]
@@ -4014,6 +4092,9 @@ fn clone(&self) -> Self {
         *self
     }
 }
+pub const TCP_KEEPCNT: i32 = 16i32;
+pub const TCP_KEEPIDLE: i32 = 3i32;
+pub const TCP_KEEPINTVL: i32 = 17i32;
 pub const TCP_NODELAY: i32 = 1i32;
 pub const THREAD_CREATE_RUN_IMMEDIATELY: THREAD_CREATION_FLAGS = 0u32;
 pub const THREAD_CREATE_SUSPENDED: THREAD_CREATION_FLAGS = 4u32;
@@ -4208,6 +4289,18 @@ fn clone(&self) -> Self {
 pub const WSANO_DATA: WSA_ERROR = 11004i32;
 pub const WSANO_RECOVERY: WSA_ERROR = 11003i32;
 #[repr(C)]
+pub struct WSAPOLLFD {
+    pub fd: SOCKET,
+    pub events: i16,
+    pub revents: i16,
+}
+impl ::core::marker::Copy for WSAPOLLFD {}
+impl ::core::clone::Clone for WSAPOLLFD {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+#[repr(C)]
 pub struct WSAPROTOCOLCHAIN {
     pub ChainLen: i32,
     pub ChainEntries: [u32; 7],
@@ -19,6 +19,10 @@
 use super::path::maybe_verbatim;
 use super::{api, to_u16s, IoResult};
 
+pub use crate::sys_common::fs::{
+    create_dir_private, create_new, create_unnamed, link_unnamed, readdir_at,
+};
+
 pub struct File {
     handle: Handle,
 }
@@ -340,6 +344,82 @@ pub fn datasync(&self) -> io::Result<()> {
         self.fsync()
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        self.lock_file_ex(c::LOCKFILE_EXCLUSIVE_LOCK)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.lock_file_ex(0)
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.try_lock_file_ex(c::LOCKFILE_EXCLUSIVE_LOCK)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.try_lock_file_ex(0)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        // A handle may hold both an exclusive and a shared lock on the same
+        // range, in which case each `UnlockFile` only releases one of them.
+        // Like `flock`, unlocking a file that isn't locked is not an error.
+        for _ in 0..2 {
+            match self.unlock_file() {
+                Ok(()) => {}
+                Err(e) if e.raw_os_error() == Some(c::ERROR_NOT_LOCKED as _) => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    fn lock_file_ex(&self, flags: c::LOCK_FILE_FLAGS) -> io::Result<()> {
+        // Handles opened for asynchronous I/O may return `ERROR_IO_PENDING`
+        // before the lock is taken, with the request still using `overlapped`.
+        // It is given an event so that the request can be waited for, after
+        // cancelling it if it shouldn't block.
+        let event = Handle::new_event(true, false)?;
+        let handle = self.handle.as_raw_handle();
+        unsafe {
+            let mut overlapped: c::OVERLAPPED = mem::zeroed();
+            overlapped.hEvent = event.as_raw_handle();
+            // The whole file is locked, including any range it may later grow into.
+            match cvt(c::LockFileEx(handle, flags, 0, u32::MAX, u32::MAX, &mut overlapped)) {
+                Err(e) if e.raw_os_error() == Some(c::ERROR_IO_PENDING as _) => {
+                    if flags & c::LOCKFILE_FAIL_IMMEDIATELY != 0 {
+                        // Fails if the request has completed in the meantime.
+                        c::CancelIoEx(handle, &overlapped);
+                    }
+                    let mut bytes = 0;
+                    cvt(c::GetOverlappedResult(handle, &mut overlapped, &mut bytes, c::TRUE))?;
+                    Ok(())
+                }
+                res => res.map(drop),
+            }
+        }
+    }
+
+    fn try_lock_file_ex(&self, flags: c::LOCK_FILE_FLAGS) -> io::Result<bool> {
+        match self.lock_file_ex(flags | c::LOCKFILE_FAIL_IMMEDIATELY) {
+            Ok(()) => Ok(true),
+            // A request of a handle opened for asynchronous I/O that couldn't
+            // complete immediately is cancelled instead.
+            Err(e)
+                if e.raw_os_error() == Some(c::ERROR_LOCK_VIOLATION as _)
+                    || e.raw_os_error() == Some(c::ERROR_OPERATION_ABORTED as _) =>
+            {
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn unlock_file(&self) -> io::Result<()> {
+        cvt(unsafe { c::UnlockFile(self.handle.as_raw_handle(), 0, 0, u32::MAX, u32::MAX) })?;
+        Ok(())
+    }
+
     pub fn truncate(&self, size: u64) -> io::Result<()> {
         let info = c::FILE_END_OF_FILE_INFO { EndOfFile: size as i64 };
         api::set_file_information_by_handle(self.handle.as_raw_handle(), &info).io_result()
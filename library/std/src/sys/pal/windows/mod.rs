@@ -27,6 +27,7 @@
 pub mod os_str;
 pub mod path;
 pub mod pipe;
+pub mod poller;
 pub mod process;
 pub mod rand;
 pub mod stdio;
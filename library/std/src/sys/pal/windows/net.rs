@@ -333,6 +333,46 @@ pub fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
         self.recv_from_with_flags(buf, 0)
     }
 
+    pub fn recv_vectored_from(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, SocketAddr)> {
+        let mut storage = unsafe { mem::zeroed::<c::SOCKADDR_STORAGE_LH>() };
+        let mut addrlen = mem::size_of_val(&storage) as c::socklen_t;
+        let length = cmp::min(bufs.len(), c::DWORD::MAX as usize) as c::DWORD;
+        let mut nread = 0;
+        let mut flags = 0;
+
+        // On unix when a socket is shut down all further reads return 0, so we
+        // do the same on windows to map a shut down socket to returning EOF.
+        let result = unsafe {
+            c::WSARecvFrom(
+                self.as_raw(),
+                bufs.as_mut_ptr() as *mut c::WSABUF,
+                length,
+                &mut nread,
+                &mut flags,
+                &mut storage as *mut _ as *mut _,
+                &mut addrlen,
+                ptr::null_mut(),
+                None,
+            )
+        };
+
+        match result {
+            0 => Ok((nread as usize, net::sockaddr_to_addr(&storage, addrlen as usize)?)),
+            _ => {
+                let error = unsafe { c::WSAGetLastError() };
+
+                if error == c::WSAESHUTDOWN {
+                    Ok((0, net::sockaddr_to_addr(&storage, addrlen as usize)?))
+                } else {
+                    Err(io::Error::from_raw_os_error(error))
+                }
+            }
+        }
+    }
+
     pub fn peek_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
         self.recv_from_with_flags(buf, c::MSG_PEEK)
     }
@@ -359,6 +399,26 @@ pub fn is_write_vectored(&self) -> bool {
         true
     }
 
+    pub fn send_vectored_to(&self, bufs: &[IoSlice<'_>], dst: &SocketAddr) -> io::Result<usize> {
+        let length = cmp::min(bufs.len(), c::DWORD::MAX as usize) as c::DWORD;
+        let (dst, dstlen) = dst.into_inner();
+        let mut nwritten = 0;
+        let result = unsafe {
+            c::WSASendTo(
+                self.as_raw(),
+                bufs.as_ptr() as *const c::WSABUF,
+                length,
+                &mut nwritten,
+                0,
+                dst.as_ptr(),
+                dstlen,
+                ptr::null_mut(),
+                None,
+            )
+        };
+        cvt(result).map(|_| nwritten as usize)
+    }
+
     pub fn set_timeout(&self, dur: Option<Duration>, kind: c_int) -> io::Result<()> {
         let timeout = match dur {
             Some(dur) => {
@@ -1,7 +1,7 @@
 use crate::os::windows::prelude::*;
 
 use crate::ffi::OsStr;
-use crate::io::{self, BorrowedCursor, IoSlice, IoSliceMut, Read};
+use crate::io::{self, BorrowedCursor, IoSlice, IoSliceMut, PipeReader, PipeWriter, Read};
 use crate::mem;
 use crate::path::Path;
 use crate::ptr;
@@ -9,10 +9,11 @@
 use crate::sync::atomic::AtomicUsize;
 use crate::sync::atomic::Ordering::SeqCst;
 use crate::sys::c;
+use crate::sys::cvt;
 use crate::sys::fs::{File, OpenOptions};
 use crate::sys::handle::Handle;
 use crate::sys::hashmap_random_keys;
-use crate::sys_common::{FromInner, IntoInner};
+use crate::sys_common::{AsInner, FromInner, IntoInner};
 
 ////////////////////////////////////////////////////////////////////////////////
 // Anonymous pipes
@@ -171,6 +172,70 @@ pub fn anon_pipe(ours_readable: bool, their_handle_inheritable: bool) -> io::Res
     }
 }
 
+/// The pipes of `io::pipe` are used synchronously, so unlike `AnonPipe`, they
+/// are plain handles, which can be passed to child processes as they are.
+pub type UnnamedPipe = Handle;
+
+/// Creates a pipe with `CreatePipe`, whose handles are not inheritable. They
+/// are duplicated into inheritable handles when passed to a child process.
+pub fn unnamed_pipe() -> io::Result<(Handle, Handle)> {
+    let mut read_pipe = ptr::null_mut();
+    let mut write_pipe = ptr::null_mut();
+    cvt(unsafe { c::CreatePipe(&mut read_pipe, &mut write_pipe, ptr::null(), 0) })?;
+    // SAFETY: `CreatePipe` returned two new handles, that we own.
+    unsafe { Ok((Handle::from_raw_handle(read_pipe), Handle::from_raw_handle(write_pipe))) }
+}
+
+macro_rules! impl_pipe_conversions {
+    ($($t:ty)*) => {$(
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl AsHandle for $t {
+            fn as_handle(&self) -> BorrowedHandle<'_> {
+                self.as_inner().as_handle()
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl AsRawHandle for $t {
+            fn as_raw_handle(&self) -> RawHandle {
+                self.as_inner().as_raw_handle()
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl IntoRawHandle for $t {
+            fn into_raw_handle(self) -> RawHandle {
+                self.into_inner().into_raw_handle()
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl FromRawHandle for $t {
+            unsafe fn from_raw_handle(raw_handle: RawHandle) -> Self {
+                Self::from_inner(Handle::from_raw_handle(raw_handle))
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl From<$t> for OwnedHandle {
+            fn from(pipe: $t) -> OwnedHandle {
+                pipe.into_inner().into_inner()
+            }
+        }
+
+        /// Creates a pipe end from the provided `OwnedHandle`, which should
+        /// be the corresponding end of a pipe opened for synchronous I/O.
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl From<OwnedHandle> for $t {
+            fn from(handle: OwnedHandle) -> Self {
+                Self::from_inner(Handle::from_inner(handle))
+            }
+        }
+    )*};
+}
+
+impl_pipe_conversions!(PipeReader PipeWriter);
+
 /// Takes an asynchronous source pipe and returns a synchronous pipe suitable
 /// for sending to a child process.
 ///
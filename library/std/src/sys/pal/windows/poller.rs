@@ -0,0 +1,111 @@
+//! Native implementation of `io::Poller`, with `WSAPoll`.
+
+use crate::io::{self, Event, Interest};
+use crate::os::windows::io::{AsRawSocket, AsSocket};
+use crate::sys::c;
+use crate::sys::net::{cvt, init};
+use crate::thread;
+use crate::time::Duration;
+
+/// The bound of `io::PollSource`: the sources are the sockets.
+pub trait Source: AsSocket {}
+
+impl<T: AsSocket + ?Sized> Source for T {}
+
+pub struct Poller {
+    fds: Vec<c::WSAPOLLFD>,
+    /// The keys of the sources, in the same order as `fds`.
+    keys: Vec<usize>,
+}
+
+impl Poller {
+    pub fn new() -> io::Result<Poller> {
+        init();
+        Ok(Poller { fds: Vec::new(), keys: Vec::new() })
+    }
+
+    pub fn add<S: Source + ?Sized>(
+        &mut self,
+        source: &S,
+        key: usize,
+        interest: Interest,
+    ) -> io::Result<()> {
+        let fd = source.as_socket().as_raw_socket() as c::SOCKET;
+        if self.position(fd).is_ok() {
+            return Err(io::const_io_error!(
+                io::ErrorKind::AlreadyExists,
+                "source is already registered",
+            ));
+        }
+        self.fds.push(c::WSAPOLLFD { fd, events: poll_events(interest), revents: 0 });
+        self.keys.push(key);
+        Ok(())
+    }
+
+    pub fn modify<S: Source + ?Sized>(
+        &mut self,
+        source: &S,
+        key: usize,
+        interest: Interest,
+    ) -> io::Result<()> {
+        let i = self.position(source.as_socket().as_raw_socket() as c::SOCKET)?;
+        self.fds[i].events = poll_events(interest);
+        self.keys[i] = key;
+        Ok(())
+    }
+
+    pub fn delete<S: Source + ?Sized>(&mut self, source: &S) -> io::Result<()> {
+        let i = self.position(source.as_socket().as_raw_socket() as c::SOCKET)?;
+        self.fds.swap_remove(i);
+        self.keys.swap_remove(i);
+        Ok(())
+    }
+
+    fn position(&self, fd: c::SOCKET) -> io::Result<usize> {
+        self.fds
+            .iter()
+            .position(|pollfd| pollfd.fd == fd)
+            .ok_or_else(|| io::const_io_error!(io::ErrorKind::NotFound, "source is not registered"))
+    }
+
+    pub fn wait(&mut self, events: &mut Vec<Event>, timeout: Option<Duration>) -> io::Result<()> {
+        // `WSAPoll` fails without any socket to poll, and nothing could ever
+        // be reported without a timeout.
+        if self.fds.is_empty() {
+            if let Some(timeout) = timeout {
+                thread::sleep(timeout);
+            }
+            return Ok(());
+        }
+
+        let timeout = match timeout {
+            // Round up, so that we don't wake up just before the timeout.
+            Some(timeout) => timeout.as_nanos().div_ceil(1_000_000).try_into().unwrap_or(i32::MAX),
+            None => -1,
+        };
+        let len = self.fds.len() as u32;
+        cvt(unsafe { c::WSAPoll(self.fds.as_mut_ptr(), len, timeout) })?;
+
+        let closed = c::POLLHUP | c::POLLERR | c::POLLNVAL;
+        let ready = self.fds.iter().zip(&self.keys).filter(|(pollfd, _)| pollfd.revents != 0);
+        events.extend(ready.map(|(pollfd, &key)| {
+            Event::new(
+                key,
+                pollfd.revents & (c::POLLRDNORM | closed) != 0,
+                pollfd.revents & (c::POLLWRNORM | closed) != 0,
+            )
+        }));
+        Ok(())
+    }
+}
+
+fn poll_events(interest: Interest) -> i16 {
+    let mut events = 0;
+    if interest.is_readable() {
+        events |= c::POLLRDNORM;
+    }
+    if interest.is_writable() {
+        events |= c::POLLWRNORM;
+    }
+    events
+}
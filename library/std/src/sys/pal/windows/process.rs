@@ -20,14 +20,15 @@
 use crate::sync::Mutex;
 use crate::sys::args::{self, Arg};
 use crate::sys::c::{self, NonZeroDWORD, EXIT_FAILURE, EXIT_SUCCESS};
-use crate::sys::cvt;
 use crate::sys::fs::{File, OpenOptions};
 use crate::sys::handle::Handle;
 use crate::sys::path;
 use crate::sys::pipe::{self, AnonPipe};
 use crate::sys::stdio;
+use crate::sys::{cvt, dur2timeout};
 use crate::sys_common::process::{CommandEnv, CommandEnvs};
 use crate::sys_common::IntoInner;
+use crate::time::Duration;
 
 use core::ffi::c_void;
 
@@ -176,6 +177,8 @@ pub enum Stdio {
     MakePipe,
     Pipe(AnonPipe),
     Handle(Handle),
+    // Only used for stderr, which then refers to the same file as stdout.
+    SameAsStdout,
 }
 
 pub struct StdioPipes {
@@ -319,7 +322,14 @@ pub fn spawn(
         let stderr = self.stderr.as_ref().unwrap_or(&default);
         let stdin = stdin.to_handle(c::STD_INPUT_HANDLE, &mut pipes.stdin)?;
         let stdout = stdout.to_handle(c::STD_OUTPUT_HANDLE, &mut pipes.stdout)?;
-        let stderr = stderr.to_handle(c::STD_ERROR_HANDLE, &mut pipes.stderr)?;
+        let stderr = match stderr {
+            // If no stdout handle is available, then propagate the null value.
+            Stdio::SameAsStdout if stdout.as_raw_handle().is_null() => unsafe {
+                Handle::from_raw_handle(ptr::null_mut())
+            },
+            Stdio::SameAsStdout => stdout.duplicate(0, true, c::DUPLICATE_SAME_ACCESS)?,
+            _ => stderr.to_handle(c::STD_ERROR_HANDLE, &mut pipes.stderr)?,
+        };
 
         let mut si = zeroed_startupinfo();
 
@@ -386,6 +396,15 @@ pub fn output(&mut self) -> io::Result<(ExitStatus, Vec<u8>, Vec<u8>)> {
         let (proc, pipes) = self.spawn(Stdio::MakePipe, false)?;
         crate::sys_common::process::wait_with_output(proc, pipes)
     }
+
+    pub fn output_combined(&mut self) -> io::Result<(ExitStatus, Vec<u8>)> {
+        let stderr = self.stderr.replace(Stdio::SameAsStdout);
+        let spawned = self.spawn(Stdio::MakePipe, false);
+        self.stderr = stderr;
+        let (proc, pipes) = spawned?;
+        let (status, stdout, _) = crate::sys_common::process::wait_with_output(proc, pipes)?;
+        Ok((status, stdout))
+    }
 }
 
 impl fmt::Debug for Command {
@@ -599,6 +618,9 @@ fn to_handle(&self, stdio_id: c::DWORD, pipe: &mut Option<AnonPipe>) -> io::Resu
                 opts.security_attributes(&mut sa);
                 File::open(Path::new(r"\\.\NUL"), &opts).map(|file| file.into_inner())
             }
+
+            // Handled by `Command::spawn`, since it depends on the child's stdout.
+            Stdio::SameAsStdout => unreachable!(),
         }
     }
 }
@@ -615,6 +637,12 @@ fn from(file: File) -> Stdio {
     }
 }
 
+impl From<Handle> for Stdio {
+    fn from(handle: Handle) -> Stdio {
+        Stdio::Handle(handle)
+    }
+}
+
 impl From<io::Stdout> for Stdio {
     fn from(_: io::Stdout) -> Stdio {
         Stdio::InheritSpecific { from_stdio_id: c::STD_OUTPUT_HANDLE }
@@ -677,8 +705,12 @@ pub fn wait(&mut self) -> io::Result<ExitStatus> {
     }
 
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.wait_timeout(Duration::ZERO)
+    }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
         unsafe {
-            match c::WaitForSingleObject(self.handle.as_raw_handle(), 0) {
+            match c::WaitForSingleObject(self.handle.as_raw_handle(), dur2timeout(timeout)) {
                 c::WAIT_OBJECT_0 => {}
                 c::WAIT_TIMEOUT => {
                     return Ok(None);
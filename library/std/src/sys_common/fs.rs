@@ -1,8 +1,10 @@
 #![allow(dead_code)] // not used on all platforms
 
+use crate::ffi::OsStr;
 use crate::fs;
 use crate::io::{self, Error, ErrorKind};
 use crate::path::Path;
+use crate::sys;
 
 pub(crate) const NOT_FILE_ERROR: Error = io::const_io_error!(
     ErrorKind::InvalidInput,
@@ -42,6 +44,20 @@ fn remove_dir_all_recursive(path: &Path) -> io::Result<()> {
     fs::remove_dir(path)
 }
 
+/// Opens the subdirectory `name` of `parent`, whose full path is `path`.
+///
+/// This generic version opens `path` itself, so symbolic links are followed
+/// even if `follow_links` isn't set, e.g. if the subdirectory was replaced by
+/// one since it was read from `parent`.
+pub fn readdir_at(
+    _parent: &sys::fs::ReadDir,
+    _name: &OsStr,
+    path: &Path,
+    _follow_links: bool,
+) -> io::Result<sys::fs::ReadDir> {
+    sys::fs::readdir(path)
+}
+
 pub fn try_exists(path: &Path) -> io::Result<bool> {
     match fs::metadata(path) {
         Ok(_) => Ok(true),
@@ -49,3 +65,35 @@ pub fn try_exists(path: &Path) -> io::Result<bool> {
         Err(error) => Err(error),
     }
 }
+
+/// Creates a new file for reading and writing.
+///
+/// This generic version ignores `private`, for platforms without
+/// permissions that restrict a file to its owner.
+pub fn create_new(path: &Path, _private: bool) -> io::Result<sys::fs::File> {
+    let mut options = sys::fs::OpenOptions::new();
+    options.read(true);
+    options.write(true);
+    options.create_new(true);
+    sys::fs::File::open(path, &options)
+}
+
+/// Creates a new directory.
+///
+/// This generic version creates it with the default permissions.
+pub fn create_dir_private(path: &Path) -> io::Result<()> {
+    sys::fs::DirBuilder::new().mkdir(path)
+}
+
+/// Creates a file without a name in `dir`, in the same way as [`create_new`],
+/// or returns `None` if that isn't supported.
+///
+/// This generic version always returns `None`.
+pub fn create_unnamed(_dir: &Path, _private: bool) -> io::Result<Option<sys::fs::File>> {
+    Ok(None)
+}
+
+/// Gives a name to a file created by [`create_unnamed`].
+pub fn link_unnamed(_file: &sys::fs::File, _link: &Path) -> io::Result<()> {
+    unreachable!("`create_unnamed` never creates files on this platform")
+}
@@ -0,0 +1,143 @@
+//! A filesystem watcher that polls the metadata of the watched files, for
+//! platforms without a native way of watching the filesystem.
+
+use crate::collections::{BTreeMap, VecDeque};
+use crate::fs::{self, WatchEvent};
+use crate::io;
+use crate::mem;
+use crate::path::{Path, PathBuf};
+use crate::thread;
+use crate::time::{Duration, Instant, SystemTime};
+
+pub struct PollWatcher {
+    interval: Duration,
+    watches: Vec<Watch>,
+    events: VecDeque<WatchEvent>,
+    /// `None` if the interval is too large for the watcher to ever poll again.
+    next_poll: Option<Instant>,
+}
+
+struct Watch {
+    path: PathBuf,
+    recursive: bool,
+    snapshot: BTreeMap<PathBuf, Entry>,
+}
+
+#[derive(PartialEq, Eq)]
+enum Entry {
+    // The modification time of directories isn't compared, since it only
+    // changes when entries are created or removed, which are reported anyway.
+    Dir,
+    File { len: u64, modified: Option<SystemTime> },
+}
+
+impl PollWatcher {
+    pub fn new(interval: Duration) -> PollWatcher {
+        PollWatcher {
+            interval,
+            watches: Vec::new(),
+            events: VecDeque::new(),
+            next_poll: Instant::now().checked_add(interval),
+        }
+    }
+
+    pub fn watch(&mut self, path: &Path, recursive: bool) -> io::Result<()> {
+        fs::metadata(path)?;
+        let snapshot = snapshot(path, recursive);
+        match self.watches.iter_mut().find(|watch| watch.path == path) {
+            Some(watch) => *watch = Watch { path: path.to_owned(), recursive, snapshot },
+            None => self.watches.push(Watch { path: path.to_owned(), recursive, snapshot }),
+        }
+        Ok(())
+    }
+
+    pub fn unwatch(&mut self, path: &Path) -> io::Result<()> {
+        match self.watches.iter().position(|watch| watch.path == path) {
+            Some(i) => {
+                self.watches.remove(i);
+                Ok(())
+            }
+            None => Err(io::const_io_error!(io::ErrorKind::NotFound, "path is not watched")),
+        }
+    }
+
+    pub fn next_event(&mut self, deadline: Option<Instant>) -> io::Result<Option<WatchEvent>> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Ok(Some(event));
+            }
+            let now = Instant::now();
+            match self.next_poll {
+                Some(next_poll) if next_poll <= now => {
+                    self.poll();
+                    self.next_poll = now.checked_add(self.interval);
+                    continue;
+                }
+                _ => {}
+            }
+            let wake_up = match (self.next_poll, deadline) {
+                (_, Some(deadline)) if deadline <= now => return Ok(None),
+                (Some(next_poll), Some(deadline)) => next_poll.min(deadline),
+                (Some(wake_up), None) | (None, Some(wake_up)) => wake_up,
+                (None, None) => loop {
+                    thread::park();
+                },
+            };
+            thread::sleep(wake_up - now);
+        }
+    }
+
+    fn poll(&mut self) {
+        for watch in &mut self.watches {
+            let old = mem::replace(&mut watch.snapshot, snapshot(&watch.path, watch.recursive));
+            let new = &watch.snapshot;
+            // Children are removed before their parent, and created after it.
+            for path in old.keys().rev().filter(|&path| !new.contains_key(path)) {
+                self.events.push_back(WatchEvent::Remove(path.clone()));
+            }
+            for (path, entry) in new {
+                match old.get(path) {
+                    None => self.events.push_back(WatchEvent::Create(path.clone())),
+                    Some(old_entry) if old_entry != entry => {
+                        self.events.push_back(WatchEvent::Modify(path.clone()))
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+}
+
+/// Records the metadata of `path` and of its entries, or of all of its
+/// descendants if `recursive` is set. Files that can't be read are skipped.
+fn snapshot(path: &Path, recursive: bool) -> BTreeMap<PathBuf, Entry> {
+    let mut snapshot = BTreeMap::new();
+    // The watched path itself may be a symbolic link, which is followed.
+    if let Ok(metadata) = fs::metadata(path) {
+        snapshot.insert(path.to_owned(), entry(&metadata));
+        if metadata.is_dir() {
+            add_entries(&mut snapshot, path, recursive);
+        }
+    }
+    snapshot
+}
+
+fn add_entries(snapshot: &mut BTreeMap<PathBuf, Entry>, dir: &Path, recursive: bool) {
+    let Ok(read_dir) = fs::read_dir(dir) else { return };
+    for child in read_dir.flatten() {
+        let Ok(metadata) = child.metadata() else { continue };
+        let path = child.path();
+        if recursive && metadata.is_dir() {
+            add_entries(snapshot, &path, recursive);
+        }
+        snapshot.insert(path, entry(&metadata));
+    }
+}
+
+fn entry(metadata: &fs::Metadata) -> Entry {
+    if metadata.is_dir() {
+        Entry::Dir
+    } else {
+        Entry::File { len: metadata.len(), modified: metadata.modified().ok() }
+    }
+}
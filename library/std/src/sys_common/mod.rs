@@ -22,6 +22,7 @@
 
 pub mod backtrace;
 pub mod fs;
+pub mod fs_watcher;
 pub mod io;
 pub mod lazy_box;
 pub mod memchr;
@@ -55,6 +55,56 @@
     }
 }
 
+// The socket options that are only available on some platforms.
+cfg_if::cfg_if! {
+    if #[cfg(any(
+        target_os = "linux", target_os = "android",
+        target_os = "dragonfly", target_os = "freebsd",
+        target_os = "openbsd", target_os = "netbsd",
+        target_os = "ios", target_os = "tvos", target_os = "macos", target_os = "watchos"))] {
+        const SO_REUSEPORT: Option<c_int> = Some(libc::SO_REUSEPORT);
+    } else {
+        const SO_REUSEPORT: Option<c_int> = None;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "hermit")] {
+        const SO_SNDBUF: Option<c_int> = None;
+        const SO_RCVBUF: Option<c_int> = None;
+        const SO_KEEPALIVE: Option<c_int> = None;
+    } else {
+        const SO_SNDBUF: Option<c_int> = Some(c::SO_SNDBUF);
+        const SO_RCVBUF: Option<c_int> = Some(c::SO_RCVBUF);
+        const SO_KEEPALIVE: Option<c_int> = Some(c::SO_KEEPALIVE);
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(any(
+        target_os = "linux", target_os = "android",
+        target_os = "dragonfly", target_os = "freebsd",
+        target_os = "netbsd"))] {
+        const TCP_KEEPIDLE: Option<c_int> = Some(libc::TCP_KEEPIDLE);
+        const TCP_KEEPINTVL: Option<c_int> = Some(libc::TCP_KEEPINTVL);
+        const TCP_KEEPCNT: Option<c_int> = Some(libc::TCP_KEEPCNT);
+    } else if #[cfg(any(
+        target_os = "ios", target_os = "tvos", target_os = "macos", target_os = "watchos"))] {
+        // Apple platforms name the idle time `TCP_KEEPALIVE`.
+        const TCP_KEEPIDLE: Option<c_int> = Some(libc::TCP_KEEPALIVE);
+        const TCP_KEEPINTVL: Option<c_int> = Some(libc::TCP_KEEPINTVL);
+        const TCP_KEEPCNT: Option<c_int> = Some(libc::TCP_KEEPCNT);
+    } else if #[cfg(windows)] {
+        const TCP_KEEPIDLE: Option<c_int> = Some(c::TCP_KEEPIDLE);
+        const TCP_KEEPINTVL: Option<c_int> = Some(c::TCP_KEEPINTVL);
+        const TCP_KEEPCNT: Option<c_int> = Some(c::TCP_KEEPCNT);
+    } else {
+        const TCP_KEEPIDLE: Option<c_int> = None;
+        const TCP_KEEPINTVL: Option<c_int> = None;
+        const TCP_KEEPCNT: Option<c_int> = None;
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // sockaddr and misc bindings
 ////////////////////////////////////////////////////////////////////////////////
@@ -122,6 +172,60 @@ pub fn sockaddr_to_addr(storage: &c::sockaddr_storage, len: usize) -> io::Result
     }
 }
 
+fn unsupported_option() -> io::Error {
+    io::const_io_error!(ErrorKind::Unsupported, "socket option not supported on this platform")
+}
+
+fn set_bool_option(
+    sock: &Socket,
+    level: c_int,
+    option_name: Option<c_int>,
+    value: bool,
+) -> io::Result<()> {
+    let option_name = option_name.ok_or_else(unsupported_option)?;
+    setsockopt(sock, level, option_name, value as c_int)
+}
+
+fn bool_option(sock: &Socket, level: c_int, option_name: Option<c_int>) -> io::Result<bool> {
+    let option_name = option_name.ok_or_else(unsupported_option)?;
+    let raw: c_int = getsockopt(sock, level, option_name)?;
+    Ok(raw != 0)
+}
+
+fn set_int_option(
+    sock: &Socket,
+    level: c_int,
+    option_name: Option<c_int>,
+    value: u64,
+) -> io::Result<()> {
+    let option_name = option_name.ok_or_else(unsupported_option)?;
+    let value: c_int = value.try_into().unwrap_or(c_int::MAX);
+    setsockopt(sock, level, option_name, value)
+}
+
+fn int_option(sock: &Socket, level: c_int, option_name: Option<c_int>) -> io::Result<u64> {
+    let option_name = option_name.ok_or_else(unsupported_option)?;
+    let raw: c_int = getsockopt(sock, level, option_name)?;
+    Ok(raw as u64)
+}
+
+/// Sets an option that holds a number of seconds, rounding `dur` up.
+fn set_secs_option(
+    sock: &Socket,
+    level: c_int,
+    option_name: Option<c_int>,
+    dur: Duration,
+) -> io::Result<()> {
+    if dur.is_zero() {
+        return Err(io::const_io_error!(
+            ErrorKind::InvalidInput,
+            "cannot set a socket option to a 0 duration",
+        ));
+    }
+    let secs = dur.as_secs().saturating_add((dur.subsec_nanos() != 0) as u64);
+    set_int_option(sock, level, option_name, secs)
+}
+
 #[cfg(target_os = "android")]
 fn to_ipv6mr_interface(value: u32) -> c_int {
     value as c_int
@@ -342,6 +446,54 @@ pub fn ttl(&self) -> io::Result<u32> {
         Ok(raw as u32)
     }
 
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        set_bool_option(&self.inner, c::SOL_SOCKET, SO_KEEPALIVE, keepalive)
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        bool_option(&self.inner, c::SOL_SOCKET, SO_KEEPALIVE)
+    }
+
+    pub fn set_keepalive_idle(&self, idle: Duration) -> io::Result<()> {
+        set_secs_option(&self.inner, c::IPPROTO_TCP, TCP_KEEPIDLE, idle)
+    }
+
+    pub fn keepalive_idle(&self) -> io::Result<Duration> {
+        int_option(&self.inner, c::IPPROTO_TCP, TCP_KEEPIDLE).map(Duration::from_secs)
+    }
+
+    pub fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()> {
+        set_secs_option(&self.inner, c::IPPROTO_TCP, TCP_KEEPINTVL, interval)
+    }
+
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        int_option(&self.inner, c::IPPROTO_TCP, TCP_KEEPINTVL).map(Duration::from_secs)
+    }
+
+    pub fn set_keepalive_retries(&self, retries: u32) -> io::Result<()> {
+        set_int_option(&self.inner, c::IPPROTO_TCP, TCP_KEEPCNT, retries.into())
+    }
+
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        Ok(int_option(&self.inner, c::IPPROTO_TCP, TCP_KEEPCNT)? as u32)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_int_option(&self.inner, c::SOL_SOCKET, SO_SNDBUF, size as u64)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        Ok(int_option(&self.inner, c::SOL_SOCKET, SO_SNDBUF)? as usize)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_int_option(&self.inner, c::SOL_SOCKET, SO_RCVBUF, size as u64)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        Ok(int_option(&self.inner, c::SOL_SOCKET, SO_RCVBUF)? as usize)
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
@@ -499,6 +651,123 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Unbound sockets
+////////////////////////////////////////////////////////////////////////////////
+
+/// A socket that is configured before being bound, and then becomes a TCP
+/// listener or stream, or a UDP socket.
+pub struct UnboundSocket {
+    inner: Socket,
+}
+
+impl UnboundSocket {
+    pub fn new_tcp(v6: bool) -> io::Result<UnboundSocket> {
+        UnboundSocket::new(v6, c::SOCK_STREAM)
+    }
+
+    pub fn new_udp(v6: bool) -> io::Result<UnboundSocket> {
+        UnboundSocket::new(v6, c::SOCK_DGRAM)
+    }
+
+    fn new(v6: bool, ty: c_int) -> io::Result<UnboundSocket> {
+        init();
+
+        // The address is only used to choose the family of the socket.
+        let addr = if v6 {
+            SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0))
+        } else {
+            SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0))
+        };
+        let sock = Socket::new(&addr, ty)?;
+        Ok(UnboundSocket { inner: sock })
+    }
+
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR, reuseaddr as c_int)
+    }
+
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        set_bool_option(&self.inner, c::SOL_SOCKET, SO_REUSEPORT, reuseport)
+    }
+
+    pub fn reuseport(&self) -> io::Result<bool> {
+        bool_option(&self.inner, c::SOL_SOCKET, SO_REUSEPORT)
+    }
+
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_V6ONLY, only_v6 as c_int)
+    }
+
+    pub fn only_v6(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_V6ONLY)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_int_option(&self.inner, c::SOL_SOCKET, SO_SNDBUF, size as u64)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        Ok(int_option(&self.inner, c::SOL_SOCKET, SO_SNDBUF)? as usize)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_int_option(&self.inner, c::SOL_SOCKET, SO_RCVBUF, size as u64)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        Ok(int_option(&self.inner, c::SOL_SOCKET, SO_RCVBUF)? as usize)
+    }
+
+    pub fn bind(&self, addr: &SocketAddr) -> io::Result<()> {
+        let (addr, len) = addr.into_inner();
+        cvt(unsafe { c::bind(self.inner.as_raw(), addr.as_ptr(), len as _) })?;
+        Ok(())
+    }
+
+    pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+        sockname(|buf, len| unsafe { c::getsockname(self.inner.as_raw(), buf, len) })
+    }
+
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        let backlog = backlog.try_into().unwrap_or(c_int::MAX);
+        cvt(unsafe { c::listen(self.inner.as_raw(), backlog) })?;
+        Ok(TcpListener { inner: self.inner })
+    }
+
+    pub fn connect(self, addr: &SocketAddr) -> io::Result<TcpStream> {
+        self.inner.connect(addr)?;
+        Ok(TcpStream { inner: self.inner })
+    }
+
+    pub fn connect_timeout(self, addr: &SocketAddr, timeout: Duration) -> io::Result<TcpStream> {
+        self.inner.connect_timeout(addr, timeout)?;
+        Ok(TcpStream { inner: self.inner })
+    }
+
+    pub fn into_udp(self) -> UdpSocket {
+        UdpSocket { inner: self.inner }
+    }
+
+    /// Formats the socket for the `Debug` implementation of `name`.
+    pub fn fmt_named(&self, name: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut res = f.debug_struct(name);
+
+        if let Ok(addr) = self.socket_addr() {
+            res.field("addr", &addr);
+        }
+
+        let name = if cfg!(windows) { "socket" } else { "fd" };
+        res.field(name, &self.inner.as_raw()).finish()
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // UDP
 ////////////////////////////////////////////////////////////////////////////////
@@ -544,6 +813,48 @@ pub fn peek_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
         self.inner.peek_from(buf)
     }
 
+    pub fn recv_vectored_from(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, SocketAddr)> {
+        cfg_if::cfg_if! {
+            if #[cfg(any(
+                windows,
+                all(unix, not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))
+            ))] {
+                self.inner.recv_vectored_from(bufs)
+            } else {
+                // Without `recvmsg`, the datagram is received into a single
+                // buffer, then scattered into `bufs`.
+                let mut buf = vec![0; bufs.iter().map(|buf| buf.len()).sum()];
+                let (n, addr) = self.inner.recv_from(&mut buf)?;
+                let mut remaining = &buf[..n];
+                for dst in bufs {
+                    let len = cmp::min(dst.len(), remaining.len());
+                    dst[..len].copy_from_slice(&remaining[..len]);
+                    remaining = &remaining[len..];
+                }
+                Ok((n, addr))
+            }
+        }
+    }
+
+    pub fn send_vectored_to(&self, bufs: &[IoSlice<'_>], dst: &SocketAddr) -> io::Result<usize> {
+        cfg_if::cfg_if! {
+            if #[cfg(any(
+                windows,
+                all(unix, not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))
+            ))] {
+                self.inner.send_vectored_to(bufs, dst)
+            } else {
+                // Without `sendmsg`, the buffers are gathered into a single
+                // datagram.
+                let buf: Vec<u8> = bufs.iter().flat_map(|buf| buf.iter().copied()).collect();
+                self.send_to(&buf, dst)
+            }
+        }
+    }
+
     pub fn send_to(&self, buf: &[u8], dst: &SocketAddr) -> io::Result<usize> {
         let len = cmp::min(buf.len(), <wrlen_t>::MAX as usize) as wrlen_t;
         let (dst, dstlen) = dst.into_inner();
@@ -186,6 +186,12 @@
 #[stable(feature = "scoped_threads", since = "1.63.0")]
 pub use scoped::{scope, Scope, ScopedJoinHandle};
 
+#[unstable(feature = "thread_pool", issue = "none")]
+mod pool;
+
+#[unstable(feature = "thread_pool", issue = "none")]
+pub use pool::{PoolScope, ThreadPool};
+
 ////////////////////////////////////////////////////////////////////////////////
 // Thread-local storage
 ////////////////////////////////////////////////////////////////////////////////
@@ -265,6 +271,19 @@ pub struct Builder {
     name: Option<String>,
     // The size of the stack for the spawned thread in bytes
     stack_size: Option<usize>,
+    // Platform-specific settings, e.g. from `os::linux::thread::BuilderExt`
+    spawn_options: crate::sys::thread_spawn::SpawnOptions,
+}
+
+/// Allows extension traits within `std`.
+#[unstable(feature = "sealed", issue = "none")]
+impl crate::sealed::Sealed for Builder {}
+
+impl crate::sys_common::AsInnerMut<crate::sys::thread_spawn::SpawnOptions> for Builder {
+    #[inline]
+    fn as_inner_mut(&mut self) -> &mut crate::sys::thread_spawn::SpawnOptions {
+        &mut self.spawn_options
+    }
 }
 
 impl Builder {
@@ -288,7 +307,7 @@ impl Builder {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn new() -> Builder {
-        Builder { name: None, stack_size: None }
+        Builder { name: None, stack_size: None, spawn_options: Default::default() }
     }
 
     /// Names the thread-to-be. Currently the name is used for identification
@@ -468,7 +487,7 @@ unsafe fn spawn_unchecked_<'a, 'scope, F, T>(
         T: Send + 'a,
         'scope: 'a,
     {
-        let Builder { name, stack_size } = self;
+        let Builder { name, stack_size, spawn_options } = self;
 
         let stack_size = stack_size.unwrap_or_else(thread::min_stack);
 
@@ -550,24 +569,22 @@ fn drop(&mut self) {
         // lifetime change is justified.
         let main = unsafe { Box::from_raw(Box::into_raw(main) as *mut (dyn FnOnce() + 'static)) };
 
-        Ok(JoinInner {
-            // SAFETY:
-            //
-            // `imp::Thread::new` takes a closure with a `'static` lifetime, since it's passed
-            // through FFI or otherwise used with low-level threading primitives that have no
-            // notion of or way to enforce lifetimes.
-            //
-            // As mentioned in the `Safety` section of this function's documentation, the caller of
-            // this function needs to guarantee that the passed-in lifetime is sufficiently long
-            // for the lifetime of the thread.
-            //
-            // Similarly, the `sys` implementation must guarantee that no references to the closure
-            // exist after the thread has terminated, which is signaled by `Thread::join`
-            // returning.
-            native: unsafe { imp::Thread::new(stack_size, main)? },
-            thread: my_thread,
-            packet: my_packet,
-        })
+        // SAFETY:
+        //
+        // `imp::Thread::new` takes a closure with a `'static` lifetime, since it's passed
+        // through FFI or otherwise used with low-level threading primitives that have no
+        // notion of or way to enforce lifetimes.
+        //
+        // As mentioned in the `Safety` section of this function's documentation, the caller of
+        // this function needs to guarantee that the passed-in lifetime is sufficiently long
+        // for the lifetime of the thread.
+        //
+        // Similarly, the `sys` implementation must guarantee that no references to the closure
+        // exist after the thread has terminated, which is signaled by `Thread::join`
+        // returning.
+        let native = unsafe { crate::sys::thread_spawn::spawn(stack_size, main, spawn_options)? };
+
+        Ok(JoinInner { native, thread: my_thread, packet: my_packet })
     }
 }
 
@@ -0,0 +1,315 @@
+use super::scoped::ScopeData;
+use super::{available_parallelism, current, park, Builder, JoinHandle};
+use crate::fmt;
+use crate::io;
+use crate::marker::PhantomData;
+use crate::mem;
+use crate::num::NonZeroUsize;
+use crate::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use crate::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use crate::sync::mpmc::{self, Receiver, Sender};
+use crate::sync::Arc;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// A fixed-size pool of worker threads that run jobs submitted to it.
+///
+/// Jobs are closures submitted with [`execute`], or with [`PoolScope::spawn`]
+/// inside a [`scope`], in which case they may borrow non-`'static` data.
+/// Each job runs exactly once, on whichever worker thread is idle first.
+///
+/// If a job panics, the panic is reported like any other panic, and the
+/// worker thread carries on with the next job.
+///
+/// When the pool is dropped, the worker threads finish running all the jobs
+/// that were already submitted, and the drop blocks until they have exited.
+///
+/// [`execute`]: ThreadPool::execute
+/// [`scope`]: ThreadPool::scope
+///
+/// # Examples
+///
+/// ```
+/// #![feature(thread_pool)]
+/// use std::thread::ThreadPool;
+///
+/// let pool = ThreadPool::new().expect("failed to create thread pool");
+///
+/// let mut squares = vec![0; 16];
+/// pool.scope(|s| {
+///     for (i, square) in squares.iter_mut().enumerate() {
+///         s.spawn(move || *square = i * i);
+///     }
+/// });
+///
+/// assert_eq!(squares[15], 225);
+/// ```
+#[unstable(feature = "thread_pool", issue = "none")]
+pub struct ThreadPool {
+    sender: Option<Sender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl ThreadPool {
+    /// Creates a thread pool with one worker thread per unit of
+    /// [`available_parallelism`], or a single worker thread if that
+    /// can't be determined.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS fails to create a worker thread. The worker
+    /// threads that were already created are joined before returning.
+    #[unstable(feature = "thread_pool", issue = "none")]
+    pub fn new() -> io::Result<ThreadPool> {
+        ThreadPool::with_threads(available_parallelism().unwrap_or(NonZeroUsize::MIN))
+    }
+
+    /// Creates a thread pool with the given number of worker threads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS fails to create a worker thread. The worker
+    /// threads that were already created are joined before returning.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(thread_pool)]
+    /// use std::num::NonZeroUsize;
+    /// use std::thread::ThreadPool;
+    ///
+    /// let pool = ThreadPool::with_threads(NonZeroUsize::new(2).unwrap()).unwrap();
+    /// assert_eq!(pool.num_threads(), 2);
+    /// ```
+    #[unstable(feature = "thread_pool", issue = "none")]
+    pub fn with_threads(num_threads: NonZeroUsize) -> io::Result<ThreadPool> {
+        let (sender, receiver) = mpmc::channel::<Job>();
+        let mut pool =
+            ThreadPool { sender: Some(sender), workers: Vec::with_capacity(num_threads.get()) };
+        for _ in 0..num_threads.get() {
+            let receiver = receiver.clone();
+            // On error, dropping `pool` joins the workers spawned so far.
+            let worker = Builder::new().spawn(move || {
+                while let Ok(job) = receiver.recv() {
+                    run(job);
+                }
+            })?;
+            pool.workers.push(worker);
+        }
+        Ok(pool)
+    }
+
+    /// Returns the number of worker threads of the pool.
+    #[unstable(feature = "thread_pool", issue = "none")]
+    pub fn num_threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Submits a job to the pool, to be run on one of its worker threads.
+    ///
+    /// This function doesn't wait for the job to run. Use [`scope`] to wait
+    /// for jobs to finish, or to submit jobs borrowing non-`'static` data.
+    ///
+    /// [`scope`]: ThreadPool::scope
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(thread_pool)]
+    /// use std::sync::mpsc::channel;
+    /// use std::thread::ThreadPool;
+    ///
+    /// let pool = ThreadPool::new().unwrap();
+    /// let (tx, rx) = channel();
+    /// for i in 0..4 {
+    ///     let tx = tx.clone();
+    ///     pool.execute(move || tx.send(i).unwrap());
+    /// }
+    /// drop(tx);
+    ///
+    /// assert_eq!(rx.iter().sum::<i32>(), 6);
+    /// ```
+    #[unstable(feature = "thread_pool", issue = "none")]
+    pub fn execute<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.submit(Box::new(f));
+    }
+
+    /// Creates a scope for submitting jobs that may borrow non-`'static`
+    /// data to the pool.
+    ///
+    /// The function passed to `scope` will be provided a [`PoolScope`],
+    /// through which jobs can be [submitted][PoolScope::spawn]. All of these
+    /// jobs will have finished running before this function returns. While
+    /// waiting for them, the calling thread helps running the jobs of this
+    /// scope that are still queued, which also makes it possible to use
+    /// `scope` from a job. It never runs other jobs of the pool.
+    ///
+    /// # Panics
+    ///
+    /// If any of the jobs submitted through the scope panicked, this function
+    /// will panic after all of them have finished.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(thread_pool)]
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::thread::ThreadPool;
+    ///
+    /// let pool = ThreadPool::new().unwrap();
+    /// let words = ["apple", "banana", "cherry"];
+    /// let letters = AtomicUsize::new(0);
+    ///
+    /// pool.scope(|s| {
+    ///     for word in &words {
+    ///         s.spawn(|| {
+    ///             letters.fetch_add(word.len(), Ordering::Relaxed);
+    ///         });
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(letters.into_inner(), 17);
+    /// ```
+    #[track_caller]
+    #[unstable(feature = "thread_pool", issue = "none")]
+    pub fn scope<'env, F, T>(&self, f: F) -> T
+    where
+        F: for<'scope> FnOnce(&'scope PoolScope<'scope, 'env>) -> T,
+    {
+        let (sender, receiver) = mpmc::channel();
+        let scope = PoolScope {
+            pool: self,
+            sender,
+            receiver,
+            data: Arc::new(ScopeData {
+                num_running_threads: AtomicUsize::new(0),
+                main_thread: current(),
+                a_thread_panicked: AtomicBool::new(false),
+            }),
+            env: PhantomData,
+            scope: PhantomData,
+        };
+
+        // Run `f`, but catch panics so we can make sure to wait for all the jobs to finish.
+        let result = catch_unwind(AssertUnwindSafe(|| f(&scope)));
+
+        // Wait until all the jobs are finished, running the queued ones of this
+        // scope in the meantime.
+        while scope.data.num_running_threads.load(Ordering::Acquire) != 0 {
+            match scope.receiver.try_recv() {
+                Ok(job) => run(job),
+                Err(_) => park(),
+            }
+        }
+
+        // Throw any panic from `f`, or the return value of `f` if no job panicked.
+        match result {
+            Err(e) => resume_unwind(e),
+            Ok(_) if scope.data.a_thread_panicked.load(Ordering::Relaxed) => {
+                panic!("a scoped job panicked")
+            }
+            Ok(result) => result,
+        }
+    }
+
+    fn submit(&self, job: Job) {
+        // The workers only exit once the sender is dropped, so the channel
+        // can't be disconnected.
+        self.sender.as_ref().unwrap().send(job).expect("thread pool channel disconnected");
+    }
+}
+
+#[unstable(feature = "thread_pool", issue = "none")]
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        // Disconnect the channel, so that workers exit once it's empty.
+        drop(self.sender.take());
+        let current = current().id();
+        for worker in self.workers.drain(..) {
+            // The last reference to a shared pool may be dropped by one of its own jobs.
+            if worker.thread().id() != current {
+                let _ = worker.join();
+            }
+        }
+    }
+}
+
+#[unstable(feature = "thread_pool", issue = "none")]
+impl fmt::Debug for ThreadPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThreadPool")
+            .field("num_threads", &self.num_threads())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A scope to submit jobs borrowing non-`'static` data to a [`ThreadPool`].
+///
+/// See [`ThreadPool::scope`] for details.
+#[unstable(feature = "thread_pool", issue = "none")]
+pub struct PoolScope<'scope, 'env: 'scope> {
+    pool: &'scope ThreadPool,
+    /// The jobs of the scope that haven't started yet. Each of them is also
+    /// given a job of the pool that runs the next one of the queue, unless
+    /// the thread waiting for the scope did so first.
+    sender: Sender<Job>,
+    receiver: Receiver<Job>,
+    data: Arc<ScopeData>,
+    /// Invariance over 'scope, to make sure 'scope cannot shrink,
+    /// which is necessary for soundness. See [`Scope`](super::Scope).
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'scope, 'env> PoolScope<'scope, 'env> {
+    /// Submits a job to the pool, that may borrow non-`'static` data from
+    /// outside the scope.
+    ///
+    /// The job is guaranteed to have finished running by the end of the
+    /// scope. If it panics, [`ThreadPool::scope`] will panic after all jobs
+    /// are finished.
+    #[unstable(feature = "thread_pool", issue = "none")]
+    pub fn spawn<F>(&'scope self, f: F)
+    where
+        F: FnOnce() + Send + 'scope,
+    {
+        self.data.increment_num_running_threads();
+        let data = self.data.clone();
+        let job: Box<dyn FnOnce() + Send + 'scope> = Box::new(move || {
+            let result = catch_unwind(AssertUnwindSafe(f));
+            data.decrement_num_running_threads(result.is_err());
+        });
+        // SAFETY: `ThreadPool::scope` doesn't return before the job has run,
+        // which is signaled after everything borrowed by `f` was dropped.
+        // The pool can't be dropped in the meantime, since the scope borrows it.
+        // The queue may outlive the scope, but it is empty by then.
+        let job: Job = unsafe { mem::transmute(job) };
+        // Both ends of the queue are held by the scope, so this can't fail.
+        self.sender.send(job).unwrap();
+        let receiver = self.receiver.clone();
+        self.pool.submit(Box::new(move || {
+            if let Ok(job) = receiver.try_recv() {
+                run(job);
+            }
+        }));
+    }
+}
+
+#[unstable(feature = "thread_pool", issue = "none")]
+impl fmt::Debug for PoolScope<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoolScope")
+            .field("num_running_jobs", &self.data.num_running_threads.load(Ordering::Relaxed))
+            .field("a_job_panicked", &self.data.a_thread_panicked.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+fn run(job: Job) {
+    // The panic has already been reported by the panic hook, and scoped jobs
+    // record it themselves, so the worker can carry on.
+    let _ = catch_unwind(AssertUnwindSafe(job));
+}
@@ -36,9 +36,9 @@ pub struct Scope<'scope, 'env: 'scope> {
 pub struct ScopedJoinHandle<'scope, T>(JoinInner<'scope, T>);
 
 pub(super) struct ScopeData {
-    num_running_threads: AtomicUsize,
-    a_thread_panicked: AtomicBool,
-    main_thread: Thread,
+    pub(super) num_running_threads: AtomicUsize,
+    pub(super) a_thread_panicked: AtomicBool,
+    pub(super) main_thread: Thread,
 }
 
 impl ScopeData {
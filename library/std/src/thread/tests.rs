@@ -1,14 +1,15 @@
 use super::Builder;
 use crate::any::Any;
 use crate::mem;
+use crate::num::NonZeroUsize;
 use crate::panic::panic_any;
 use crate::result;
 use crate::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicUsize, Ordering},
     mpsc::{channel, Sender},
     Arc, Barrier,
 };
-use crate::thread::{self, Scope, ThreadId};
+use crate::thread::{self, Scope, ThreadId, ThreadPool};
 use crate::time::Duration;
 use crate::time::Instant;
 
@@ -404,3 +405,121 @@ fn scope_join_race() {
         });
     }
 }
+
+#[test]
+fn test_thread_pool_execute() {
+    let pool = ThreadPool::with_threads(NonZeroUsize::new(3).unwrap()).unwrap();
+    assert_eq!(pool.num_threads(), 3);
+
+    let (tx, rx) = channel();
+    for i in 0..10 {
+        let tx = tx.clone();
+        pool.execute(move || tx.send(i).unwrap());
+    }
+    drop(tx);
+    assert_eq!(rx.iter().sum::<i32>(), 45);
+}
+
+#[test]
+fn test_thread_pool_drop_runs_pending_jobs() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let pool = ThreadPool::with_threads(NonZeroUsize::new(2).unwrap()).unwrap();
+    for _ in 0..20 {
+        let counter = counter.clone();
+        pool.execute(move || {
+            thread::sleep(Duration::from_millis(1));
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+    }
+    drop(pool);
+    assert_eq!(counter.load(Ordering::Relaxed), 20);
+}
+
+#[test]
+fn test_thread_pool_scope_borrows() {
+    let pool = ThreadPool::with_threads(NonZeroUsize::new(4).unwrap()).unwrap();
+    let mut values = vec![0; 100];
+    pool.scope(|s| {
+        for (i, value) in values.iter_mut().enumerate() {
+            s.spawn(move || *value = i * 2);
+        }
+    });
+    assert!(values.iter().enumerate().all(|(i, &value)| value == i * 2));
+}
+
+#[test]
+fn test_thread_pool_nested_scope() {
+    // A single worker must not deadlock when a job waits for a nested scope.
+    let pool = ThreadPool::with_threads(NonZeroUsize::new(1).unwrap()).unwrap();
+    let counter = AtomicUsize::new(0);
+    pool.scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                pool.scope(|s| {
+                    for _ in 0..4 {
+                        s.spawn(|| {
+                            counter.fetch_add(1, Ordering::Relaxed);
+                        });
+                    }
+                });
+            });
+        }
+    });
+    assert_eq!(counter.into_inner(), 16);
+}
+
+#[test]
+fn test_thread_pool_scope_runs_only_its_jobs() {
+    let pool = ThreadPool::with_threads(NonZeroUsize::new(1).unwrap()).unwrap();
+    let (unblock_tx, unblock_rx) = channel::<()>();
+    pool.execute(move || unblock_rx.recv().unwrap());
+    let (tx, rx) = channel();
+    pool.execute(move || tx.send(thread::current().id()).unwrap());
+
+    // The worker is busy, so this thread runs the job of the scope, but it
+    // must leave the other queued job to the worker.
+    let mut ran_on = None;
+    pool.scope(|s| s.spawn(|| ran_on = Some(thread::current().id())));
+    assert_eq!(ran_on, Some(thread::current().id()));
+
+    unblock_tx.send(()).unwrap();
+    assert_ne!(rx.recv().unwrap(), thread::current().id());
+}
+
+#[test]
+fn test_thread_pool_scope_panic() {
+    let pool = ThreadPool::with_threads(NonZeroUsize::new(2).unwrap()).unwrap();
+    let finished = AtomicUsize::new(0);
+    let result = crate::panic::catch_unwind(crate::panic::AssertUnwindSafe(|| {
+        pool.scope(|s| {
+            s.spawn(|| panic!("job panicked"));
+            for _ in 0..4 {
+                s.spawn(|| {
+                    finished.fetch_add(1, Ordering::Relaxed);
+                });
+            }
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(finished.into_inner(), 4);
+
+    // The pool keeps working after a job panicked.
+    let ran = AtomicBool::new(false);
+    pool.scope(|s| s.spawn(|| ran.store(true, Ordering::Relaxed)));
+    assert!(ran.into_inner());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_builder_affinity() {
+    use crate::os::linux::thread::{affinity, BuilderExt, CpuSet};
+
+    let first = affinity().unwrap().iter().next().unwrap();
+    let cpus: CpuSet = [first].into_iter().collect();
+    let handle = Builder::new().affinity(cpus).spawn(|| affinity().unwrap()).unwrap();
+    assert_eq!(handle.join().unwrap(), cpus);
+
+    // An empty mask is rejected, without running the thread's main function.
+    let result = Builder::new().affinity(CpuSet::new()).spawn(|| unreachable!());
+    assert_eq!(result.unwrap_err().kind(), crate::io::ErrorKind::InvalidInput);
+}
@@ -0,0 +1,165 @@
+//! Saving the results of benchmarks, and comparing them with the results of a
+//! previous run (the baseline).
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::bench::BenchSamples;
+use super::stats::Summary;
+
+/// First line of a baseline file, used to detect files in another format.
+const HEADER: &str = "libtest-bench-baseline 1";
+
+/// Benchmark results of a previous run, by benchmark name.
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline(BTreeMap<String, BenchSamples>);
+
+impl Baseline {
+    pub fn new() -> Baseline {
+        Baseline(BTreeMap::new())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn insert(&mut self, name: &str, bs: BenchSamples) {
+        self.0.insert(name.to_owned(), bs);
+    }
+
+    /// Reads a baseline saved by [`Baseline::save`].
+    pub fn load(path: &Path) -> io::Result<Baseline> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+        let contents = fs::read_to_string(path)?;
+        let mut lines = contents.lines();
+        if lines.next() != Some(HEADER) {
+            return Err(invalid("unknown format".into()));
+        }
+
+        let mut baseline = Baseline::new();
+        for line in lines {
+            // The name comes first, as it's the only field that may contain spaces.
+            let mut fields = line.rsplit(' ');
+            let mut next = || -> io::Result<f64> {
+                let field = fields.next().unwrap_or_default();
+                field.parse().map_err(|_| invalid(format!("malformed line `{line}`")))
+            };
+            let mb_s = next()? as usize;
+            let iqr = next()?;
+            let q3 = next()?;
+            let q2 = next()?;
+            let q1 = next()?;
+            let median_abs_dev_pct = next()?;
+            let median_abs_dev = next()?;
+            let std_dev_pct = next()?;
+            let std_dev = next()?;
+            let var = next()?;
+            let median = next()?;
+            let mean = next()?;
+            let max = next()?;
+            let min = next()?;
+            let sum = next()?;
+            let name = fields.rev().collect::<Vec<_>>().join(" ");
+            if name.is_empty() {
+                return Err(invalid(format!("malformed line `{line}`")));
+            }
+
+            let ns_iter_summ = Summary {
+                sum,
+                min,
+                max,
+                mean,
+                median,
+                var,
+                std_dev,
+                std_dev_pct,
+                median_abs_dev,
+                median_abs_dev_pct,
+                quartiles: (q1, q2, q3),
+                iqr,
+            };
+            baseline.insert(&name, BenchSamples { ns_iter_summ, mb_s });
+        }
+
+        Ok(baseline)
+    }
+
+    /// Writes the baseline to the provided path, so it can be read by
+    /// [`Baseline::load`] in a later run.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut contents = format!("{HEADER}\n");
+        for (name, bs) in &self.0 {
+            let Summary {
+                sum,
+                min,
+                max,
+                mean,
+                median,
+                var,
+                std_dev,
+                std_dev_pct,
+                median_abs_dev,
+                median_abs_dev_pct,
+                quartiles: (q1, q2, q3),
+                iqr,
+            } = bs.ns_iter_summ;
+            contents.push_str(&format!(
+                "{name} {sum} {min} {max} {mean} {median} {var} {std_dev} {std_dev_pct} \
+                 {median_abs_dev} {median_abs_dev_pct} {q1} {q2} {q3} {iqr} {}\n",
+                bs.mb_s
+            ));
+        }
+        fs::write(path, contents)
+    }
+
+    /// Compares the results of a benchmark with its results in the baseline.
+    /// Returns `None` if the benchmark isn't part of the baseline.
+    pub fn compare(&self, name: &str, bs: &BenchSamples) -> Option<BenchComparison> {
+        let old = &self.0.get(name)?.ns_iter_summ;
+        let new = &bs.ns_iter_summ;
+
+        // The medians are considered to differ if they are further apart than
+        // the noise of both runs, as measured by their median absolute deviation.
+        let diff = new.median - old.median;
+        let significant = diff.abs() > old.median_abs_dev + new.median_abs_dev;
+        let change = if old.median > 0.0 { diff / old.median } else { 0.0 };
+
+        Some(BenchComparison { baseline_median: old.median, change, significant })
+    }
+}
+
+/// Result of comparing a benchmark with its results in the baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchComparison {
+    /// Median time per iteration in the baseline, in nanoseconds.
+    pub baseline_median: f64,
+    /// Relative change of the median time per iteration; positive if the
+    /// benchmark got slower.
+    pub change: f64,
+    /// Whether the change is larger than the noise of the measurements.
+    pub significant: bool,
+}
+
+impl BenchComparison {
+    pub fn is_regression(&self) -> bool {
+        self.significant && self.change > 0.0
+    }
+
+    pub fn is_improvement(&self) -> bool {
+        self.significant && self.change < 0.0
+    }
+}
+
+pub fn fmt_bench_comparison(comparison: &BenchComparison) -> String {
+    let verdict = if comparison.is_regression() {
+        "regressed"
+    } else if comparison.is_improvement() {
+        "improved"
+    } else {
+        "no change"
+    };
+    format!("{:+.2}% vs baseline, {verdict}", comparison.change * 100.0)
+}
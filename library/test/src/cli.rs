@@ -2,9 +2,10 @@
 
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use super::options::{ColorConfig, Options, OutputFormat, RunIgnored};
-use super::time::TestTimeOptions;
+use super::options::{ColorConfig, Options, OutputFormat, RunIgnored, TestShard};
+use super::time::{TestTimeOptions, TestTimeout};
 use std::io::{self, IsTerminal};
 
 #[derive(Debug)]
@@ -13,19 +14,30 @@ pub struct TestOpts {
     pub filters: Vec<String>,
     pub filter_exact: bool,
     pub force_run_in_process: bool,
+    /// Run each test in a separate process, regardless of the panic strategy.
+    pub isolate: bool,
     pub exclude_should_panic: bool,
     pub run_ignored: RunIgnored,
     pub run_tests: bool,
     pub bench_benchmarks: bool,
     pub logfile: Option<PathBuf>,
+    /// File to save the results of benchmarks to.
+    pub save_baseline: Option<PathBuf>,
+    /// File with the results of a previous run to compare benchmarks with.
+    pub baseline: Option<PathBuf>,
     pub nocapture: bool,
     pub color: ColorConfig,
     pub format: OutputFormat,
     pub shuffle: bool,
     pub shuffle_seed: Option<u64>,
+    pub shard: Option<TestShard>,
     pub test_threads: Option<usize>,
+    /// Number of times a failed test is run again before it's reported as failed.
+    pub retries: usize,
     pub skip: Vec<String>,
     pub time_options: Option<TestTimeOptions>,
+    /// Hard time limits after which running tests are reported as failed.
+    pub test_timeouts: Vec<TestTimeout>,
     /// Stop at first failing test.
     /// May run a few more tests due to threading, but will
     /// abort as soon as possible.
@@ -53,12 +65,21 @@ fn optgroups() -> getopts::Options {
     opts.optflag("", "include-ignored", "Run ignored and not ignored tests")
         .optflag("", "ignored", "Run only ignored tests")
         .optflag("", "force-run-in-process", "Forces tests to run in-process when panic=abort")
+        .optflag("", "isolate", "Run each test in a separate process")
         .optflag("", "exclude-should-panic", "Excludes tests marked as should_panic")
         .optflag("", "test", "Run tests and not benchmarks")
         .optflag("", "bench", "Run benchmarks instead of tests")
         .optflag("", "list", "List all tests and benchmarks")
         .optflag("h", "help", "Display this message")
         .optopt("", "logfile", "Write logs to the specified file", "PATH")
+        .optopt("", "save-baseline", "Save the results of benchmarks to the specified file", "PATH")
+        .optopt(
+            "",
+            "baseline",
+            "Compare the results of benchmarks with the ones saved to the
+            specified file by --save-baseline",
+            "PATH",
+        )
         .optflag(
             "",
             "nocapture",
@@ -72,6 +93,22 @@ fn optgroups() -> getopts::Options {
              in parallel",
             "n_threads",
         )
+        .optopt(
+            "",
+            "retries",
+            "Run failed tests again up to N times, and report them as flaky \
+             if one of the attempts succeeds",
+            "N",
+        )
+        .optmulti(
+            "",
+            "test-timeout",
+            "Fail tests that run longer than SECS seconds, killing them if they
+            run in a separate process. With FILTER, only applies to tests whose
+            names contain FILTER (this flag can be used multiple times, the
+            last matching one wins)",
+            "[FILTER=]SECS",
+        )
         .optmulti(
             "",
             "skip",
@@ -148,6 +185,20 @@ fn optgroups() -> getopts::Options {
             "shuffle-seed",
             "Run tests in random order; seed the random number generator with SEED",
             "SEED",
+        )
+        .optopt(
+            "",
+            "shard-index",
+            "Only run the tests of the shard with the given zero-based index;
+            requires --shard-count",
+            "INDEX",
+        )
+        .optopt(
+            "",
+            "shard-count",
+            "Split the tests into COUNT shards and only run the tests of the
+            shard selected by --shard-index",
+            "COUNT",
         );
     opts
 }
@@ -171,6 +222,21 @@ fn usage(binary: &str, options: &getopts::Options) {
 tests in the same order again. Note that --shuffle and --shuffle-seed do not
 affect whether the tests are run in parallel.
 
+A test run can be split across several machines with --shard-count and
+--shard-index. After filtering, the tests are sorted by name and distributed
+round-robin among the shards, so that every test is run by exactly one shard
+and the shards are of the same size (give or take one test).
+
+A test that runs for longer than the limit given with --test-timeout is
+reported as failed. If the test runs in a separate process, the process is
+killed; otherwise the test is left running in the background while the other
+tests are run.
+
+With --isolate, each test is run in a separate process, as with panic=abort, so
+that tests can't affect each other through global state, environment variables
+or by exiting the process. This also captures output written directly to the
+standard output and standard error file descriptors.
+
 All tests have their standard output and standard error captured by default.
 This can be overridden with the --nocapture flag or setting RUST_TEST_NOCAPTURE
 environment variable to a value other than "0". Logging is not captured by default.
@@ -257,10 +323,20 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
 
     // Unstable flags
     let force_run_in_process = unstable_optflag!(matches, allow_unstable, "force-run-in-process");
+    let isolate = unstable_optflag!(matches, allow_unstable, "isolate");
+    if isolate && force_run_in_process {
+        return Err("the options --isolate and --force-run-in-process are incompatible".into());
+    }
     let exclude_should_panic = unstable_optflag!(matches, allow_unstable, "exclude-should-panic");
     let time_options = get_time_options(&matches, allow_unstable)?;
     let shuffle = get_shuffle(&matches, allow_unstable)?;
     let shuffle_seed = get_shuffle_seed(&matches, allow_unstable)?;
+    let shard = get_shard(&matches, allow_unstable)?;
+    let retries = get_retries(&matches, allow_unstable)?;
+    let test_timeouts = get_test_timeouts(&matches, allow_unstable)?;
+    let save_baseline =
+        unstable_optopt!(matches, allow_unstable, "save-baseline").map(PathBuf::from);
+    let baseline = unstable_optopt!(matches, allow_unstable, "baseline").map(PathBuf::from);
 
     let include_ignored = matches.opt_present("include-ignored");
     let quiet = matches.opt_present("quiet");
@@ -286,19 +362,25 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
         filters,
         filter_exact: exact,
         force_run_in_process,
+        isolate,
         exclude_should_panic,
         run_ignored,
         run_tests,
         bench_benchmarks,
         logfile,
+        save_baseline,
+        baseline,
         nocapture,
         color,
         format,
         shuffle,
         shuffle_seed,
+        shard,
         test_threads,
+        retries,
         skip,
         time_options,
+        test_timeouts,
         options,
         fail_fast: false,
     };
@@ -375,6 +457,37 @@ fn get_shuffle_seed(matches: &getopts::Matches, allow_unstable: bool) -> OptPart
     Ok(shuffle_seed)
 }
 
+fn get_shard(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Option<TestShard>> {
+    let parse = |name: &str, value: Option<String>| -> OptPartRes<Option<usize>> {
+        match value {
+            Some(n_str) => match n_str.parse::<usize>() {
+                Ok(n) => Ok(Some(n)),
+                Err(e) => Err(format!("argument for --{name} must be a number (error: {e})")),
+            },
+            None => Ok(None),
+        }
+    };
+    let index = parse("shard-index", unstable_optopt!(matches, allow_unstable, "shard-index"))?;
+    let count = parse("shard-count", unstable_optopt!(matches, allow_unstable, "shard-count"))?;
+
+    let shard = match (index, count) {
+        (None, None) => None,
+        (Some(_), None) | (None, Some(_)) => {
+            return Err("the options --shard-index and --shard-count must be used together".into());
+        }
+        (Some(_), Some(0)) => return Err("argument for --shard-count must not be 0".into()),
+        (Some(index), Some(count)) if index >= count => {
+            return Err(format!(
+                "argument for --shard-index must be less than --shard-count ({count}), \
+                 but was {index}"
+            ));
+        }
+        (Some(index), Some(count)) => Some(TestShard { index, count }),
+    };
+
+    Ok(shard)
+}
+
 fn get_test_threads(matches: &getopts::Matches) -> OptPartRes<Option<usize>> {
     let test_threads = match matches.opt_str("test-threads") {
         Some(n_str) => match n_str.parse::<usize>() {
@@ -393,6 +506,50 @@ fn get_test_threads(matches: &getopts::Matches) -> OptPartRes<Option<usize>> {
     Ok(test_threads)
 }
 
+fn get_retries(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<usize> {
+    let retries = match unstable_optopt!(matches, allow_unstable, "retries") {
+        Some(n_str) => match n_str.parse::<usize>() {
+            Ok(n) => n,
+            Err(e) => {
+                return Err(format!("argument for --retries must be a number (error: {e})"));
+            }
+        },
+        None => 0,
+    };
+
+    Ok(retries)
+}
+
+fn get_test_timeouts(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Vec<TestTimeout>> {
+    let values = matches.opt_strs("test-timeout");
+    if !allow_unstable && !values.is_empty() {
+        return Err("The \"test-timeout\" option is only accepted on the nightly compiler with \
+                    -Z unstable-options"
+            .into());
+    }
+
+    values
+        .iter()
+        .map(|value| {
+            let (filter, secs) = match value.rsplit_once('=') {
+                Some((filter, secs)) => (Some(filter.to_string()), secs),
+                None => (None, value.as_str()),
+            };
+            match secs.parse::<u64>() {
+                Ok(0) => Err("argument for --test-timeout must not be 0".to_string()),
+                Ok(secs) => Ok(TestTimeout { filter, duration: Duration::from_secs(secs) }),
+                Err(e) => Err(format!(
+                    "argument for --test-timeout must be a number of seconds, \
+                     optionally preceded by `FILTER=` (error: {e})"
+                )),
+            }
+        })
+        .collect()
+}
+
 fn get_format(
     matches: &getopts::Matches,
     quiet: bool,
@@ -6,11 +6,14 @@
 use std::time::Instant;
 
 use super::{
-    bench::fmt_bench_samples,
+    baseline::{Baseline, BenchComparison},
+    bench::{fmt_bench_samples, BenchSamples},
     cli::TestOpts,
     event::{CompletedTest, TestEvent},
     filter_tests,
-    formatters::{JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TerseFormatter},
+    formatters::{
+        fmt_flaky, JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TerseFormatter,
+    },
     helpers::{concurrency::get_concurrency, metrics::MetricMap},
     options::{Options, OutputFormat},
     run_tests, term,
@@ -87,14 +90,22 @@ pub struct ConsoleTestState {
     pub passed: usize,
     pub failed: usize,
     pub ignored: usize,
+    /// Number of tests that passed after being retried; these are not
+    /// counted as passed.
+    pub flaky: usize,
     pub filtered_out: usize,
     pub measured: usize,
     pub exec_time: Option<TestSuiteExecTime>,
     pub metrics: MetricMap,
+    /// Results of a previous run to compare benchmarks with.
+    pub baseline: Option<Baseline>,
+    /// Results of the benchmarks run so far.
+    pub bench_results: Baseline,
     pub failures: Vec<(TestDesc, Vec<u8>)>,
     pub not_failures: Vec<(TestDesc, Vec<u8>)>,
     pub ignores: Vec<(TestDesc, Vec<u8>)>,
     pub time_failures: Vec<(TestDesc, Vec<u8>)>,
+    pub flaky_tests: Vec<(TestDesc, Vec<u8>)>,
     pub options: Options,
 }
 
@@ -104,6 +115,15 @@ pub fn new(opts: &TestOpts) -> io::Result<ConsoleTestState> {
             Some(ref path) => Some(File::create(path)?),
             None => None,
         };
+        let baseline = match opts.baseline {
+            Some(ref path) => Some(Baseline::load(path).map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!("failed to read benchmark baseline {}: {e}", path.display()),
+                )
+            })?),
+            None => None,
+        };
 
         Ok(ConsoleTestState {
             log_out,
@@ -111,14 +131,18 @@ pub fn new(opts: &TestOpts) -> io::Result<ConsoleTestState> {
             passed: 0,
             failed: 0,
             ignored: 0,
+            flaky: 0,
             filtered_out: 0,
             measured: 0,
             exec_time: None,
             metrics: MetricMap::new(),
+            baseline,
+            bench_results: Baseline::new(),
             failures: Vec::new(),
             not_failures: Vec::new(),
             ignores: Vec::new(),
             time_failures: Vec::new(),
+            flaky_tests: Vec::new(),
             options: opts.options,
         })
     }
@@ -161,6 +185,7 @@ pub fn write_log_result(
                     }
                     TestResult::TrBench(ref bs) => fmt_bench_samples(bs),
                     TestResult::TrTimedFail => "failed (time limit exceeded)".to_owned(),
+                    TestResult::TrFlaky(retries) => format!("ok ({})", fmt_flaky(retries)),
                 },
                 name,
             )
@@ -171,8 +196,17 @@ pub fn write_log_result(
         self.write_log(|| "\n")
     }
 
+    /// Compares the results of a benchmark with the baseline, if any.
+    pub fn compare_to_baseline(
+        &self,
+        test: &TestDesc,
+        bs: &BenchSamples,
+    ) -> Option<BenchComparison> {
+        self.baseline.as_ref()?.compare(test.name.as_slice(), bs)
+    }
+
     fn current_test_count(&self) -> usize {
-        self.passed + self.failed + self.ignored + self.measured
+        self.passed + self.failed + self.ignored + self.measured + self.flaky
     }
 }
 
@@ -237,6 +271,7 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
                 bs.ns_iter_summ.median,
                 bs.ns_iter_summ.max - bs.ns_iter_summ.min,
             );
+            st.bench_results.insert(test.name.as_slice(), bs);
             st.measured += 1
         }
         TestResult::TrFailed => {
@@ -253,6 +288,10 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
             st.failed += 1;
             st.time_failures.push((test, stdout));
         }
+        TestResult::TrFlaky(_) => {
+            st.flaky += 1;
+            st.flaky_tests.push((test, stdout));
+        }
     }
 }
 
@@ -331,6 +370,14 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
 
     assert!(opts.fail_fast || st.current_test_count() == st.total);
 
+    // Without any benchmark results, e.g. when the benchmarks are filtered
+    // out, the previous baseline is kept.
+    if let Some(ref path) = opts.save_baseline {
+        if !st.bench_results.is_empty() {
+            st.bench_results.save(path)?;
+        }
+    }
+
     out.write_run_finish(&st)
 }
 
@@ -1,13 +1,26 @@
-use std::{borrow::Cow, io, io::prelude::Write};
+use std::{
+    borrow::Cow,
+    io,
+    io::prelude::Write,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use super::OutputFormatter;
 use crate::{
     console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation},
+    options::ShouldPanic,
+    stats::Summary,
     test_result::TestResult,
     time,
-    types::TestDesc,
+    types::{TestDesc, TestType},
 };
 
+/// Version of the event stream emitted by the JSON formatter.
+///
+/// Adding new fields to an event does not change the version; removing a field, or changing
+/// its meaning, does.
+const FORMAT_VERSION: u32 = 1;
+
 pub(crate) struct JsonFormatter<T> {
     out: OutputLocation<T>,
 }
@@ -49,10 +62,11 @@ fn write_event(
         };
         let extra_json =
             if let Some(extra) = extra { format!(r#", {extra}"#) } else { String::from("") };
+        let timestamp = timestamp();
         let newline = "\n";
 
         self.writeln_message(&format!(
-                r#"{{ "type": "{ty}", "name": "{name}", "event": "{event}"{exec_time_json}{stdout_json}{extra_json} }}{newline}"#))
+                r#"{{ "type": "{ty}", "name": "{name}", "event": "{event}", "timestamp": {timestamp}{exec_time_json}{stdout_json}{extra_json} }}{newline}"#))
     }
 }
 
@@ -71,16 +85,31 @@ fn write_test_discovered(&mut self, desc: &TestDesc, test_type: &str) -> io::Res
             start_col,
             end_line,
             end_col,
-            ..
+            should_panic,
+            compile_fail,
+            no_run,
+            test_type: kind,
         } = desc;
 
         let name = EscapedString(name.as_slice());
-        let ignore_message = ignore_message.unwrap_or("");
+        let ignore_message = EscapedString(ignore_message.unwrap_or(""));
         let source_path = EscapedString(source_file);
+        let (should_panic, should_panic_message) = match should_panic {
+            ShouldPanic::No => (false, ""),
+            ShouldPanic::Yes => (true, ""),
+            ShouldPanic::YesWithMessage(message) => (true, *message),
+        };
+        let should_panic_message = EscapedString(should_panic_message);
+        let kind = match kind {
+            TestType::UnitTest => "unit",
+            TestType::IntegrationTest => "integration",
+            TestType::DocTest => "doctest",
+            TestType::Unknown => "unknown",
+        };
         let newline = "\n";
 
         self.writeln_message(&format!(
-            r#"{{ "type": "{test_type}", "event": "discovered", "name": "{name}", "ignore": {ignore}, "ignore_message": "{ignore_message}", "source_path": "{source_path}", "start_line": {start_line}, "start_col": {start_col}, "end_line": {end_line}, "end_col": {end_col} }}{newline}"#
+            r#"{{ "type": "{test_type}", "event": "discovered", "name": "{name}", "ignore": {ignore}, "ignore_message": "{ignore_message}", "source_path": "{source_path}", "start_line": {start_line}, "start_col": {start_col}, "end_line": {end_line}, "end_col": {end_col}, "should_panic": {should_panic}, "should_panic_message": "{should_panic_message}", "compile_fail": {compile_fail}, "no_run": {no_run}, "kind": "{kind}" }}{newline}"#
         ))
     }
 
@@ -102,15 +131,16 @@ fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> i
         };
         let newline = "\n";
         self.writeln_message(&format!(
-            r#"{{ "type": "suite", "event": "started", "test_count": {test_count}{shuffle_seed_json} }}{newline}"#
+            r#"{{ "type": "suite", "event": "started", "format_version": {FORMAT_VERSION}, "test_count": {test_count}{shuffle_seed_json} }}{newline}"#
             ))
     }
 
     fn write_test_start(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = EscapedString(desc.name.as_slice());
+        let timestamp = timestamp();
         let newline = "\n";
         self.writeln_message(&format!(
-            r#"{{ "type": "test", "event": "started", "name": "{name}" }}{newline}"#
+            r#"{{ "type": "test", "event": "started", "name": "{name}", "timestamp": {timestamp} }}{newline}"#
         ))
     }
 
@@ -133,6 +163,15 @@ fn write_result(
                 self.write_event("test", desc.name.as_slice(), "ok", exec_time, stdout, None)
             }
 
+            TestResult::TrFlaky(retries) => self.write_event(
+                "test",
+                desc.name.as_slice(),
+                "ok",
+                exec_time,
+                stdout,
+                Some(&*format!(r#""flaky": true, "retries": {retries}"#)),
+            ),
+
             TestResult::TrFailed => {
                 self.write_event("test", desc.name.as_slice(), "failed", exec_time, stdout, None)
             }
@@ -176,12 +215,24 @@ fn write_result(
                     format!(r#", "mib_per_second": {}"#, bs.mb_s)
                 };
                 let name = EscapedString(desc.name.as_slice());
+                let summary = fmt_summary(&bs.ns_iter_summ);
+                let baseline = if let Some(comparison) = state.compare_to_baseline(desc, bs) {
+                    format!(
+                        r#", "baseline": {{ "median": {}, "change": {}, "significant": {} }}"#,
+                        JsonF64(comparison.baseline_median),
+                        JsonF64(comparison.change),
+                        comparison.significant
+                    )
+                } else {
+                    String::new()
+                };
 
                 self.writeln_message(&format!(
                     "{{ \"type\": \"bench\", \
                      \"name\": \"{name}\", \
                      \"median\": {median}, \
-                     \"deviation\": {deviation}{mbps} }}\n",
+                     \"deviation\": {deviation}{mbps}, \
+                     \"summary\": {summary}{baseline} }}\n",
                 ))
             }
         }
@@ -189,9 +240,10 @@ fn write_result(
 
     fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = EscapedString(desc.name.as_slice());
+        let timestamp = timestamp();
         let newline = "\n";
         self.writeln_message(&format!(
-            r#"{{ "type": "test", "event": "timeout", "name": "{name}" }}{newline}"#,
+            r#"{{ "type": "test", "event": "timeout", "name": "{name}", "timestamp": {timestamp} }}{newline}"#,
         ))
     }
 
@@ -202,6 +254,8 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         let ignored = state.ignored;
         let measured = state.measured;
         let filtered_out = state.filtered_out;
+        let flaky_json =
+            if state.flaky > 0 { format!(r#", "flaky": {}"#, state.flaky) } else { String::new() };
         let exec_time_json = if let Some(ref exec_time) = state.exec_time {
             format!(r#", "exec_time": {}"#, exec_time.0.as_secs_f64())
         } else {
@@ -210,13 +264,56 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         let newline = "\n";
 
         self.writeln_message(&format!(
-            r#"{{ "type": "suite", "event": "{event}", "passed": {passed}, "failed": {failed}, "ignored": {ignored}, "measured": {measured}, "filtered_out": {filtered_out}{exec_time_json} }}{newline}"#
+            r#"{{ "type": "suite", "event": "{event}", "passed": {passed}, "failed": {failed}, "ignored": {ignored}, "measured": {measured}, "filtered_out": {filtered_out}{flaky_json}{exec_time_json} }}{newline}"#
         ))?;
 
         Ok(state.failed == 0)
     }
 }
 
+/// Formats all the statistics of a benchmark as a JSON object.
+fn fmt_summary(summary: &Summary) -> String {
+    let Summary {
+        sum,
+        min,
+        max,
+        mean,
+        median,
+        var,
+        std_dev,
+        std_dev_pct,
+        median_abs_dev,
+        median_abs_dev_pct,
+        quartiles: (q1, q2, q3),
+        iqr,
+    } = *summary;
+    let [sum, min, max, mean, median, var, std_dev, std_dev_pct] =
+        [sum, min, max, mean, median, var, std_dev, std_dev_pct].map(JsonF64);
+    let [median_abs_dev, median_abs_dev_pct, q1, q2, q3, iqr] =
+        [median_abs_dev, median_abs_dev_pct, q1, q2, q3, iqr].map(JsonF64);
+    format!(
+        r#"{{ "sum": {sum}, "min": {min}, "max": {max}, "mean": {mean}, "median": {median}, "var": {var}, "std_dev": {std_dev}, "std_dev_pct": {std_dev_pct}, "median_abs_dev": {median_abs_dev}, "median_abs_dev_pct": {median_abs_dev_pct}, "quartiles": [{q1}, {q2}, {q3}], "iqr": {iqr} }}"#
+    )
+}
+
+/// A number formatted as JSON, which has no representation for NaN and infinities.
+struct JsonF64(f64);
+
+impl std::fmt::Display for JsonF64 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_finite() {
+            write!(f, "{}", self.0)
+        } else {
+            f.write_str("null")
+        }
+    }
+}
+
+/// Seconds since the Unix epoch, used to timestamp test events.
+fn timestamp() -> f64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0.0, |d| d.as_secs_f64())
+}
+
 /// A formatting utility used to print strings with characters in need of escaping.
 /// Base code taken form `libserialize::json::escape_str`
 struct EscapedString<S: AsRef<str>>(S);
@@ -155,6 +155,27 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
                     ))?;
                 }
 
+                TestResult::TrFlaky(retries) => {
+                    self.write_message(&format!(
+                        "<testcase classname=\"{}\" \
+                         name=\"{}\" time=\"{}\">",
+                        class_name,
+                        test_name,
+                        duration.as_secs_f64()
+                    ))?;
+                    // Surefire's schema reports each failed attempt of a test
+                    // that eventually passed as a `flakyFailure`.
+                    for _ in 0..retries {
+                        self.write_message("<flakyFailure type=\"assert\"/>")?;
+                    }
+                    if !stdout.is_empty() && state.options.display_output {
+                        self.write_message("<system-out>")?;
+                        self.write_message(&str_to_cdata(&String::from_utf8_lossy(&stdout)))?;
+                        self.write_message("</system-out>")?;
+                    }
+                    self.write_message("</testcase>")?;
+                }
+
                 TestResult::TrOk => {
                     self.write_message(&format!(
                         "<testcase classname=\"{}\" \
@@ -1,7 +1,9 @@
 use std::{io, io::prelude::Write};
 
 use crate::{
-    console::{ConsoleTestDiscoveryState, ConsoleTestState},
+    baseline::{fmt_bench_comparison, BenchComparison},
+    console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation},
+    term,
     test_result::TestResult,
     time,
     types::{TestDesc, TestName},
@@ -36,6 +38,41 @@ fn write_result(
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool>;
 }
 
+pub(crate) fn fmt_flaky(retries: usize) -> String {
+    match retries {
+        1 => "flaky, passed after 1 retry".to_string(),
+        n => format!("flaky, passed after {n} retries"),
+    }
+}
+
+/// Writes how a benchmark compares with its baseline, in red if it regressed
+/// and in green if it improved.
+pub(crate) fn write_bench_comparison<T: Write>(
+    out: &mut OutputLocation<T>,
+    use_color: bool,
+    comparison: &BenchComparison,
+) -> io::Result<()> {
+    let color = if comparison.is_regression() {
+        Some(term::color::RED)
+    } else if comparison.is_improvement() {
+        Some(term::color::GREEN)
+    } else {
+        None
+    };
+    let comparison_str = fmt_bench_comparison(comparison);
+    out.write_all(b" (")?;
+    match (&mut *out, color) {
+        (OutputLocation::Pretty(term), Some(color)) if use_color => {
+            term.fg(color)?;
+            term.write_all(comparison_str.as_bytes())?;
+            term.reset()?;
+        }
+        (out, _) => out.write_all(comparison_str.as_bytes())?,
+    }
+    out.write_all(b")")?;
+    out.flush()
+}
+
 pub(crate) fn write_stderr_delimiter(test_output: &mut Vec<u8>, test_name: &TestName) {
     match test_output.last() {
         Some(b'\n') => (),
@@ -1,6 +1,6 @@
 use std::{io, io::prelude::Write};
 
-use super::OutputFormatter;
+use super::{fmt_flaky, write_bench_comparison, OutputFormatter};
 use crate::{
     bench::fmt_bench_samples,
     console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation},
@@ -53,6 +53,10 @@ pub fn write_ignored(&mut self, message: Option<&'static str>) -> io::Result<()>
         }
     }
 
+    pub fn write_flaky(&mut self, retries: usize) -> io::Result<()> {
+        self.write_short_result(&format!("ok ({})", fmt_flaky(retries)), term::color::YELLOW)
+    }
+
     pub fn write_time_failed(&mut self) -> io::Result<()> {
         self.write_short_result("FAILED (time limit exceeded)", term::color::RED)
     }
@@ -168,6 +172,10 @@ pub fn write_time_failures(&mut self, state: &ConsoleTestState) -> io::Result<()
         self.write_results(&state.time_failures, "failures (time limit exceeded)")
     }
 
+    pub fn write_flaky_tests(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_results(&state.flaky_tests, "flaky")
+    }
+
     fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = desc.padded_name(self.max_name_len, desc.name.padding());
         if let Some(test_mode) = desc.test_mode() {
@@ -236,7 +244,7 @@ fn write_result(
         result: &TestResult,
         exec_time: Option<&time::TestExecTime>,
         _: &[u8],
-        _: &ConsoleTestState,
+        state: &ConsoleTestState,
     ) -> io::Result<()> {
         if self.is_multithreaded {
             self.write_test_name(desc)?;
@@ -249,8 +257,12 @@ fn write_result(
             TestResult::TrBench(ref bs) => {
                 self.write_bench()?;
                 self.write_plain(format!(": {}", fmt_bench_samples(bs)))?;
+                if let Some(comparison) = state.compare_to_baseline(desc, bs) {
+                    write_bench_comparison(&mut self.out, self.use_color, &comparison)?;
+                }
             }
             TestResult::TrTimedFail => self.write_time_failed()?,
+            TestResult::TrFlaky(retries) => self.write_flaky(retries)?,
         }
 
         self.write_time(desc, exec_time)?;
@@ -280,6 +292,10 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
             }
         }
 
+        if !state.flaky_tests.is_empty() {
+            self.write_flaky_tests(state)?;
+        }
+
         self.write_plain("\ntest result: ")?;
 
         if success {
@@ -296,6 +312,10 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
 
         self.write_plain(s)?;
 
+        if state.flaky > 0 {
+            self.write_plain(format!("; {} flaky", state.flaky))?;
+        }
+
         if let Some(ref exec_time) = state.exec_time {
             let time_str = format!("; finished in {exec_time}");
             self.write_plain(time_str)?;
@@ -28,11 +28,12 @@
 // Public reexports
 pub use self::bench::{black_box, Bencher};
 pub use self::console::run_tests_console;
-pub use self::options::{ColorConfig, Options, OutputFormat, RunIgnored, ShouldPanic};
+pub use self::options::{ColorConfig, Options, OutputFormat, RunIgnored, ShouldPanic, TestShard};
 pub use self::types::TestName::*;
 pub use self::types::*;
 pub use self::ColorConfig::*;
 pub use cli::TestOpts;
+pub use time::TestTimeout;
 
 // Module to be used by rustc to compile tests in libtest
 pub mod test {
@@ -42,10 +43,11 @@ pub mod test {
         cli::{parse_opts, TestOpts},
         filter_tests,
         helpers::metrics::{Metric, MetricMap},
-        options::{Options, RunIgnored, RunStrategy, ShouldPanic},
-        run_test, test_main, test_main_static,
+        options::{Options, RunIgnored, RunStrategy, ShouldPanic, TestShard},
+        register_setup, register_teardown, run_test, test_main, test_main_static,
+        test_main_static_with_options,
         test_result::{TestResult, TrFailed, TrFailedMsg, TrIgnored, TrOk},
-        time::{TestExecTime, TestTimeOptions},
+        time::{TestExecTime, TestTimeOptions, TestTimeout},
         types::{
             DynTestFn, DynTestName, StaticBenchFn, StaticTestFn, StaticTestName, TestDesc,
             TestDescAndFn, TestId, TestName, TestType,
@@ -66,6 +68,7 @@ pub mod test {
     time::{Duration, Instant},
 };
 
+mod baseline;
 pub mod bench;
 mod cli;
 mod console;
@@ -97,9 +100,42 @@ pub mod test {
 const SECONDARY_TEST_INVOKER_VAR: &str = "__RUST_TEST_INVOKE";
 const SECONDARY_TEST_BENCH_BENCHMARKS_VAR: &str = "__RUST_TEST_BENCH_BENCHMARKS";
 
+// The setup and teardown functions registered with `register_setup` and
+// `register_teardown`.
+static SETUP: Mutex<Option<fn()>> = Mutex::new(None);
+static TEARDOWN: Mutex<Option<fn()>> = Mutex::new(None);
+
+/// Registers a function to run once before any of the tests, as with
+/// [`Options::setup`], when the tests are run by [`test_main`] or one of the
+/// functions calling it, such as [`test_main_static`].
+///
+/// This is how the harness generated by `rustc --test` runs the function given
+/// with `#![test_setup(path)]`. The function given in the options, if any,
+/// takes precedence.
+pub fn register_setup(setup: fn()) {
+    *SETUP.lock().unwrap() = Some(setup);
+}
+
+/// Registers a function to run once after all the tests, as with
+/// [`Options::teardown`]. See [`register_setup`] for when it is run.
+///
+/// This is how the harness generated by `rustc --test` runs the function given
+/// with `#![test_teardown(path)]`.
+pub fn register_teardown(teardown: fn()) {
+    *TEARDOWN.lock().unwrap() = Some(teardown);
+}
+
 // The default console test runner. It accepts the command line
 // arguments and a vector of test_descs.
 pub fn test_main(args: &[String], tests: Vec<TestDescAndFn>, options: Option<Options>) {
+    // If we're being run in SpawnedSecondary mode, run the test here. run_test
+    // will then exit the process. This is checked here, rather than in the
+    // static entry points, so that custom test runners that call this function
+    // directly don't spawn themselves again and again.
+    if let Ok(name) = env::var(SECONDARY_TEST_INVOKER_VAR) {
+        run_spawned_secondary(tests, name);
+    }
+
     let mut opts = match cli::parse_opts(args) {
         Some(Ok(o)) => o,
         Some(Err(msg)) => {
@@ -111,6 +147,8 @@ pub fn test_main(args: &[String], tests: Vec<TestDescAndFn>, options: Option<Opt
     if let Some(options) = options {
         opts.options = options;
     }
+    opts.options.setup = opts.options.setup.or(*SETUP.lock().unwrap());
+    opts.options.teardown = opts.options.teardown.or(*TEARDOWN.lock().unwrap());
     if opts.list {
         if let Err(e) = console::list_tests_console(&opts, tests) {
             eprintln!("error: io error when listing tests: {e:?}");
@@ -162,6 +200,17 @@ pub fn test_main_static(tests: &[&TestDescAndFn]) {
     test_main(&args, owned_tests, None)
 }
 
+/// A variant of [`test_main_static`] that accepts [`Options`], for example to
+/// run setup and teardown functions before and after the tests. It can be
+/// used as a custom test runner with `#![test_runner]`.
+///
+/// In panic=abort mode, the options must enable [`Options::panic_abort`].
+pub fn test_main_static_with_options(tests: &[&TestDescAndFn], options: Options) {
+    let args = env::args().collect::<Vec<_>>();
+    let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
+    test_main(&args, owned_tests, Some(options))
+}
+
 /// A variant optimized for invocation with a static test vector.
 /// This will panic (intentionally) when fed any dynamic tests.
 ///
@@ -171,40 +220,39 @@ pub fn test_main_static(tests: &[&TestDescAndFn]) {
 /// This is the entry point for the main function generated by `rustc --test`
 /// when panic=abort.
 pub fn test_main_static_abort(tests: &[&TestDescAndFn]) {
-    // If we're being run in SpawnedSecondary mode, run the test here. run_test
-    // will then exit the process.
-    if let Ok(name) = env::var(SECONDARY_TEST_INVOKER_VAR) {
-        env::remove_var(SECONDARY_TEST_INVOKER_VAR);
+    let args = env::args().collect::<Vec<_>>();
+    let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
+    test_main(&args, owned_tests, Some(Options::new().panic_abort(true)))
+}
 
-        // Convert benchmarks to tests if we're not benchmarking.
-        let mut tests = tests.iter().map(make_owned_test).collect::<Vec<_>>();
-        if env::var(SECONDARY_TEST_BENCH_BENCHMARKS_VAR).is_ok() {
-            env::remove_var(SECONDARY_TEST_BENCH_BENCHMARKS_VAR);
-        } else {
-            tests = convert_benchmarks_to_tests(tests);
-        };
+/// Runs the test with the provided name in a process spawned by the primary
+/// test runner, and exits the process with the result.
+fn run_spawned_secondary(mut tests: Vec<TestDescAndFn>, name: String) -> ! {
+    env::remove_var(SECONDARY_TEST_INVOKER_VAR);
 
-        let test = tests
-            .into_iter()
-            .find(|test| test.desc.name.as_slice() == name)
-            .unwrap_or_else(|| panic!("couldn't find a test with the provided name '{name}'"));
-        let TestDescAndFn { desc, testfn } = test;
-        match testfn.into_runnable() {
-            Runnable::Test(runnable_test) => {
-                if runnable_test.is_dynamic() {
-                    panic!("only static tests are supported");
-                }
-                run_test_in_spawned_subprocess(desc, runnable_test);
-            }
-            Runnable::Bench(_) => {
-                panic!("benchmarks should not be executed into child processes")
+    // Convert benchmarks to tests if we're not benchmarking.
+    if env::var(SECONDARY_TEST_BENCH_BENCHMARKS_VAR).is_ok() {
+        env::remove_var(SECONDARY_TEST_BENCH_BENCHMARKS_VAR);
+    } else {
+        tests = convert_benchmarks_to_tests(tests);
+    };
+
+    let test = tests
+        .into_iter()
+        .find(|test| test.desc.name.as_slice() == name)
+        .unwrap_or_else(|| panic!("couldn't find a test with the provided name '{name}'"));
+    let TestDescAndFn { desc, testfn } = test;
+    match testfn.into_runnable() {
+        Runnable::Test(runnable_test) => {
+            if runnable_test.is_dynamic() {
+                panic!("only static tests are supported");
             }
+            run_test_in_spawned_subprocess(desc, runnable_test);
+        }
+        Runnable::Bench(_) => {
+            panic!("benchmarks should not be executed into child processes")
         }
     }
-
-    let args = env::args().collect::<Vec<_>>();
-    let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
-    test_main(&args, owned_tests, Some(Options::new().panic_abort(true)))
 }
 
 /// Clones static values for putting into a dynamic vector, which test_main()
@@ -264,9 +312,9 @@ pub fn run_tests<F>(
 where
     F: FnMut(TestEvent) -> io::Result<()>,
 {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
     use std::hash::{BuildHasherDefault, DefaultHasher};
-    use std::sync::mpsc::RecvTimeoutError;
+    use std::sync::mpsc::{Receiver, RecvTimeoutError};
 
     struct RunningTest {
         join_handle: Option<thread::JoinHandle<()>>,
@@ -294,6 +342,36 @@ struct TimeoutEntry {
         timeout: Instant,
     }
 
+    // A test that can be run again if it fails, along with the output of its
+    // failed attempts.
+    struct RetryEntry {
+        desc: TestDesc,
+        testfn: TestFn,
+        failed_attempts: usize,
+        stdout: Vec<u8>,
+    }
+
+    type RetryMap = HashMap<TestId, RetryEntry, BuildHasherDefault<DefaultHasher>>;
+
+    // Tests that exceeded their hard timeout while running in-process. They
+    // can't be stopped, so they are left running and their results are ignored.
+    type AbandonedSet = HashSet<TestId, BuildHasherDefault<DefaultHasher>>;
+
+    // Runs the teardown function when dropped, so that it runs however the
+    // test run ends.
+    struct Teardown(fn());
+
+    impl Drop for Teardown {
+        fn drop(&mut self) {
+            (self.0)()
+        }
+    }
+
+    if let Some(setup) = opts.options.setup {
+        setup();
+    }
+    let _teardown = opts.options.teardown.map(Teardown);
+
     let tests_len = tests.len();
 
     let mut filtered = FilteredTests { tests: Vec::new(), benches: Vec::new(), next_id: 0 };
@@ -338,14 +416,127 @@ struct TimeoutEntry {
     let mut pending = 0;
 
     let (tx, rx) = channel::<CompletedTest>();
-    let run_strategy = if opts.options.panic_abort && !opts.force_run_in_process {
+    let run_strategy = if (opts.options.panic_abort || opts.isolate) && !opts.force_run_in_process {
         RunStrategy::SpawnPrimary
     } else {
         RunStrategy::InProcess
     };
 
+    // Dynamic tests can't be run in a separate process, so `--isolate` doesn't
+    // apply to them.
+    let get_run_strategy = |test: &TestDescAndFn| match test.testfn {
+        DynTestFn(_) | DynBenchAsTestFn(_) if !opts.options.panic_abort => RunStrategy::InProcess,
+        _ => run_strategy,
+    };
+
     let mut running_tests: TestMap = HashMap::default();
     let mut timeout_queue: VecDeque<TimeoutEntry> = VecDeque::new();
+    let mut retry_entries: RetryMap = HashMap::default();
+    let mut deadlines: Vec<TimeoutEntry> = Vec::new();
+    let mut abandoned: AbandonedSet = HashSet::default();
+
+    // Hard timeouts are only enforced here for tests that run in-process;
+    // tests that run in a separate process are killed by `spawn_test_subprocess`.
+    let get_deadline = |test: &TestDescAndFn| match get_run_strategy(test) {
+        RunStrategy::InProcess => time::get_test_timeout(&opts.test_timeouts, &test.desc)
+            .map(|timeout| Instant::now() + timeout),
+        RunStrategy::SpawnPrimary => None,
+    };
+
+    // Remembers how to run the test again if retries are enabled.
+    // Returns whether this is the first attempt to run the test.
+    fn prepare_attempt(
+        opts: &TestOpts,
+        retry_entries: &mut RetryMap,
+        id: TestId,
+        test: &TestDescAndFn,
+    ) -> bool {
+        if let Some(entry) = retry_entries.get(&id) {
+            return entry.failed_attempts == 0;
+        }
+        if opts.retries > 0 {
+            if let Some(testfn) = test.testfn.try_clone() {
+                let desc = test.desc.clone();
+                let entry = RetryEntry { desc, testfn, failed_attempts: 0, stdout: Vec::new() };
+                retry_entries.insert(id, entry);
+            }
+        }
+        true
+    }
+
+    // Returns the test to run again if it failed and has attempts left.
+    // Otherwise, marks the test as flaky if it passed after failing, and
+    // prepends the output of the failed attempts to its output.
+    fn check_retry(
+        opts: &TestOpts,
+        retry_entries: &mut RetryMap,
+        completed_test: &mut CompletedTest,
+        retryable: bool,
+    ) -> Option<(TestId, TestDescAndFn)> {
+        let id = completed_test.id;
+        let entry = retry_entries.get_mut(&id)?;
+        let failed = matches!(completed_test.result, TrFailed | TrFailedMsg(_) | TrTimedFail);
+        if failed && retryable && entry.failed_attempts < opts.retries {
+            entry.failed_attempts += 1;
+            let attempt = entry.failed_attempts;
+            writeln!(entry.stdout, "---- {} attempt {attempt} ----", entry.desc.name).unwrap();
+            entry.stdout.extend_from_slice(&completed_test.stdout);
+            if let TrFailedMsg(msg) = &completed_test.result {
+                writeln!(entry.stdout, "note: {msg}").unwrap();
+            }
+            let testfn = entry.testfn.try_clone().unwrap();
+            return Some((id, TestDescAndFn { desc: entry.desc.clone(), testfn }));
+        }
+
+        let entry = retry_entries.remove(&id).unwrap();
+        if entry.failed_attempts > 0 {
+            if completed_test.result == TrOk {
+                completed_test.result = TrFlaky(entry.failed_attempts);
+            }
+            let attempt = entry.failed_attempts + 1;
+            let mut stdout = entry.stdout;
+            writeln!(stdout, "---- {} attempt {attempt} ----", entry.desc.name).unwrap();
+            stdout.extend_from_slice(&completed_test.stdout);
+            completed_test.stdout = stdout;
+        }
+        None
+    }
+
+    // Waits for the next result, skipping the results of abandoned tests.
+    fn recv_result(
+        rx: &Receiver<CompletedTest>,
+        abandoned: &AbandonedSet,
+        deadline: Option<Instant>,
+    ) -> Result<CompletedTest, RecvTimeoutError> {
+        loop {
+            let res = match deadline {
+                Some(deadline) => {
+                    rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match res {
+                Ok(completed_test) if abandoned.contains(&completed_test.id) => {}
+                res => return res,
+            }
+        }
+    }
+
+    // Reports a test that exceeded its hard timeout while running in-process.
+    fn abandon_test(
+        opts: &TestOpts,
+        abandoned: &mut AbandonedSet,
+        id: TestId,
+        desc: TestDesc,
+    ) -> CompletedTest {
+        abandoned.insert(id);
+        let timeout = time::get_test_timeout(&opts.test_timeouts, &desc).unwrap_or_default();
+        let stdout = format!(
+            "test exceeded its timeout of {}s and was left running in the background",
+            timeout.as_secs()
+        );
+        CompletedTest::new(id, desc, TrTimedFail, None, stdout.into_bytes())
+    }
 
     fn get_timed_out_tests(
         running_tests: &TestMap,
@@ -375,15 +566,34 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
     if concurrency == 1 {
         while !remaining.is_empty() {
             let (id, test) = remaining.pop_front().unwrap();
-            let event = TestEvent::TeWait(test.desc.clone());
-            notify_about_test_event(event)?;
-            let join_handle = run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
+            if prepare_attempt(opts, &mut retry_entries, id, &test) {
+                let event = TestEvent::TeWait(test.desc.clone());
+                notify_about_test_event(event)?;
+            }
+            let deadline = get_deadline(&test);
+            let strategy = get_run_strategy(&test);
+            let desc = test.desc.clone();
+            let join_handle = run_test(opts, !opts.run_tests, id, test, strategy, tx.clone());
             // Wait for the test to complete.
-            let mut completed_test = rx.recv().unwrap();
-            RunningTest { join_handle }.join(&mut completed_test);
+            let mut completed_test = match recv_result(&rx, &abandoned, deadline) {
+                Ok(mut completed_test) => {
+                    RunningTest { join_handle }.join(&mut completed_test);
+                    completed_test
+                }
+                Err(RecvTimeoutError::Timeout) => abandon_test(opts, &mut abandoned, id, desc),
+                Err(RecvTimeoutError::Disconnected) => panic!("test result channel disconnected"),
+            };
+
+            let retryable = !abandoned.contains(&id);
+            if let Some(test) =
+                check_retry(opts, &mut retry_entries, &mut completed_test, retryable)
+            {
+                remaining.push_front(test);
+                continue;
+            }
 
             let fail_fast = match completed_test.result {
-                TrIgnored | TrOk | TrBench(_) => false,
+                TrIgnored | TrOk | TrFlaky(_) | TrBench(_) => false,
                 TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
             };
 
@@ -391,6 +601,10 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
             notify_about_test_event(event)?;
 
             if fail_fast {
+                if !abandoned.is_empty() {
+                    // Prevent abandoned test threads from panicking
+                    std::mem::forget(rx);
+                }
                 return Ok(());
             }
         }
@@ -401,51 +615,78 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
                 let timeout = time::get_default_test_timeout();
                 let desc = test.desc.clone();
 
-                let event = TestEvent::TeWait(desc.clone());
-                notify_about_test_event(event)?; //here no pad
-                let join_handle =
-                    run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
+                if prepare_attempt(opts, &mut retry_entries, id, &test) {
+                    let event = TestEvent::TeWait(desc.clone());
+                    notify_about_test_event(event)?; //here no pad
+                }
+                if let Some(deadline) = get_deadline(&test) {
+                    deadlines.push(TimeoutEntry { id, desc: desc.clone(), timeout: deadline });
+                }
+                let strategy = get_run_strategy(&test);
+                let join_handle = run_test(opts, !opts.run_tests, id, test, strategy, tx.clone());
                 running_tests.insert(id, RunningTest { join_handle });
                 timeout_queue.push_back(TimeoutEntry { id, desc, timeout });
                 pending += 1;
             }
 
-            let mut res;
+            let mut completed_test;
             loop {
-                if let Some(timeout) = calc_timeout(&timeout_queue) {
-                    res = rx.recv_timeout(timeout);
-                    for test in get_timed_out_tests(&running_tests, &mut timeout_queue) {
-                        let event = TestEvent::TeTimeout(test);
-                        notify_about_test_event(event)?;
-                    }
+                let next_timeout = calc_timeout(&timeout_queue).map(|t| Instant::now() + t);
+                let next_deadline = deadlines.iter().map(|entry| entry.timeout).min();
+                let wake_up = next_timeout.into_iter().chain(next_deadline).min();
+                let res = recv_result(&rx, &abandoned, wake_up);
+                for test in get_timed_out_tests(&running_tests, &mut timeout_queue) {
+                    let event = TestEvent::TeTimeout(test);
+                    notify_about_test_event(event)?;
+                }
 
-                    match res {
-                        Err(RecvTimeoutError::Timeout) => {
-                            // Result is not yet ready, continue waiting.
-                        }
-                        _ => {
-                            // We've got a result, stop the loop.
+                match res {
+                    Ok(res) => {
+                        // We've got a result, stop the loop.
+                        completed_test = res;
+                        break;
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        // Result is not yet ready; stop waiting for a test that
+                        // exceeded its hard timeout, or continue waiting.
+                        let now = Instant::now();
+                        if let Some(i) = deadlines.iter().position(|entry| entry.timeout <= now) {
+                            let TimeoutEntry { id, desc, .. } = deadlines.swap_remove(i);
+                            running_tests.remove(&id);
+                            completed_test = abandon_test(opts, &mut abandoned, id, desc);
                             break;
                         }
                     }
-                } else {
-                    res = rx.recv().map_err(|_| RecvTimeoutError::Disconnected);
-                    break;
+                    Err(RecvTimeoutError::Disconnected) => {
+                        panic!("test result channel disconnected")
+                    }
                 }
             }
 
-            let mut completed_test = res.unwrap();
-            let running_test = running_tests.remove(&completed_test.id).unwrap();
-            running_test.join(&mut completed_test);
+            let id = completed_test.id;
+            if let Some(running_test) = running_tests.remove(&id) {
+                running_test.join(&mut completed_test);
+            }
+            deadlines.retain(|entry| entry.id != id);
+            pending -= 1;
+
+            let retryable = !abandoned.contains(&id);
+            if let Some(test) =
+                check_retry(opts, &mut retry_entries, &mut completed_test, retryable)
+            {
+                // The timeout of the failed attempt doesn't apply to the next one.
+                timeout_queue.retain(|entry| entry.id != test.0);
+                remaining.push_front(test);
+                continue;
+            }
 
             let fail_fast = match completed_test.result {
-                TrIgnored | TrOk | TrBench(_) => false,
+                TrIgnored | TrOk | TrFlaky(_) | TrBench(_) => false,
                 TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
             };
 
             let event = TestEvent::TeResult(completed_test);
             notify_about_test_event(event)?;
-            pending -= 1;
 
             if fail_fast {
                 // Prevent remaining test threads from panicking
@@ -469,6 +710,11 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
             notify_about_test_event(event)?;
         }
     }
+
+    if !abandoned.is_empty() {
+        // Prevent abandoned test threads from panicking
+        std::mem::forget(rx);
+    }
     Ok(())
 }
 
@@ -510,6 +756,19 @@ pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescA
         RunIgnored::No => {}
     }
 
+    // Only keep the tests of the requested shard. The tests are assigned
+    // round-robin in name order, so the assignment doesn't depend on the order
+    // in which the tests were passed.
+    if let Some(TestShard { index, count }) = opts.shard {
+        filtered.sort_by(|a, b| a.desc.name.as_slice().cmp(b.desc.name.as_slice()));
+        filtered = filtered
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| i % count == index)
+            .map(|(_, test)| test)
+            .collect();
+    }
+
     filtered
 }
 
@@ -562,6 +821,7 @@ pub fn run_test(
             let nocapture = opts.nocapture;
             let time_options = opts.time_options;
             let bench_benchmarks = opts.bench_benchmarks;
+            let timeout = time::get_test_timeout(&opts.test_timeouts, &desc);
 
             let runtest = move || match strategy {
                 RunStrategy::InProcess => run_test_in_process(
@@ -581,6 +841,7 @@ pub fn run_test(
                     monitor_ch,
                     time_options,
                     bench_benchmarks,
+                    timeout,
                 ),
             };
 
@@ -679,6 +940,7 @@ fn spawn_test_subprocess(
     monitor_ch: Sender<CompletedTest>,
     time_opts: Option<time::TestTimeOptions>,
     bench_benchmarks: bool,
+    timeout: Option<Duration>,
 ) {
     let (result, test_output, exec_time) = (|| {
         let args = env::args().collect::<Vec<_>>();
@@ -695,7 +957,18 @@ fn spawn_test_subprocess(
         }
 
         let start = report_time.then(Instant::now);
-        let output = match command.output() {
+        let output = match timeout {
+            Some(timeout) => {
+                if !nocapture {
+                    command.stdout(process::Stdio::piped());
+                    command.stderr(process::Stdio::piped());
+                }
+                command.stdin(process::Stdio::null());
+                command.spawn().and_then(|child| wait_with_timeout(child, timeout))
+            }
+            None => command.output().map(|output| (output, false)),
+        };
+        let (output, timed_out) = match output {
             Ok(out) => out,
             Err(e) => {
                 let err = format!("Failed to spawn {} as child for test: {:?}", args[0], e);
@@ -712,6 +985,17 @@ fn spawn_test_subprocess(
         formatters::write_stderr_delimiter(&mut test_output, &desc.name);
         test_output.extend_from_slice(&stderr);
 
+        if timed_out {
+            let timeout = timeout.unwrap_or_default();
+            write!(
+                &mut test_output,
+                "test killed after exceeding its timeout of {}s",
+                timeout.as_secs()
+            )
+            .unwrap();
+            return (TrTimedFail, test_output, exec_time);
+        }
+
         let result = match (|| -> Result<TestResult, String> {
             let exit_code = get_exit_code(status)?;
             Ok(get_result_from_exit_code(&desc, exit_code, &time_opts, &exec_time))
@@ -730,6 +1014,88 @@ fn spawn_test_subprocess(
     monitor_ch.send(message).unwrap();
 }
 
+/// Waits for the child process to exit, killing it if it's still running after `timeout`.
+/// Returns the output of the process, and whether it was killed.
+fn wait_with_timeout(
+    mut child: process::Child,
+    timeout: Duration,
+) -> io::Result<(process::Output, bool)> {
+    // Reads the pipe on a separate thread, keeping what was read so far in a
+    // buffer, so that the output is available even if the thread never ends.
+    struct Reader {
+        buf: Arc<Mutex<Vec<u8>>>,
+        handle: thread::JoinHandle<()>,
+    }
+
+    impl Reader {
+        fn new(pipe: Option<impl io::Read + Send + 'static>) -> Reader {
+            let buf = Arc::new(Mutex::new(Vec::new()));
+            let thread_buf = buf.clone();
+            let handle = thread::spawn(move || {
+                let Some(mut pipe) = pipe else { return };
+                let mut chunk = [0; 8192];
+                loop {
+                    match pipe.read(&mut chunk) {
+                        Ok(0) => break,
+                        Ok(n) => thread_buf.lock().unwrap().extend_from_slice(&chunk[..n]),
+                        Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                        // Keep whatever was read before the process went away.
+                        Err(_) => break,
+                    }
+                }
+            });
+            Reader { buf, handle }
+        }
+
+        /// Waits for the pipe to be closed, for at most `grace` if given, and
+        /// returns what was read. The thread is left running if it doesn't
+        /// end in time, which happens when a process spawned by the test
+        /// still holds the pipe.
+        fn finish(self, grace: Option<Duration>) -> Vec<u8> {
+            match grace {
+                Some(grace) => {
+                    let deadline = Instant::now() + grace;
+                    while !self.handle.is_finished() && Instant::now() < deadline {
+                        thread::sleep(Duration::from_millis(10));
+                    }
+                }
+                None => {
+                    let _ = self.handle.join();
+                }
+            }
+            std::mem::take(&mut *self.buf.lock().unwrap())
+        }
+    }
+
+    let stdout = Reader::new(child.stdout.take());
+    let stderr = Reader::new(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let mut poll_interval = Duration::from_millis(1);
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            // The process may have exited in the meantime, which is fine.
+            let _ = child.kill();
+            timed_out = true;
+            break child.wait()?;
+        }
+        thread::sleep(poll_interval.min(deadline - now));
+        poll_interval = (poll_interval * 2).min(Duration::from_millis(100));
+    };
+
+    // After killing the test, its output is only waited for briefly, as the
+    // processes it spawned may still hold the pipes.
+    let grace = timed_out.then(|| Duration::from_secs(1));
+    let stdout = stdout.finish(grace);
+    let stderr = stderr.finish(grace);
+    Ok((process::Output { status, stdout, stderr }, timed_out))
+}
+
 fn run_test_in_spawned_subprocess(desc: TestDesc, runnable_test: RunnableTest) -> ! {
     let builtin_panic_hook = panic::take_hook();
     let record_result = Arc::new(move |panic_info: Option<&'_ PanicInfo<'_>>| {
@@ -47,6 +47,15 @@ pub enum RunIgnored {
     Only,
 }
 
+/// Subset of the tests to run, used to split a test run across several machines
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TestShard {
+    /// Zero-based index of the shard to run
+    pub index: usize,
+    /// Total number of shards
+    pub count: usize,
+}
+
 #[derive(Clone, Copy)]
 pub enum RunStrategy {
     /// Runs the test in the current process, and sends the result back over the
@@ -65,11 +74,26 @@ pub enum RunStrategy {
 pub struct Options {
     pub display_output: bool,
     pub panic_abort: bool,
+    /// Function run once before any of the tests. It isn't run again in the
+    /// processes spawned for tests that run in a separate process.
+    ///
+    /// Options can only be passed by a custom test runner, such as
+    /// [`test_main_static_with_options`](crate::test_main_static_with_options)
+    /// used with `#![test_runner]`. The harness of ordinary `#[test]` binaries
+    /// registers the function given with `#![test_setup(path)]` with
+    /// [`register_setup`](crate::register_setup) instead.
+    pub setup: Option<fn()>,
+    /// Function run once after all the tests, even if some of them failed.
+    ///
+    /// The harness of ordinary `#[test]` binaries registers the function given
+    /// with `#![test_teardown(path)]` with
+    /// [`register_teardown`](crate::register_teardown) instead.
+    pub teardown: Option<fn()>,
 }
 
 impl Options {
     pub fn new() -> Options {
-        Options { display_output: false, panic_abort: false }
+        Options { display_output: false, panic_abort: false, setup: None, teardown: None }
     }
 
     pub fn display_output(mut self, display_output: bool) -> Options {
@@ -81,4 +105,14 @@ pub fn panic_abort(mut self, panic_abort: bool) -> Options {
         self.panic_abort = panic_abort;
         self
     }
+
+    pub fn setup(mut self, setup: fn()) -> Options {
+        self.setup = Some(setup);
+        self
+    }
+
+    pub fn teardown(mut self, teardown: fn()) -> Options {
+        self.teardown = Some(teardown);
+        self
+    }
 }
@@ -21,6 +21,9 @@ pub enum TestResult {
     TrIgnored,
     TrBench(BenchSamples),
     TrTimedFail,
+    /// The test failed, but passed when it was run again. Holds the number of
+    /// failed attempts.
+    TrFlaky(usize),
 }
 
 /// Creates a `TestResult` depending on the raw result of test execution
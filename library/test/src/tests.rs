@@ -1,6 +1,8 @@
 use super::*;
 
 use crate::{
+    baseline::Baseline,
+    bench::BenchSamples,
     console::OutputLocation,
     formatters::PrettyFormatter,
     test::{
@@ -10,7 +12,7 @@
         // ShouldPanic, StaticTestName, TestDesc, TestDescAndFn, TestOpts, TestTimeOptions,
         // TestType, TrFailedMsg, TrIgnored, TrOk,
     },
-    time::{TestTimeOptions, TimeThreshold},
+    time::{TestTimeOptions, TestTimeout, TimeThreshold},
 };
 
 impl TestOpts {
@@ -20,19 +22,25 @@ fn new() -> TestOpts {
             filters: vec![],
             filter_exact: false,
             force_run_in_process: false,
+            isolate: false,
             exclude_should_panic: false,
             run_ignored: RunIgnored::No,
             run_tests: false,
             bench_benchmarks: false,
             logfile: None,
+            save_baseline: None,
+            baseline: None,
             nocapture: false,
             color: AutoColor,
             format: OutputFormat::Pretty,
             shuffle: false,
             shuffle_seed: None,
+            shard: None,
             test_threads: None,
+            retries: 0,
             skip: vec![],
             time_options: None,
+            test_timeouts: vec![],
             options: Options::new(),
             fail_fast: false,
         }
@@ -419,6 +427,10 @@ fn typed_test_desc(test_type: TestType) -> TestDesc {
     }
 }
 
+fn test_desc(name: &'static str) -> TestDesc {
+    TestDesc { name: StaticTestName(name), ..typed_test_desc(TestType::Unknown) }
+}
+
 fn test_exec_time(millis: u64) -> TestExecTime {
     TestExecTime(Duration::from_millis(millis))
 }
@@ -478,6 +490,73 @@ fn parse_include_ignored_flag() {
     assert_eq!(opts.run_ignored, RunIgnored::Yes);
 }
 
+#[test]
+fn parse_shard_options() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--shard-index".to_string(),
+        "1".to_string(),
+        "--shard-count".to_string(),
+        "3".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.shard, Some(TestShard { index: 1, count: 3 }));
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--shard-index".to_string(),
+        "3".to_string(),
+        "--shard-count".to_string(),
+        "3".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--shard-count".to_string(),
+        "3".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_test_timeout_options() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--test-timeout".to_string(),
+        "60".to_string(),
+        "--test-timeout".to_string(),
+        "slow::=600".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(
+        opts.test_timeouts,
+        [
+            TestTimeout { filter: None, duration: Duration::from_secs(60) },
+            TestTimeout { filter: Some("slow::".into()), duration: Duration::from_secs(600) },
+        ]
+    );
+
+    let timeout_of = |name| time::get_test_timeout(&opts.test_timeouts, &test_desc(name));
+    assert_eq!(timeout_of("fast::test"), Some(Duration::from_secs(60)));
+    assert_eq!(timeout_of("slow::test"), Some(Duration::from_secs(600)));
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--test-timeout".to_string(),
+        "0".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args = vec!["progname".to_string(), "--test-timeout".to_string(), "60".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
 #[test]
 pub fn filter_for_ignored_option() {
     // When we run ignored tests the test filter should filter out all the
@@ -666,6 +745,37 @@ fn testfn() -> Result<(), String> {
     tests
 }
 
+#[test]
+pub fn shard_tests() {
+    let count = 3;
+    let mut all = Vec::new();
+    for index in 0..count {
+        let opts = TestOpts { shard: Some(TestShard { index, count }), ..TestOpts::new() };
+        let shard: Vec<_> = filter_tests(&opts, sample_tests())
+            .into_iter()
+            .map(|t| t.desc.name.to_string())
+            .collect();
+        // 11 tests split into 3 shards.
+        assert!(shard.len() == 3 || shard.len() == 4);
+        all.extend(shard);
+    }
+
+    // Every test ends up in exactly one shard, regardless of the input order.
+    all.sort();
+    let mut expected: Vec<_> =
+        sample_tests().into_iter().map(|t| t.desc.name.to_string()).collect();
+    expected.sort();
+    assert_eq!(all, expected);
+
+    let opts = TestOpts { shard: Some(TestShard { index: 1, count }), ..TestOpts::new() };
+    let mut reversed = sample_tests();
+    reversed.reverse();
+    let names = |tests: Vec<TestDescAndFn>| {
+        tests.into_iter().map(|t| t.desc.name.to_string()).collect::<Vec<_>>()
+    };
+    assert_eq!(names(filter_tests(&opts, sample_tests())), names(filter_tests(&opts, reversed)));
+}
+
 #[test]
 pub fn shuffle_tests() {
     let mut opts = TestOpts::new();
@@ -870,11 +980,15 @@ fn should_sort_failures_before_printing_them() {
         measured: 0,
         exec_time: None,
         metrics: MetricMap::new(),
+        baseline: None,
+        bench_results: Baseline::new(),
         failures: vec![(test_b, Vec::new()), (test_a, Vec::new())],
         options: Options::new(),
         not_failures: Vec::new(),
         ignores: Vec::new(),
         time_failures: Vec::new(),
+        flaky: 0,
+        flaky_tests: Vec::new(),
     };
 
     out.write_failures(&st).unwrap();
@@ -922,3 +1036,151 @@ fn f(_: &mut Bencher) -> Result<(), String> {
     let result = rx.recv().unwrap().result;
     assert_eq!(result, TrFailed);
 }
+
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn test_retries() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static FLAKY_RUNS: AtomicUsize = AtomicUsize::new(0);
+    static FAILING_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    fn flaky() -> Result<(), String> {
+        if FLAKY_RUNS.fetch_add(1, Ordering::SeqCst) < 2 {
+            Err("flaky".into())
+        } else {
+            Ok(())
+        }
+    }
+    fn failing() -> Result<(), String> {
+        FAILING_RUNS.fetch_add(1, Ordering::SeqCst);
+        Err("failing".into())
+    }
+
+    let desc = |name, testfn| TestDescAndFn { desc: test_desc(name), testfn: StaticTestFn(testfn) };
+    let tests = vec![desc("flaky", flaky), desc("failing", failing)];
+
+    let (tx, rx) = channel();
+    let notify = move |event: TestEvent| {
+        if let TestEvent::TeResult(result) = event {
+            tx.send(result).unwrap();
+        }
+        Ok(())
+    };
+    let opts = TestOpts { run_tests: true, retries: 3, test_threads: Some(1), ..TestOpts::new() };
+    run_tests(&opts, tests, notify).unwrap();
+
+    let results: Vec<_> = rx.iter().map(|test| (test.desc.name.to_string(), test.result)).collect();
+    assert_eq!(results, [("flaky".to_string(), TrFlaky(2)), ("failing".to_string(), TrFailed)]);
+    assert_eq!(FLAKY_RUNS.load(Ordering::SeqCst), 3);
+    assert_eq!(FAILING_RUNS.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+fn test_timeouts_abandon_hung_tests() {
+    fn hung() -> Result<(), String> {
+        thread::sleep(Duration::from_secs(60));
+        Ok(())
+    }
+    fn quick() -> Result<(), String> {
+        Ok(())
+    }
+
+    let desc = |name, testfn| TestDescAndFn { desc: test_desc(name), testfn: StaticTestFn(testfn) };
+
+    for test_threads in [1, 2] {
+        let tests = vec![desc("hung", hung), desc("quick", quick)];
+
+        let (tx, rx) = channel();
+        let notify = move |event: TestEvent| {
+            if let TestEvent::TeResult(result) = event {
+                tx.send(result).unwrap();
+            }
+            Ok(())
+        };
+        let timeout =
+            TestTimeout { filter: Some("hung".into()), duration: Duration::from_millis(100) };
+        let opts = TestOpts {
+            run_tests: true,
+            test_threads: Some(test_threads),
+            test_timeouts: vec![timeout],
+            ..TestOpts::new()
+        };
+        run_tests(&opts, tests, notify).unwrap();
+
+        let mut results: Vec<_> =
+            rx.iter().map(|test| (test.desc.name.to_string(), test.result)).collect();
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(results, [("hung".to_string(), TrTimedFail), ("quick".to_string(), TrOk)]);
+    }
+}
+
+#[test]
+fn bench_baseline_round_trip_and_comparison() {
+    let samples = |median: f64, median_abs_dev: f64| BenchSamples {
+        ns_iter_summ: stats::Summary { median, median_abs_dev, ..stats::Summary::new(&[1.0]) },
+        mb_s: 0,
+    };
+
+    let mut baseline = Baseline::new();
+    baseline.insert("bench with spaces", samples(100.0, 2.0));
+    baseline.insert("other", BenchSamples { mb_s: 42, ..samples(0.5, 0.25) });
+
+    let path = std::env::temp_dir().join(format!("libtest-bench-baseline-{}", process::id()));
+    baseline.save(&path).unwrap();
+    let loaded = Baseline::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.unwrap(), baseline);
+
+    // Within the noise of both runs.
+    let comparison = baseline.compare("bench with spaces", &samples(103.0, 2.0)).unwrap();
+    assert!(!comparison.significant);
+    assert_eq!(comparison.change, 0.03);
+
+    let comparison = baseline.compare("bench with spaces", &samples(110.0, 2.0)).unwrap();
+    assert!(comparison.is_regression());
+
+    let comparison = baseline.compare("bench with spaces", &samples(90.0, 2.0)).unwrap();
+    assert!(comparison.is_improvement());
+
+    assert_eq!(baseline.compare("missing", &samples(100.0, 2.0)), None);
+}
+
+#[test]
+fn setup_and_teardown_run_around_tests() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // Records the order of the calls: 1 for setup, 2 for a test, 3 for teardown.
+    static CALLS: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+    static TEARDOWNS: AtomicUsize = AtomicUsize::new(0);
+
+    fn setup() {
+        CALLS.lock().unwrap().push(1);
+    }
+    fn teardown() {
+        CALLS.lock().unwrap().push(3);
+        TEARDOWNS.fetch_add(1, Ordering::SeqCst);
+    }
+    fn passing() -> Result<(), String> {
+        CALLS.lock().unwrap().push(2);
+        Ok(())
+    }
+    fn failing() -> Result<(), String> {
+        CALLS.lock().unwrap().push(2);
+        Err("failing".into())
+    }
+
+    let desc = |name, testfn| TestDescAndFn { desc: test_desc(name), testfn: StaticTestFn(testfn) };
+    let tests = vec![desc("passing", passing), desc("failing", failing)];
+
+    let opts = TestOpts {
+        run_tests: true,
+        test_threads: Some(1),
+        options: Options::new().setup(setup).teardown(teardown),
+        ..TestOpts::new()
+    };
+    run_tests(&opts, tests, |_| Ok(())).unwrap();
+
+    assert_eq!(*CALLS.lock().unwrap(), [1, 2, 2, 3]);
+    assert_eq!(TEARDOWNS.load(Ordering::SeqCst), 1);
+}
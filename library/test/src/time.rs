@@ -193,3 +193,26 @@ fn default_doctest() -> TimeThreshold {
         TimeThreshold::new(time_constants::DOCTEST_WARN, time_constants::DOCTEST_CRITICAL)
     }
 }
+
+/// A hard time limit for test execution, after which a test is reported as
+/// failed and, if it runs in a separate process, killed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestTimeout {
+    /// Only tests whose names contain this string are subject to the limit.
+    /// A timeout without a filter applies to all tests.
+    pub filter: Option<String>,
+    pub duration: Duration,
+}
+
+/// Returns the hard time limit for the provided test, if any.
+/// If several timeouts apply to the test, the last one wins.
+pub fn get_test_timeout(timeouts: &[TestTimeout], test: &TestDesc) -> Option<Duration> {
+    timeouts
+        .iter()
+        .rev()
+        .find(|timeout| match &timeout.filter {
+            Some(filter) => test.name.as_slice().contains(filter.as_str()),
+            None => true,
+        })
+        .map(|timeout| timeout.duration)
+}
@@ -531,18 +531,24 @@ pub fn test_opts(config: &Config) -> test::TestOpts {
         run_ignored: if config.run_ignored { test::RunIgnored::Yes } else { test::RunIgnored::No },
         format: config.format,
         logfile: config.logfile.clone(),
+        save_baseline: None,
+        baseline: None,
         run_tests: true,
         bench_benchmarks: true,
         nocapture: config.nocapture,
         color: config.color,
         shuffle: false,
         shuffle_seed: None,
+        shard: None,
         test_threads: None,
+        retries: 0,
         skip: config.skip.clone(),
         list: false,
         options: test::Options::new(),
         time_options: None,
+        test_timeouts: vec![],
         force_run_in_process: false,
+        isolate: false,
         fail_fast: std::env::var_os("RUSTC_TEST_FAIL_FAST").is_some(),
     }
 }
@@ -0,0 +1,7 @@
+// compile-flags: --test
+
+#![test_setup(setup)] //~ ERROR the `#[test_setup]` attribute is an experimental feature
+#![test_teardown(teardown)] //~ ERROR the `#[test_teardown]` attribute is an experimental feature
+
+fn setup() {}
+fn teardown() {}
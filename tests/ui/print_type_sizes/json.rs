@@ -0,0 +1,17 @@
+// compile-flags: -Z print-type-sizes -Z print-type-sizes-format=json --crate-type=lib
+// build-pass
+
+// This file illustrates the JSON output of `-Z print-type-sizes`: padding is
+// reported per field, and niche usage is recorded for every type.
+
+#![allow(dead_code)]
+
+struct S {
+    a: bool,
+    g: i32,
+}
+
+enum E {
+    A(S),
+    B,
+}
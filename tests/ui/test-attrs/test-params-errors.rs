@@ -0,0 +1,18 @@
+// compile-flags: --test
+
+#![feature(test)]
+#![feature(test_params)]
+
+extern crate test;
+
+#[test]
+#[test_params(1)]
+#[test_params(1, 2)] //~ ERROR `#[test_params]` must provide one argument for each parameter
+fn wrong_count(_: u32) {}
+
+#[bench]
+#[test_params(1)] //~ ERROR `#[test_params]` cannot be used on benchmarks
+fn bench(_: &mut test::Bencher) {}
+
+#[test]
+fn no_params(_: u32) {} //~ ERROR functions used as tests can not have any arguments
@@ -0,0 +1,11 @@
+// compile-flags: --test
+
+#![feature(test_params)]
+#![allow(dead_code)]
+
+#[test_params(1)] //~ ERROR `#[test_params]` can only be used on functions marked with `#[test]`
+fn not_a_test(_: u32) {}
+
+#[test]
+#[test_params(1)]
+fn a_test(_: u32) {}
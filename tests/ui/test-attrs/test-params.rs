@@ -0,0 +1,34 @@
+// compile-flags: --test
+// run-fail
+// run-flags: --test-threads=1
+// check-run-results
+// exec-env:RUST_BACKTRACE=0
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+// ignore-emscripten no threads support
+// needs-unwind
+
+#![feature(test_params)]
+
+#[test]
+#[test_params(0, true)]
+#[test_params(1, false)]
+#[test_params(3, true)]
+fn is_even(n: u32, expected: bool) {
+    assert_eq!(n % 2 == 0, expected);
+}
+
+#[test]
+#[test_params("")]
+#[test_params(&format!("{}", 256))]
+#[should_panic]
+fn parse_fails(s: &str) {
+    s.parse::<u8>().unwrap();
+}
+
+mod m {
+    #[test]
+    #[test_params(vec![1, 2], 3)]
+    fn sum(v: Vec<u32>, expected: u32) -> Result<(), String> {
+        if v.iter().sum::<u32>() == expected { Ok(()) } else { Err("bad sum".into()) }
+    }
+}
@@ -0,0 +1,39 @@
+// compile-flags: --test
+// run-fail
+// run-flags: --test-threads=1
+// check-run-results
+// exec-env:RUST_BACKTRACE=0
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+// ignore-emscripten no threads support
+// needs-unwind
+
+#![feature(test_hooks)]
+#![test_setup(setup)]
+#![test_teardown(teardown)]
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+static SET_UP: AtomicBool = AtomicBool::new(false);
+static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+fn setup() {
+    println!("setup");
+    SET_UP.store(true, Ordering::SeqCst);
+}
+
+// Runs even though one of the tests failed.
+fn teardown() {
+    println!("teardown after {} tests", RUNS.load(Ordering::SeqCst));
+}
+
+#[test]
+fn fails() {
+    RUNS.fetch_add(1, Ordering::SeqCst);
+    assert_eq!(1 + 1, 3);
+}
+
+#[test]
+fn runs_after_setup() {
+    RUNS.fetch_add(1, Ordering::SeqCst);
+    assert!(SET_UP.load(Ordering::SeqCst));
+}